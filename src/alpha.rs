@@ -0,0 +1,362 @@
+//! This module implements [`Alpha`], a wrapper that adds an opacity to any [`Color`]. Scarlet's
+//! color types only describe the color of light, so transparency is kept separately and carried
+//! through conversions unchanged. Mixing colors with transparency is done with *premultiplied*
+//! alpha, as in CSS and most compositing software: each color is weighted by its opacity, so that a
+//! nearly transparent color has very little influence on the mix.
+//!
+//! [`Alpha`]: struct.Alpha.html
+//! [`Color`]: ../color/trait.Color.html
+
+use color::{hex_digits, Color, RGBColor};
+use colorpoint::{ColorCalcError, ColorPoint};
+use colors::hslcolor::HSLColor;
+use coord::Coord;
+use csscolor::{parse_hsla_hsva_tuple, parse_rgba_str, strip_function_name};
+use parseerror::{ColorParseError, ParseGrammar};
+use std::fmt;
+use std::str::FromStr;
+
+/// A color with an alpha (opacity) value. An alpha of 1 is fully opaque and an alpha of 0 is fully
+/// transparent. The inner color can be any [`Color`](../color/trait.Color.html), and converting
+/// it to another color type keeps the alpha as is.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::alpha::Alpha;
+/// # use scarlet::colors::CIELABColor;
+/// let translucent: Alpha<RGBColor> = "rgba(255, 0, 0, 0.5)".parse().unwrap();
+/// let lab: Alpha<CIELABColor> = translucent.convert();
+/// assert_eq!(lab.alpha, 0.5);
+/// // mixing with a transparent color doesn't darken the result, because the transparent color
+/// // carries no weight
+/// let transparent = Alpha::new(RGBColor{r: 0., g: 0., b: 0.}, 0.);
+/// let mix = translucent.midpoint(transparent);
+/// assert_eq!(mix.color.to_string(), "#FF0000");
+/// assert_eq!(mix.alpha, 0.25);
+/// assert_eq!(mix.to_string(), "#FF000040");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Alpha<C: Color> {
+    /// The color, without any transparency.
+    pub color: C,
+    /// The opacity of the color. Ranges from 0, fully transparent, to 1, fully opaque.
+    pub alpha: f64,
+}
+
+impl<C: Color> Alpha<C> {
+    /// Creates a new color with the given opacity.
+    pub fn new(color: C, alpha: f64) -> Alpha<C> {
+        Alpha { color, alpha }
+    }
+    /// Creates a fully opaque color.
+    pub fn opaque(color: C) -> Alpha<C> {
+        Alpha { color, alpha: 1. }
+    }
+    /// Converts the inner color to another color type, keeping the same alpha.
+    pub fn convert<T: Color>(&self) -> Alpha<T> {
+        Alpha {
+            color: self.color.convert(),
+            alpha: self.alpha,
+        }
+    }
+}
+
+impl<C: ColorPoint> Alpha<C> {
+    /// Gets the coordinates of the inner color multiplied by alpha. Interpolating these, and then
+    /// dividing by the interpolated alpha, is how premultiplied mixing works.
+    pub fn premultiplied(&self) -> Coord {
+        let coord: Coord = self.color.into();
+        coord * self.alpha
+    }
+    /// Creates a color from premultiplied coordinates and an alpha, undoing
+    /// [`premultiplied`](#method.premultiplied). If the alpha is 0, the color can't be recovered,
+    /// and the coordinates are used as is.
+    pub fn from_premultiplied(coord: Coord, alpha: f64) -> Alpha<C> {
+        let color = if alpha == 0. { coord } else { coord / alpha };
+        Alpha {
+            color: C::from(color),
+            alpha,
+        }
+    }
+    /// Returns the weighted average of a given set of colors, like
+    /// [`ColorPoint::weighted_average`](../colorpoint/trait.ColorPoint.html#method.weighted_average),
+    /// but with premultiplied alpha: the alpha is the weighted average of the alphas, and each color
+    /// is weighted by its alpha as well. If every color is fully transparent, the colors are averaged
+    /// without premultiplication.
+    /// # Errors
+    /// Returns `ColorCalcError::MismatchedWeights` if the number of colors (`self` and anything in
+    /// `others`) and the number of weights mismatch.
+    pub fn weighted_average(
+        self,
+        others: Vec<Alpha<C>>,
+        weights: Vec<f64>,
+    ) -> Result<Alpha<C>, ColorCalcError> {
+        if others.len() + 1 != weights.len() {
+            return Err(ColorCalcError::MismatchedWeights);
+        }
+        let norm: f64 = weights.iter().sum();
+        let mut alpha = self.alpha * weights[0] / norm;
+        let mut coord = self.premultiplied() * weights[0] / norm;
+        for i in 1..weights.len() {
+            alpha += others[i - 1].alpha * weights[i] / norm;
+            coord = coord + others[i - 1].premultiplied() * weights[i] / norm;
+        }
+        if alpha == 0. {
+            // no color has any weight: fall back to the average of the colors themselves
+            let colors = others.iter().map(|other| other.color).collect();
+            let color = self.color.weighted_average(colors, weights)?;
+            Ok(Alpha { color, alpha })
+        } else {
+            Ok(Alpha::from_premultiplied(coord, alpha))
+        }
+    }
+    /// Gets the weighted midpoint of two colors with premultiplied alpha. As in
+    /// [`ColorPoint::weighted_midpoint`](../colorpoint/trait.ColorPoint.html#method.weighted_midpoint),
+    /// a weight of 1 returns `self` and a weight of 0 returns `other`.
+    pub fn weighted_midpoint(self, other: Alpha<C>, weight: f64) -> Alpha<C> {
+        self.weighted_average(vec![other], vec![weight, 1. - weight])
+            .expect("Weights match colors.")
+    }
+    /// Gets the midpoint of two colors with premultiplied alpha.
+    pub fn midpoint(self, other: Alpha<C>) -> Alpha<C> {
+        self.weighted_midpoint(other, 0.5)
+    }
+    /// Returns a pointer to a function that maps floating-point values from 0 to 1 to colors, such
+    /// that 0 returns `self`, 1 returns `other`, and anything in between is mixed linearly with
+    /// premultiplied alpha, like
+    /// [`ColorPoint::gradient`](../colorpoint/trait.ColorPoint.html#method.gradient).
+    /// # Example
+    /// Fading a color out to transparency keeps it the same color the whole way.
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::alpha::Alpha;
+    /// let blue = Alpha::opaque(RGBColor::from_hex_code("#3a7bd5").unwrap());
+    /// let clear = Alpha::new(RGBColor{r: 1., g: 1., b: 1.}, 0.);
+    /// let fade = blue.gradient(&clear);
+    /// assert_eq!(fade(0.75).to_string(), "#3A7BD540");
+    /// ```
+    pub fn gradient(&self, other: &Alpha<C>) -> Box<dyn Fn(f64) -> Alpha<C>> {
+        // capture only the coordinates, as the color type itself needn't be 'static
+        let (c1, a1) = (self.premultiplied(), self.alpha);
+        let (c2, a2) = (other.premultiplied(), other.alpha);
+        let (raw1, raw2): (Coord, Coord) = (self.color.into(), other.color.into());
+        Box::new(move |x| {
+            let alpha = a2 * x + a1 * (1. - x);
+            if alpha == 0. {
+                Alpha::from_premultiplied(raw2.weighted_midpoint(&raw1, x), alpha)
+            } else {
+                Alpha::from_premultiplied(c2.weighted_midpoint(&c1, x), alpha)
+            }
+        })
+    }
+    /// Returns a Vector of colors that starts with this color, ends with the given other color, and
+    /// evenly transitions between colors with premultiplied alpha. The given `n` is the number of
+    /// additional colors to add.
+    pub fn gradient_scale(&self, other: &Alpha<C>, n: usize) -> Vec<Alpha<C>> {
+        (0..n + 2)
+            .map(|i| (*other).weighted_midpoint(*self, i as f64 / (n + 1) as f64))
+            .collect()
+    }
+}
+
+/// Gets the alpha value as an 8-bit integer, clamping it to the range 0-1 first.
+fn int_alpha(alpha: f64) -> u8 {
+    (alpha.clamp(0., 1.) * 255.).round() as u8
+}
+
+/// Formats an alpha value for CSS, using at most 3 decimal places.
+fn css_alpha(alpha: f64) -> f64 {
+    (alpha.clamp(0., 1.) * 1000.).round() / 1000.
+}
+
+impl Alpha<RGBColor> {
+    /// Given a hex code, returns the color it represents. Accepts the same formats as
+    /// [`RGBColor::from_hex_code`](../color/struct.RGBColor.html#method.from_hex_code), which are
+    /// opaque, as well as `"#rgba"` and `"#rrggbbaa"`, with or without the leading `#`.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::alpha::Alpha;
+    /// let color = Alpha::<RGBColor>::from_hex_code("#ff000080").unwrap();
+    /// assert_eq!(color.color.int_rgb_tup(), (255, 0, 0));
+    /// assert!((color.alpha - 128. / 255.).abs() <= 1e-15);
+    /// assert_eq!(Alpha::<RGBColor>::from_hex_code("#f008").unwrap().to_hex_code(), "#FF000088");
+    /// ```
+    pub fn from_hex_code(hex: &str) -> Result<Alpha<RGBColor>, ColorParseError> {
+        let digits = hex_digits(hex, &[3, 4, 6, 8], "3, 4, 6, or 8 hex digits")?;
+        // the digits are all checked, so parsing them can't fail
+        let component = |digits: &str| u8::from_str_radix(digits, 16).unwrap();
+        // split off the alpha, if there is one, and parse the rest as a normal hex code
+        let (rgb, alpha) = match digits.len() {
+            4 => (&digits[..3], component(&digits[3..]) * 17),
+            8 => (&digits[..6], component(&digits[6..])),
+            _ => (digits, 255),
+        };
+        Ok(Alpha {
+            color: RGBColor::from_hex_code(rgb)?,
+            alpha: f64::from(alpha) / 255.,
+        })
+    }
+    /// Gets the hex code of the color in the form `"#RRGGBBAA"`, with the alpha as the last
+    /// component. Like [`RGBColor`](../color/struct.RGBColor.html), components are clamped and
+    /// rounded to 8 bits.
+    pub fn to_hex_code(&self) -> String {
+        format!("{}{:02X}", self.color.to_string(), int_alpha(self.alpha))
+    }
+    /// Gets the color as a CSS `rgba()` function, such as `"rgba(255, 0, 0, 0.5)"`. The alpha is
+    /// rounded to 3 decimal places.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::alpha::Alpha;
+    /// let color = Alpha::new(RGBColor{r: 1., g: 0.5, b: 0.}, 0.25);
+    /// assert_eq!(color.to_rgba_string(), "rgba(255, 128, 0, 0.25)");
+    /// ```
+    pub fn to_rgba_string(&self) -> String {
+        let (r, g, b) = self.color.int_rgb_tup();
+        format!("rgba({}, {}, {}, {})", r, g, b, css_alpha(self.alpha))
+    }
+}
+
+impl fmt::Display for Alpha<RGBColor> {
+    /// Writes the color as a hex code with alpha, `"#RRGGBBAA"`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_hex_code())
+    }
+}
+
+impl FromStr for Alpha<RGBColor> {
+    type Err = ColorParseError;
+
+    /// Parses a hex code with or without alpha, an X11 color name (or `"transparent"`), or a CSS
+    /// `rgb()` or `rgba()` function.
+    fn from_str(s: &str) -> Result<Alpha<RGBColor>, ColorParseError> {
+        if s.eq_ignore_ascii_case("transparent") {
+            return Ok(Alpha::new(RGBColor::from((0, 0, 0)), 0.));
+        }
+        match ParseGrammar::guess(s) {
+            ParseGrammar::Hex => Alpha::<RGBColor>::from_hex_code(s),
+            ParseGrammar::Name => RGBColor::from_color_name(s).map(Alpha::opaque),
+            ParseGrammar::Functional => {
+                let (r, g, b, a) = parse_rgba_str(s)?;
+                Ok(Alpha::new(RGBColor::from((r, g, b)), a))
+            }
+        }
+    }
+}
+
+impl Alpha<HSLColor> {
+    /// Gets the color as a CSS `hsla()` function, such as `"hsla(120, 50%, 25%, 0.5)"`. The hue,
+    /// saturation, and lightness are rounded to integers, and the alpha to 3 decimal places.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::alpha::Alpha;
+    /// # use scarlet::colors::HSLColor;
+    /// let color: Alpha<HSLColor> = "hsla(120, 50%, 25%, 0.5)".parse().unwrap();
+    /// assert_eq!(color.to_hsla_string(), "hsla(120, 50%, 25%, 0.5)");
+    /// ```
+    pub fn to_hsla_string(&self) -> String {
+        format!(
+            "hsla({}, {}%, {}%, {})",
+            self.color.h.round(),
+            (self.color.s * 100.).round(),
+            (self.color.l * 100.).round(),
+            css_alpha(self.alpha)
+        )
+    }
+}
+
+impl FromStr for Alpha<HSLColor> {
+    type Err = ColorParseError;
+
+    /// Parses a CSS `hsl()` or `hsla()` function.
+    fn from_str(s: &str) -> Result<Alpha<HSLColor>, ColorParseError> {
+        // keep the opening parenthesis, which parse_hsla_hsva_tuple expects
+        let tup = strip_function_name(s, &["hsl", "hsla"], "'hsl' or 'hsla'")?;
+        let (h, sat, l, alpha) =
+            parse_hsla_hsva_tuple(tup).map_err(|e| e.offset(s.len() - tup.len()))?;
+        Ok(Alpha::new(HSLColor { h, s: sat, l }, alpha.unwrap_or(1.)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use colors::CIELABColor;
+
+    #[test]
+    fn test_alpha_conversion() {
+        let rgba = Alpha::new(RGBColor::from_hex_code("#bada55").unwrap(), 0.3);
+        let lab: Alpha<CIELABColor> = rgba.convert();
+        let back: Alpha<RGBColor> = lab.convert();
+        assert_eq!(back.alpha, 0.3);
+        assert!(back.color.distance(&rgba.color) <= 1e-10);
+    }
+    #[test]
+    fn test_premultiplied_average() {
+        let red = Alpha::new(RGBColor::from((255, 0, 0)), 1.);
+        let blue = Alpha::new(RGBColor::from((0, 0, 255)), 0.25);
+        let mix = red.weighted_average(vec![blue], vec![1., 1.]).unwrap();
+        assert!((mix.alpha - 0.625).abs() <= 1e-15);
+        // red has four times the weight of blue
+        assert!((mix.color.r - 0.8).abs() <= 1e-15);
+        assert!((mix.color.b - 0.2).abs() <= 1e-15);
+        // mismatched weights are an error
+        assert_eq!(
+            red.weighted_average(vec![blue], vec![1.]),
+            Err(ColorCalcError::MismatchedWeights)
+        );
+        // fully transparent colors fall back to a normal average
+        let clear_red = Alpha::new(red.color, 0.);
+        let clear_blue = Alpha::new(blue.color, 0.);
+        let mix = clear_red.midpoint(clear_blue);
+        assert_eq!(mix.alpha, 0.);
+        assert!((mix.color.r - 0.5).abs() <= 1e-15);
+    }
+    #[test]
+    fn test_alpha_gradient() {
+        let start = Alpha::new(RGBColor::from((0, 0, 0)), 1.);
+        let end = Alpha::new(RGBColor::from((255, 255, 255)), 0.5);
+        let grad = start.gradient(&end);
+        assert_eq!(grad(0.), start);
+        assert_eq!(grad(1.), end);
+        let scale = start.gradient_scale(&end, 3);
+        assert_eq!(scale.len(), 5);
+        assert_eq!(scale[2], grad(0.5));
+    }
+    #[test]
+    fn test_rgba_parsing() {
+        let hex: Alpha<RGBColor> = "#12345678".parse().unwrap();
+        assert_eq!(hex.color.int_rgb_tup(), (0x12, 0x34, 0x56));
+        assert_eq!(hex.to_string(), "#12345678");
+        let opaque: Alpha<RGBColor> = "#123456".parse().unwrap();
+        assert_eq!(opaque.alpha, 1.);
+        let named: Alpha<RGBColor> = "teal".parse().unwrap();
+        assert_eq!(named.to_string(), "#008080FF");
+        let func: Alpha<RGBColor> = "rgba(18, 52, 86, 40%)".parse().unwrap();
+        assert_eq!(func.to_rgba_string(), "rgba(18, 52, 86, 0.4)");
+        let round_trip: Alpha<RGBColor> = func.to_rgba_string().parse().unwrap();
+        assert_eq!(round_trip, func);
+        let clear: Alpha<RGBColor> = "transparent".parse().unwrap();
+        assert_eq!(clear.alpha, 0.);
+        assert!("#1234567".parse::<Alpha<RGBColor>>().is_err());
+        assert!("rgba(1, 2, 3, 4, 5)".parse::<Alpha<RGBColor>>().is_err());
+    }
+    #[test]
+    fn test_hsla_parsing() {
+        let hsla: Alpha<HSLColor> = "hsla(210, 40%, 60%, 0.75)".parse().unwrap();
+        assert_eq!(hsla.alpha, 0.75);
+        assert_eq!(hsla.to_hsla_string(), "hsla(210, 40%, 60%, 0.75)");
+        let hsl: Alpha<HSLColor> = "hsl(210, 40%, 60%)".parse().unwrap();
+        assert_eq!(hsl.alpha, 1.);
+        assert!("hsva(210, 40%, 60%, 0.75)"
+            .parse::<Alpha<HSLColor>>()
+            .is_err());
+    }
+}
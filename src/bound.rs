@@ -4,8 +4,31 @@
 
 use color::{Color, RGBColor};
 use colorpoint::ColorPoint;
+use colors::oklabcolor::OklabColor;
+use colors::oklchcolor::OklchColor;
 use coord::Coord;
 
+/// The just noticeable difference in Oklab that CSS gamut mapping allows clipping to cause.
+const GAMUT_MAP_JND: f64 = 0.02;
+/// How precisely CSS gamut mapping finds the chroma to clip at.
+const GAMUT_MAP_EPSILON: f64 = 0.0001;
+
+/// Returns whether every component of a point is within the given bounds, or outside of them by at
+/// most `tolerance`.
+fn within_bounds(point: Coord, bounds: [(f64, f64); 3], tolerance: f64) -> bool {
+    [point.x, point.y, point.z]
+        .iter()
+        .zip(bounds.iter())
+        .all(|(&c, &(min, max))| c >= min - tolerance && c <= max + tolerance)
+}
+
+/// Gets the Euclidean distance between two colors in Oklab, which CSS calls deltaEOK.
+fn delta_eok<T: Color, U: Color>(color1: &T, color2: &U) -> f64 {
+    let lab1: OklabColor = color1.convert();
+    let lab2: OklabColor = color2.convert();
+    lab1.euclidean_distance(lab2)
+}
+
 /// Describes a color space in which the total space of representable colors has explicit bounds
 /// besides those imposed by human vision. For example, an sRGB color can't have negative values for
 /// any of its components, whereas the CIELAB space can feasibly describe even those colors that
@@ -55,6 +78,27 @@ pub trait Bound: Color + ColorPoint {
             z: point_vals[2],
         }
     }
+    /// Returns whether the given color is in the bounds of this color space, allowing each component
+    /// to be outside of them by at most `tolerance`, in the units of this space. Conversions always
+    /// have a bit of rounding error, so a tolerance of 0 can reject colors that were computed to be
+    /// exactly on the edge of the gamut: something like 1e-6 avoids that.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::{CIELABColor, DisplayP3Color};
+    /// // a saturated green that wider gamuts can show, but sRGB can't
+    /// let green = CIELABColor{l: 80., a: -90., b: 70.};
+    /// assert!(!RGBColor::in_gamut(&green, 1e-6));
+    /// assert!(DisplayP3Color::in_gamut(&green, 1e-6));
+    /// // a larger tolerance lets colors that are only just outside through
+    /// assert!(!RGBColor::in_gamut(&RGBColor{r: 1.01, g: 0.5, b: 0.5}, 1e-6));
+    /// assert!(RGBColor::in_gamut(&RGBColor{r: 1.01, g: 0.5, b: 0.5}, 0.02));
+    /// ```
+    fn in_gamut<T: Color>(color: &T, tolerance: f64) -> bool {
+        let converted: Self = color.convert();
+        within_bounds(converted.into(), Self::bounds(), tolerance)
+    }
     /// Given a Color that can be embedded in 3D space, returns a new version of that color that is in
     /// the bounds of this color space, even if the coordinate systems of the two spaces differ. If
     /// the color is already in the gamut, it simply returns a copy. See [trait
@@ -64,6 +108,85 @@ pub trait Bound: Color + ColorPoint {
         let point: Coord = converted_color.into();
         Self::from(Self::clamp_coord(point)).convert()
     }
+    /// Given a Color, returns a new version of that color that is in the bounds of this color space,
+    /// using the gamut mapping algorithm from [CSS Color Module Level
+    /// 4](https://www.w3.org/TR/css-color-4/#binsearch). Unlike [`clamp`](#method.clamp), which
+    /// clamps each component separately and can change hue a lot, this keeps the hue and lightness
+    /// of the color in Oklch and only reduces its chroma, by binary search, until clamping it
+    /// changes it by less than a just noticeable difference: 0.02 in Oklab, or deltaEOK. That
+    /// clamped color is the result. Colors lighter than white become white and colors darker than
+    /// black become black, and colors already in gamut are simply copied. Whether a color is in
+    /// gamut is decided by [`in_gamut`](#method.in_gamut), so types that clamp when converting
+    /// override that to check the color before it's clamped.
+    /// # Example
+    /// A vivid cyan, far outside of sRGB. Clamping it turns it blue, but gamut mapping keeps its hue
+    /// close to the original.
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::{CIELCHColor, OklchColor};
+    /// let cyan = CIELCHColor{l: 60., c: 130., h: 260.};
+    /// let hue = cyan.convert::<OklchColor>().h;
+    /// let clamped: RGBColor = RGBColor::clamp(cyan).convert();
+    /// let mapped: RGBColor = RGBColor::gamut_map(cyan).convert();
+    /// assert!((clamped.convert::<OklchColor>().h - hue).abs() > 20.);
+    /// assert!((mapped.convert::<OklchColor>().h - hue).abs() < 5.);
+    /// // in gamut, up to rounding error
+    /// assert!(mapped.r <= 1. + 1e-6 && mapped.b >= -1e-6);
+    /// ```
+    fn gamut_map<T: Color>(color: T) -> T {
+        let origin: OklchColor = color.convert();
+        if origin.l >= 1.0 {
+            return OklchColor {
+                l: 1.0,
+                c: 0.0,
+                h: 0.0,
+            }
+            .convert::<Self>()
+            .convert();
+        } else if origin.l <= 0.0 {
+            return OklchColor {
+                l: 0.0,
+                c: 0.0,
+                h: 0.0,
+            }
+            .convert::<Self>()
+            .convert();
+        }
+        let in_gamut = |color: OklchColor| Self::in_gamut(&color, 0.0);
+        let clip =
+            |color: OklchColor| Self::from(Self::clamp_coord(color.convert::<Self>().into()));
+        if in_gamut(origin) {
+            return color.convert::<Self>().convert();
+        }
+        let mut current = origin;
+        let mut clipped = clip(current);
+        if delta_eok(&clipped, &current) < GAMUT_MAP_JND {
+            return clipped.convert();
+        }
+        // binary search for the chroma where clipping makes a just noticeable difference
+        let (mut min, mut max) = (0.0, origin.c);
+        let mut min_in_gamut = true;
+        while max - min > GAMUT_MAP_EPSILON {
+            current.c = (min + max) / 2.0;
+            if min_in_gamut && in_gamut(current) {
+                min = current.c;
+                continue;
+            }
+            clipped = clip(current);
+            let e = delta_eok(&clipped, &current);
+            if e < GAMUT_MAP_JND {
+                if GAMUT_MAP_JND - e < GAMUT_MAP_EPSILON {
+                    break;
+                }
+                min_in_gamut = false;
+                min = current.c;
+            } else {
+                max = current.c;
+            }
+        }
+        clipped.convert()
+    }
 }
 
 // implement Bound for the base colors in the color module, to avoid cluttering that more than it
@@ -79,8 +202,21 @@ mod tests {
     use super::Bound;
     use color::Color;
     use color::RGBColor;
+    use colors::adobergbcolor::AdobeRGBColor;
+    use colors::cielabcolor::CIELABColor;
+    use colors::cielchcolor::CIELCHColor;
+    use colors::cielchuvcolor::CIELCHuvColor;
+    use colors::cieluvcolor::CIELUVColor;
+    use colors::displayp3color::DisplayP3Color;
     use colors::hslcolor::HSLColor;
     use colors::hsvcolor::HSVColor;
+    use colors::oklchcolor::OklchColor;
+    use colors::rec2020color::Rec2020Color;
+
+    // the difference between two hues in degrees, going the short way around the circle
+    fn hue_shift(h1: f64, h2: f64) -> f64 {
+        ((h1 - h2 + 540.0) % 360.0 - 180.0).abs()
+    }
 
     #[test]
     fn test_zero_one_bounds() {
@@ -131,4 +267,132 @@ mod tests {
             },)
         );
     }
+
+    #[test]
+    fn test_gamut_map_in_gamut() {
+        let color = RGBColor {
+            r: 0.2,
+            g: 0.7,
+            b: 0.4,
+        };
+        let mapped = RGBColor::gamut_map(color);
+        assert!((mapped.r - 0.2).abs() <= 1e-10);
+        assert!((mapped.g - 0.7).abs() <= 1e-10);
+        assert!((mapped.b - 0.4).abs() <= 1e-10);
+    }
+
+    #[test]
+    fn test_gamut_map_keeps_hue() {
+        let (mut mapped_shift, mut clamped_shift) = (0.0, 0.0);
+        for &h in [0.0, 45.0, 90.0, 150.0, 200.0, 260.0, 320.0].iter() {
+            let color = CIELCHColor {
+                l: 60.0,
+                c: 130.0,
+                h,
+            };
+            let origin: OklchColor = color.convert();
+            let srgb: RGBColor = RGBColor::gamut_map(color).convert();
+            let p3: DisplayP3Color = DisplayP3Color::gamut_map(color).convert();
+            // converting back and forth leaves some rounding error
+            for &(r, g, b) in [(srgb.r, srgb.g, srgb.b), (p3.r, p3.g, p3.b)].iter() {
+                assert!([r, g, b].iter().all(|c| (-1e-6..=1.0 + 1e-6).contains(c)));
+            }
+            // clipping is allowed to make a just noticeable difference, which can shift hue a bit
+            for mapped in [srgb.convert::<OklchColor>(), p3.convert::<OklchColor>()].iter() {
+                assert!(
+                    hue_shift(mapped.h, origin.h) <= 6.0,
+                    "{} {}",
+                    origin.h,
+                    mapped.h
+                );
+                assert!((mapped.l - origin.l).abs() <= 0.03);
+                assert!(mapped.c < origin.c);
+            }
+            // the wider gamut keeps more chroma
+            assert!(p3.convert::<OklchColor>().c >= srgb.convert::<OklchColor>().c - 0.005);
+            mapped_shift += hue_shift(srgb.convert::<OklchColor>().h, origin.h);
+            let clamped: RGBColor = RGBColor::clamp(color).convert();
+            clamped_shift += hue_shift(clamped.convert::<OklchColor>().h, origin.h);
+        }
+        assert!(mapped_shift * 2.0 < clamped_shift);
+    }
+
+    #[test]
+    fn test_gamut_map_clamping_type() {
+        // Adobe RGB clamps when converting, which gamut mapping has to see past
+        let color = CIELCHColor {
+            l: 60.0,
+            c: 160.0,
+            h: 200.0,
+        };
+        let origin: OklchColor = color.convert();
+        let mapped: OklchColor = AdobeRGBColor::gamut_map(color).convert();
+        let clamped: OklchColor = AdobeRGBColor::clamp(color).convert();
+        assert!(hue_shift(mapped.h, origin.h) <= 2.0);
+        assert!(hue_shift(clamped.h, origin.h) > 10.0);
+        assert!(mapped.c < origin.c);
+    }
+
+    #[test]
+    fn test_gamut_map_extremes() {
+        let too_light = CIELABColor {
+            l: 120.0,
+            a: 30.0,
+            b: 0.0,
+        };
+        let white: RGBColor = RGBColor::gamut_map(too_light).convert();
+        assert_eq!(white.to_string(), "#FFFFFF");
+        let too_dark = CIELABColor {
+            l: -5.0,
+            a: 0.0,
+            b: -30.0,
+        };
+        let black: RGBColor = RGBColor::gamut_map(too_dark).convert();
+        assert_eq!(black.to_string(), "#000000");
+        // other bounded types work too
+        let hsl: HSLColor = HSLColor::gamut_map(CIELCHColor {
+            l: 50.0,
+            c: 150.0,
+            h: 300.0,
+        })
+        .convert();
+        assert!(hsl.s >= 0.0 && hsl.s <= 1.0 && hsl.l >= 0.0 && hsl.l <= 1.0);
+    }
+
+    #[test]
+    fn test_in_gamut() {
+        let color = RGBColor {
+            r: 0.5,
+            g: 1.0 + 1e-9,
+            b: -0.001,
+        };
+        assert!(!RGBColor::in_gamut(&color, 0.0));
+        assert!(!RGBColor::in_gamut(&color, 1e-6));
+        assert!(RGBColor::in_gamut(&color, 0.01));
+        assert!(HSLColor::in_gamut(&RGBColor::gamut_map(color), 1e-6));
+        // the primaries of Rec. 2020 are real colors, so they're in the bounds of every CIE space
+        for &(r, g, b) in [
+            (1.0, 0.0, 0.0),
+            (0.0, 1.0, 0.0),
+            (0.0, 0.0, 1.0),
+            (1.0, 1.0, 1.0),
+        ]
+        .iter()
+        {
+            let primary = Rec2020Color { r, g, b };
+            assert!(CIELABColor::in_gamut(&primary, 1e-6));
+            assert!(CIELCHColor::in_gamut(&primary, 1e-6));
+            assert!(CIELUVColor::in_gamut(&primary, 1e-6));
+            assert!(CIELCHuvColor::in_gamut(&primary, 1e-6));
+        }
+        let imaginary = CIELABColor {
+            l: 50.0,
+            a: 230.0,
+            b: 0.0,
+        };
+        assert!(!CIELABColor::in_gamut(&imaginary, 1e-6));
+        assert!(!CIELCHColor::in_gamut(&imaginary, 1e-6));
+        let mapped: CIELABColor = CIELABColor::gamut_map(imaginary);
+        assert!(CIELABColor::in_gamut(&mapped, 1e-6));
+    }
 }
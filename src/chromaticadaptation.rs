@@ -0,0 +1,334 @@
+//! This module implements a choice of chromatic adaptation transforms through
+//! [`ChromaticAdaptation`]. A chromatic adaptation transform predicts what a color seen under one
+//! illuminant looks like under another, by modeling the way the eye adjusts its sensitivity to the
+//! light around it. Every transform here works the same way, as a "von Kries" transform: convert to
+//! a space of cone-like responses, scale each response by the ratio of the two white points, and
+//! convert back. They differ in the space they scale in, and so give slightly different answers.
+//!
+//! Scarlet uses Bradford with complete adaptation whenever it converts between illuminants, as in
+//! [`XYZColor::color_adapt`]. Other color-managed tools make other choices: ICC profiles use
+//! Bradford, CIECAM02 uses CAT02, CAM16 uses CAT16, and some older software uses plain XYZ scaling.
+//! To match their results, use [`XYZColor::color_adapt_with`] or get the matrix directly with
+//! [`ChromaticAdaptation::matrix`].
+//!
+//! [`ChromaticAdaptation`]: enum.ChromaticAdaptation.html
+//! [`ChromaticAdaptation::matrix`]: enum.ChromaticAdaptation.html#method.matrix
+//! [`XYZColor::color_adapt`]: ../color/struct.XYZColor.html#method.color_adapt
+//! [`XYZColor::color_adapt_with`]: ../color/struct.XYZColor.html#method.color_adapt_with
+
+use color::XYZColor;
+use consts;
+use illuminants::Illuminant;
+use rulinalg::matrix::decomposition::PartialPivLu;
+use rulinalg::matrix::{BaseMatrix, Matrix};
+use rulinalg::vector::Vector;
+
+/// A chromatic adaptation transform, defined by the matrix that takes XYZ to the cone-like
+/// responses it scales.
+/// # Example
+/// Different transforms agree on the white point, but not on anything else.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::chromaticadaptation::ChromaticAdaptation;
+/// let red = RGBColor{r: 0.9, g: 0.1, b: 0.1}.to_xyz(Illuminant::D65);
+/// let bradford = red.color_adapt_with(Illuminant::D50, ChromaticAdaptation::Bradford, 1.0);
+/// let scaling = red.color_adapt_with(Illuminant::D50, ChromaticAdaptation::XYZScaling, 1.0);
+/// // color_adapt uses Bradford
+/// assert!(bradford.approx_equal(&red.color_adapt(Illuminant::D50)));
+/// assert!(!bradford.approx_equal(&scaling));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ChromaticAdaptation {
+    /// Scales X, Y, and Z directly. This is the simplest transform and the least accurate, as XYZ
+    /// is nothing like the responses of the cones.
+    XYZScaling,
+    /// The classic von Kries transform, scaling in the Hunt-Pointer-Estévez cone fundamentals
+    /// normalized to D65.
+    VonKries,
+    /// The Bradford transform, from Lam's 1985 thesis, without its small nonlinearity in the blue
+    /// channel. This is what ICC profiles and Scarlet itself use.
+    Bradford,
+    /// The transform of the CIECAM02 color appearance model.
+    CAT02,
+    /// The transform of the CAM16 color appearance model, which fixes some problems CAT02 has with
+    /// highly saturated colors.
+    CAT16,
+    /// The "sharp" transform of Finlayson and Süsstrunk, optimized to keep the responses as narrow
+    /// as possible.
+    Sharp,
+    /// The transform of the CMCCAT2000 model of the Colour Measurement Committee. The original model
+    /// computes its degree of adaptation from the luminance of both fields, which callers can do
+    /// themselves and pass in.
+    CMCCAT2000,
+}
+
+impl Default for ChromaticAdaptation {
+    /// Bradford, which is what Scarlet uses everywhere else.
+    fn default() -> ChromaticAdaptation {
+        ChromaticAdaptation::Bradford
+    }
+}
+
+impl ChromaticAdaptation {
+    /// Gets the matrix that takes XYZ to cone responses, along with its decomposition for inverting
+    /// it accurately.
+    fn transform(self) -> (&'static Matrix<f64>, &'static PartialPivLu<f64>) {
+        match self {
+            ChromaticAdaptation::XYZScaling => (
+                &consts::XYZ_SCALING_TRANSFORM,
+                &consts::XYZ_SCALING_TRANSFORM_LU,
+            ),
+            ChromaticAdaptation::VonKries => (
+                &consts::VON_KRIES_TRANSFORM,
+                &consts::VON_KRIES_TRANSFORM_LU,
+            ),
+            ChromaticAdaptation::Bradford => {
+                (&consts::BRADFORD_TRANSFORM, &consts::BRADFORD_TRANSFORM_LU)
+            }
+            ChromaticAdaptation::CAT02 => (&consts::CAT02_TRANSFORM, &consts::CAT02_TRANSFORM_LU),
+            ChromaticAdaptation::CAT16 => (&consts::CAT16_TRANSFORM, &consts::CAT16_TRANSFORM_LU),
+            ChromaticAdaptation::Sharp => (&consts::SHARP_TRANSFORM, &consts::SHARP_TRANSFORM_LU),
+            ChromaticAdaptation::CMCCAT2000 => (
+                &consts::CMCCAT2000_TRANSFORM,
+                &consts::CMCCAT2000_TRANSFORM_LU,
+            ),
+        }
+    }
+    /// Gets the cone responses of the white points of two illuminants.
+    fn white_responses(self, from: Illuminant, to: Illuminant) -> (Vector<f64>, Vector<f64>) {
+        let (cone, _) = self.transform();
+        // because each white point has already been normalized to Y = 1, there's no need to scale
+        // by the ratio of luminances
+        let rgb_w = cone * Vector::from(from.white_point().to_vec());
+        let rgb_wr = cone * Vector::from(to.white_point().to_vec());
+        (rgb_w, rgb_wr)
+    }
+    /// Gets the matrix that takes XYZ to the cone-like responses this transform scales, as rows.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::chromaticadaptation::ChromaticAdaptation;
+    /// let cat02 = ChromaticAdaptation::CAT02.cone_response_matrix();
+    /// assert_eq!(cat02[0], [0.7328, 0.4296, -0.1624]);
+    /// ```
+    pub fn cone_response_matrix(self) -> [[f64; 3]; 3] {
+        let (cone, _) = self.transform();
+        let mut rows = [[0.0; 3]; 3];
+        for (i, row) in cone.row_iter().enumerate() {
+            rows[i].copy_from_slice(row.raw_slice());
+        }
+        rows
+    }
+    /// Gets the 3×3 matrix, as rows, that adapts XYZ colors seen under `from` to colors seen under
+    /// `to`. `degree` is the degree of adaptation *D*, between 0 and 1: 1 is complete adaptation,
+    /// which is right for surface colors, and 0 is no adaptation at all. For a value between, see
+    /// [`degree_of_adaptation`](#method.degree_of_adaptation).
+    /// # Example
+    /// This gives the well-known Bradford matrix for D65 to D50.
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::chromaticadaptation::ChromaticAdaptation;
+    /// let m = ChromaticAdaptation::Bradford.matrix(Illuminant::D65, Illuminant::D50, 1.0);
+    /// let expected = [[1.0478112, 0.0228866, -0.0501270],
+    ///                 [0.0295424, 0.9904844, -0.0170491],
+    ///                 [-0.0092345, 0.0150436, 0.7521316]];
+    /// for i in 0..3 {
+    ///     for j in 0..3 {
+    ///         assert!((m[i][j] - expected[i][j]).abs() <= 1e-5);
+    ///     }
+    /// }
+    /// ```
+    pub fn matrix(self, from: Illuminant, to: Illuminant, degree: f64) -> [[f64; 3]; 3] {
+        let (cone, lu) = self.transform();
+        let (rgb_w, rgb_wr) = self.white_responses(from, to);
+        let mut gains = [0.0; 3];
+        for i in 0..3 {
+            gains[i] = degree * rgb_wr[i] / rgb_w[i] + (1.0 - degree);
+        }
+        let scaled = Matrix::from_diag(&gains) * cone;
+        let adapt = lu.inverse().expect("Matrix is invertible.") * scaled;
+        let mut rows = [[0.0; 3]; 3];
+        for (i, row) in adapt.row_iter().enumerate() {
+            rows[i].copy_from_slice(row.raw_slice());
+        }
+        rows
+    }
+    /// Adapts a color to a new illuminant with this transform and the given degree of adaptation
+    /// *D*, between 0 and 1. This is the same as [`XYZColor::color_adapt_with`].
+    ///
+    /// [`XYZColor::color_adapt_with`]: ../color/struct.XYZColor.html#method.color_adapt_with
+    pub fn adapt(self, color: XYZColor, to: Illuminant, degree: f64) -> XYZColor {
+        // no need to transform if same illuminant
+        if color.illuminant == to {
+            return color;
+        }
+        let (cone, lu) = self.transform();
+        let (rgb_w, rgb_wr) = self.white_responses(color.illuminant, to);
+        let rgb = cone * vector![color.x, color.y, color.z];
+        // blend the fully adapted responses with the unadapted ones: this keeps complete
+        // adaptation a purely linear scaling, without any extra rounding error
+        let mut rgb_c = [0.0; 3];
+        for i in 0..3 {
+            rgb_c[i] = degree * (rgb[i] * rgb_wr[i] / rgb_w[i]) + (1.0 - degree) * rgb[i];
+        }
+        // convert back to XYZ using LU decomposition for accuracy
+        let xyz_c = lu
+            .solve(vector![rgb_c[0], rgb_c[1], rgb_c[2]])
+            .expect("Matrix is invertible.");
+        XYZColor {
+            x: xyz_c[0],
+            y: xyz_c[1],
+            z: xyz_c[2],
+            illuminant: to,
+        }
+    }
+    /// Estimates the degree of adaptation *D* of an observer, as CIECAM02 and CAM16 do, from the
+    /// surround factor *F* (1 for an average surround, 0.9 for dim, and 0.8 for dark) and the
+    /// luminance of the adapting field in cd/m². Brighter light means more complete adaptation. The
+    /// result is between 0 and 1.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::chromaticadaptation::ChromaticAdaptation;
+    /// let dim_room = ChromaticAdaptation::degree_of_adaptation(1.0, 4.0);
+    /// let office = ChromaticAdaptation::degree_of_adaptation(1.0, 100.0);
+    /// assert!(dim_room < office && office < 1.0);
+    /// ```
+    pub fn degree_of_adaptation(surround_factor: f64, adapting_luminance: f64) -> f64 {
+        let d = surround_factor * (1.0 - (1.0 / 3.6) * ((-adapting_luminance - 42.0) / 92.0).exp());
+        d.clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use consts::TEST_PRECISION;
+
+    const METHODS: [ChromaticAdaptation; 7] = [
+        ChromaticAdaptation::XYZScaling,
+        ChromaticAdaptation::VonKries,
+        ChromaticAdaptation::Bradford,
+        ChromaticAdaptation::CAT02,
+        ChromaticAdaptation::CAT16,
+        ChromaticAdaptation::Sharp,
+        ChromaticAdaptation::CMCCAT2000,
+    ];
+
+    fn assert_matrix_close(m: [[f64; 3]; 3], expected: [[f64; 3]; 3], precision: f64) {
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!(
+                    (m[i][j] - expected[i][j]).abs() <= precision,
+                    "{:?} != {:?}",
+                    m,
+                    expected
+                );
+            }
+        }
+    }
+
+    fn apply(m: [[f64; 3]; 3], xyz: [f64; 3]) -> [f64; 3] {
+        let mut out = [0.0; 3];
+        for i in 0..3 {
+            out[i] = m[i][0] * xyz[0] + m[i][1] * xyz[1] + m[i][2] * xyz[2];
+        }
+        out
+    }
+
+    #[test]
+    fn test_reference_matrices() {
+        // values from Bruce Lindbloom's site, which uses a D65 Z of 1.08883: hence the precision
+        assert_matrix_close(
+            ChromaticAdaptation::Bradford.matrix(Illuminant::D65, Illuminant::D50, 1.0),
+            [
+                [1.0478112, 0.0228866, -0.0501270],
+                [0.0295424, 0.9904844, -0.0170491],
+                [-0.0092345, 0.0150436, 0.7521316],
+            ],
+            1e-5,
+        );
+        assert_matrix_close(
+            ChromaticAdaptation::XYZScaling.matrix(Illuminant::D65, Illuminant::D50, 1.0),
+            [
+                [0.96422 / 0.95047, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.82521 / 1.08884],
+            ],
+            TEST_PRECISION,
+        );
+    }
+    #[test]
+    fn test_white_maps_to_white() {
+        let d65 = Illuminant::D65.white_point();
+        let d50 = Illuminant::D50.white_point();
+        for method in METHODS.iter() {
+            let white = apply(method.matrix(Illuminant::D65, Illuminant::D50, 1.0), d65);
+            for i in 0..3 {
+                assert!((white[i] - d50[i]).abs() <= TEST_PRECISION, "{:?}", method);
+            }
+        }
+    }
+    #[test]
+    fn test_partial_adaptation() {
+        let identity = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        for method in METHODS.iter() {
+            assert_matrix_close(
+                method.matrix(Illuminant::D65, Illuminant::D50, 0.0),
+                identity,
+                TEST_PRECISION,
+            );
+        }
+        // halfway adaptation lands between the two whites
+        let d65 = Illuminant::D65.white_point();
+        let d50 = Illuminant::D50.white_point();
+        let m = ChromaticAdaptation::CAT02.matrix(Illuminant::D65, Illuminant::D50, 0.5);
+        let half = apply(m, d65);
+        assert!(d50[0] > half[0] && half[0] > d65[0]);
+        assert!(d50[2] < half[2] && half[2] < d65[2]);
+    }
+    #[test]
+    fn test_adapt_matches_matrix() {
+        let color = XYZColor {
+            x: 0.3,
+            y: 0.2,
+            z: 0.6,
+            illuminant: Illuminant::D65,
+        };
+        for method in METHODS.iter() {
+            let adapted = method.adapt(color, Illuminant::D55, 0.8);
+            let expected = apply(
+                method.matrix(Illuminant::D65, Illuminant::D55, 0.8),
+                [color.x, color.y, color.z],
+            );
+            assert!((adapted.x - expected[0]).abs() <= TEST_PRECISION);
+            assert!((adapted.y - expected[1]).abs() <= TEST_PRECISION);
+            assert!((adapted.z - expected[2]).abs() <= TEST_PRECISION);
+            assert_eq!(adapted.illuminant, Illuminant::D55);
+        }
+    }
+    #[test]
+    fn test_round_trip() {
+        let color = XYZColor {
+            x: 0.5,
+            y: 0.4,
+            z: 0.1,
+            illuminant: Illuminant::D50,
+        };
+        for method in METHODS.iter() {
+            let there = method.adapt(color, Illuminant::D75, 1.0);
+            let back = method.adapt(there, Illuminant::D50, 1.0);
+            assert!(color.approx_equal(&back), "{:?}", method);
+        }
+    }
+    #[test]
+    fn test_degree_of_adaptation() {
+        // CIECAM02 worked example: L_A = 318.31, average surround
+        assert!((ChromaticAdaptation::degree_of_adaptation(1.0, 318.31) - 0.9945).abs() <= 1e-4);
+        assert!(ChromaticAdaptation::degree_of_adaptation(0.8, 0.0) < 0.8);
+        assert!(ChromaticAdaptation::degree_of_adaptation(1.0, 1e6) <= 1.0);
+    }
+}
@@ -35,15 +35,18 @@ use std::str::FromStr;
 use std::string::ToString;
 
 use super::coord::Coord;
+use chromaticadaptation::ChromaticAdaptation;
+use colors::cam16ucscolor::CAM16UCSColor;
 use colors::cielabcolor::CIELABColor;
 use colors::cielchcolor::CIELCHColor;
+use colortemperature::ColorTemperature;
 use consts;
-use consts::BRADFORD_TRANSFORM as BRADFORD;
-use consts::BRADFORD_TRANSFORM_LU as BRADFORD_LU;
 use consts::STANDARD_RGB_TRANSFORM as SRGB;
 use consts::STANDARD_RGB_TRANSFORM_LU as SRGB_LU;
-use csscolor::{parse_rgb_str, CSSParseError};
+use csscolor::{parse_css_color, parse_rgb_str, CSSColor, CSSParseError};
+use deltae::DeltaE;
 use illuminants::Illuminant;
+use parseerror::{ColorParseError, ParseErrorKind, ParseGrammar};
 
 use rulinalg::vector::Vector;
 
@@ -137,45 +140,71 @@ impl XYZColor {
     /// println!("Gold: {}, White: {}", gold_rgb.to_string(), white_rgb.to_string());
     /// ```
     pub fn color_adapt(&self, other_illuminant: Illuminant) -> XYZColor {
-        // no need to transform if same illuminant
-        if other_illuminant == self.illuminant {
-            *self
-        } else {
-            // convert to Bradford RGB space
-            // &* needed because lazy_static uses a different type which implements Deref
-            let rgb = &*BRADFORD * vector![self.x, self.y, self.z];
-
-            // get the RGB values for the white point of the illuminant we are currently using and
-            // the one we want: wr here stands for "white reference", i.e., the one we're converting
-            // to
-            let rgb_w = &*BRADFORD * Vector::from(self.illuminant.white_point().to_vec());
-            let rgb_wr = &*BRADFORD * Vector::from(other_illuminant.white_point().to_vec());
-
-            // perform the transform
-            // this usually includes a parameter indicating how much you want to adapt, but it's
-            // assumed that we want total adaptation: D = 1. Maybe this could change someday?
-
-            // because each white point has already been normalized to Y = 1, we don't need ap
-            // factor for it, which simplifies calculation even more than setting D = 1 and makes it
-            // just a linear transform
-            // scale by the ratio of luminance: it should always be 1, but with rounding error it
-            // isn't
-            let r_c = rgb[0] * rgb_wr[0] / rgb_w[0];
-            let g_c = rgb[1] * rgb_wr[1] / rgb_w[1];
-            // there's a slight nonlinearity here that I will omit
-            let b_c = rgb[2] * rgb_wr[2] / rgb_w[2];
-            // convert back to XYZ using inverse of previous matrix
-
-            // using LU decomposition for accuracy
-            let xyz_c = BRADFORD_LU
-                .solve(vector![r_c, g_c, b_c])
-                .expect("Matrix is invertible.");
-            XYZColor {
-                x: xyz_c[0],
-                y: xyz_c[1],
-                z: xyz_c[2],
-                illuminant: other_illuminant,
-            }
+        // this usually includes a parameter indicating how much you want to adapt, but it's assumed
+        // that we want total adaptation: D = 1
+        self.color_adapt_with(other_illuminant, ChromaticAdaptation::Bradford, 1.0)
+    }
+    /// Converts from one illuminant to a different one, like [`color_adapt()`], but with a choice
+    /// of chromatic adaptation transform and a degree of adaptation `degree` between 0 and 1. A
+    /// degree of 1 means the observer is completely adapted to the new illuminant; anything less
+    /// leaves part of the old illuminant's cast, as happens with displays viewed in a dim room. See
+    /// the [`chromaticadaptation`] module for the available transforms.
+    ///
+    /// [`color_adapt()`]: #method.color_adapt
+    /// [`chromaticadaptation`]: ../chromaticadaptation/index.html
+    /// # Example
+    /// Matching the CIECAM02 convention of adapting with CAT02:
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::chromaticadaptation::ChromaticAdaptation;
+    /// # use scarlet::color::XYZColor;
+    /// let color = XYZColor{x: 0.2, y: 0.3, z: 0.4, illuminant: Illuminant::D65};
+    /// let full = color.color_adapt_with(Illuminant::D50, ChromaticAdaptation::CAT02, 1.0);
+    /// let none = color.color_adapt_with(Illuminant::D50, ChromaticAdaptation::CAT02, 0.0);
+    /// assert_eq!(full.illuminant, Illuminant::D50);
+    /// // with no adaptation, the coordinates stay the same
+    /// assert!((none.z - color.z).abs() <= 1e-10);
+    /// assert!(full.z < none.z);
+    /// ```
+    pub fn color_adapt_with(
+        &self,
+        other_illuminant: Illuminant,
+        method: ChromaticAdaptation,
+        degree: f64,
+    ) -> XYZColor {
+        method.adapt(*self, other_illuminant, degree)
+    }
+    /// Gets the color of the light given off by a blackbody at the given temperature in kelvin, with
+    /// a luminance Y of 1. This is the color of incandescent light: a "warm white" bulb is about
+    /// 2700 K, and the filament of a halogen lamp about 3200 K. Lights like these rarely look
+    /// colored once the eye adapts to them, so the color is set as seen under D65, the white of
+    /// most displays: converting it to RGB shows the color of the light itself. To see it as
+    /// someone adapted to it would, set the illuminant to [`Illuminant::Planckian`] at the same
+    /// temperature.
+    ///
+    /// [`Illuminant::Planckian`]: ../illuminants/enum.Illuminant.html#variant.Planckian
+    /// # Panics
+    /// Panics if `kelvin` isn't positive, like [`Illuminant::planckian`].
+    ///
+    /// [`Illuminant::planckian`]: ../illuminants/enum.Illuminant.html#method.planckian
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::XYZColor;
+    /// let warm_white: RGBColor = XYZColor::from_blackbody(2700.).convert();
+    /// assert!(warm_white.r > warm_white.g && warm_white.g > warm_white.b);
+    /// let sky_blue: RGBColor = XYZColor::from_blackbody(12000.).convert();
+    /// assert!(sky_blue.b > sky_blue.r);
+    /// ```
+    pub fn from_blackbody(kelvin: f64) -> XYZColor {
+        let wp = Illuminant::planckian(kelvin).white_point();
+        XYZColor {
+            x: wp[0],
+            y: wp[1],
+            z: wp[2],
+            illuminant: Illuminant::D65,
         }
     }
     /// Returns `true` if the given other XYZ color's coordinates are all within acceptable error of
@@ -669,7 +698,8 @@ pub trait Color: Sized {
     /// works best with small differences, so keep that in mind: it's relatively hard to quantify
     /// whether bright pink and brown are more or less similar than bright blue and dark red.
     ///
-    /// For more, check out the [associated guide](../color_distance.html).
+    /// To use a different formula, or CIEDE2000 with different weights, see
+    /// [`delta_e`](#method.delta_e). For more, check out the [associated guide](../color_distance.html).
     ///
     /// # Examples
     ///
@@ -725,106 +755,53 @@ pub trait Color: Sized {
     /// assert!(green1.distance(&green2) / blue1.distance(&blue2) < 0.992);
     /// ```
     fn distance<T: Color>(&self, other: &T) -> f64 {
-        // implementation reference found here:
-        // https://pdfs.semanticscholar.org/969b/c38ea067dd22a47a44bcb59c23807037c8d8.pdf
-
-        // I'm going to match the notation in that text pretty much exactly: it's the only way to
-        // keep this both concise and readable
-
-        // first convert to LAB
-        let lab1: CIELABColor = self.convert();
-        let lab2: CIELABColor = other.convert();
-        // step 1: calculation of C and h
-        // the method hypot returns sqrt(a^2 + b^2)
-        let c_star_1: f64 = lab1.a.hypot(lab1.b);
-        let c_star_2: f64 = lab2.a.hypot(lab2.b);
-
-        let c_bar_ab: f64 = (c_star_1 + c_star_2) / 2.0;
-        let g = 0.5 * (1.0 - ((c_bar_ab.powi(7)) / (c_bar_ab.powi(7) + 25.0f64.powi(7))).sqrt());
-
-        let a_prime_1 = (1.0 + g) * lab1.a;
-        let a_prime_2 = (1.0 + g) * lab2.a;
-
-        let c_prime_1 = a_prime_1.hypot(lab1.b);
-        let c_prime_2 = a_prime_2.hypot(lab2.b);
-
-        // this closure simply does the atan2 like CIELCH, but safely accounts for a == b == 0
-        // we're gonna do this twice, so I just use a closure
-        let h_func = |a: f64, b: f64| {
-            if a == 0.0 && b == 0.0 {
-                0.0
-            } else {
-                let val = b.atan2(a).to_degrees();
-                if val < 0.0 {
-                    val + 360.0
-                } else {
-                    val
-                }
-            }
-        };
-
-        let h_prime_1 = h_func(a_prime_1, lab1.b);
-        let h_prime_2 = h_func(a_prime_2, lab2.b);
-
-        // step 2: computing delta L, delta C, and delta H
-        // take a deep breath, you got this!
-
-        let delta_l = lab2.l - lab1.l;
-        let delta_c = c_prime_2 - c_prime_1;
-        // essentially, compute the difference in hue but keep it in the right range
-        let delta_angle_h = if c_prime_1 * c_prime_2 == 0.0 {
-            0.0
-        } else if (h_prime_2 - h_prime_1).abs() <= 180.0 {
-            h_prime_2 - h_prime_1
-        } else if h_prime_2 - h_prime_1 > 180.0 {
-            h_prime_2 - h_prime_1 - 360.0
-        } else {
-            h_prime_2 - h_prime_1 + 360.0
-        };
-        // now get the Cartesian equivalent of the angle difference in hue
-        // this also corrects for chromaticity mattering less at low luminances
-        let delta_h =
-            2.0 * (c_prime_1 * c_prime_2).sqrt() * (delta_angle_h / 2.0).to_radians().sin();
-
-        // step 3: the color difference
-        // if you're reading this, it's not too late to back out
-        let l_bar_prime = (lab1.l + lab2.l) / 2.0;
-        let c_bar_prime = (c_prime_1 + c_prime_2) / 2.0;
-        let h_bar_prime = if c_prime_1 * c_prime_2 == 0.0 {
-            h_prime_1 + h_prime_2
-        } else if (h_prime_2 - h_prime_1).abs() <= 180.0 {
-            (h_prime_1 + h_prime_2) / 2.0
-        } else if h_prime_1 + h_prime_2 < 360.0 {
-            (h_prime_1 + h_prime_2 + 360.0) / 2.0
-        } else {
-            (h_prime_1 + h_prime_2 - 360.0) / 2.0
-        };
-
-        // we're gonna use this a lot
-        let deg_cos = |x: f64| x.to_radians().cos();
-
-        let t = 1.0 - 0.17 * deg_cos(h_bar_prime - 30.0)
-            + 0.24 * deg_cos(2.0 * h_bar_prime)
-            + 0.32 * deg_cos(3.0 * h_bar_prime + 6.0)
-            - 0.20 * deg_cos(4.0 * h_bar_prime - 63.0);
-
-        let delta_theta = 30.0 * (-((h_bar_prime - 275.0) / 25.0).powi(2)).exp();
-        let r_c = 2.0 * (c_bar_prime.powi(7) / (c_bar_prime.powi(7) + 25.0f64.powi(7))).sqrt();
-        let s_l = 1.0
-            + ((0.015 * (l_bar_prime - 50.0).powi(2))
-                / (20.0 + (l_bar_prime - 50.0).powi(2)).sqrt());
-        let s_c = 1.0 + 0.045 * c_bar_prime;
-        let s_h = 1.0 + 0.015 * c_bar_prime * t;
-        let r_t = -r_c * (2.0 * delta_theta).to_radians().sin();
-        // finally, the end result
-        // in the original there are three parametric weights, used for weighting differences in
-        // lightness, chroma, or hue. In pretty much any application, including this one, all of
-        // these are 1, so they're omitted
-        ((delta_l / s_l).powi(2)
-            + (delta_c / s_c).powi(2)
-            + (delta_h / s_h).powi(2)
-            + r_t * (delta_c / s_c) * (delta_h / s_h))
-            .sqrt()
+        DeltaE::CIEDE2000_DEFAULT.between(self, other)
+    }
+    /// Computes the difference between this color, as the reference, and another with the given
+    /// color difference formula. This is the same as [`DeltaE::between`], and exists for
+    /// convenience. [`distance`](#method.distance) is the same as using
+    /// [`DeltaE::CIEDE2000_DEFAULT`].
+    ///
+    /// [`DeltaE::between`]: ../deltae/enum.DeltaE.html#method.between
+    /// [`DeltaE::CIEDE2000_DEFAULT`]: ../deltae/enum.DeltaE.html#associatedconstant.CIEDE2000_DEFAULT
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::deltae::DeltaE;
+    /// let standard = RGBColor::from_hex_code("#3a7bd5").unwrap();
+    /// let sample = RGBColor::from_hex_code("#3b7cd3").unwrap();
+    /// // a textile QA tolerance
+    /// assert!(standard.delta_e(&sample, DeltaE::CMC_2_1) < 1.0);
+    /// // CIE76 is much less forgiving for saturated colors
+    /// assert!(standard.delta_e(&sample, DeltaE::CIE76) > standard.delta_e(&sample, DeltaE::CMC_2_1));
+    /// ```
+    fn delta_e<T: Color>(&self, other: &T, formula: DeltaE) -> f64
+    where
+        Self: Sized,
+    {
+        formula.between(self, other)
+    }
+    /// Computes the distance between two colors as the Euclidean distance in
+    /// [CAM16-UCS](../colors/cam16ucscolor/index.html), using the default CAM16 viewing
+    /// conditions. This is a simpler alternative to CIEDE2000 that holds up better for large color
+    /// differences, where CIEDE2000's corrections no longer apply: like CIEDE2000, a distance of
+    /// about 1 is just noticeable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let color1 = RGBColor::from_hex_code("#123456").unwrap();
+    /// let color2 = RGBColor::from_hex_code("#123556").unwrap();
+    /// let color3 = RGBColor::from_hex_code("#333333").unwrap();
+    /// assert!(color1.cam16_distance(&color2) < 1.0);
+    /// assert!(color1.cam16_distance(&color3) > 1.0);
+    /// ```
+    fn cam16_distance<T: Color>(&self, other: &T) -> f64 {
+        let ucs1: CAM16UCSColor = self.convert();
+        let ucs2: CAM16UCSColor = other.convert();
+        ucs1.delta_e(&ucs2)
     }
     /// Using the metric that two colors with a CIEDE2000 distance of less than 1 are
     /// indistinguishable, determines whether two colors are visually distinguishable from each
@@ -846,6 +823,43 @@ pub trait Color: Sized {
     fn visually_indistinguishable<T: Color>(&self, other: &T) -> bool {
         self.distance(other) <= 1.0
     }
+    /// Gets the correlated color temperature (CCT) of this color, the temperature of the blackbody
+    /// closest in color, along with its distance Duv from the blackbody locus. This is how white
+    /// lights and display white points are classified. This uses Ohno's method: see the
+    /// [`colortemperature`] module for more. The color is taken as seen under D65, which leaves
+    /// [`XYZColor`] values as they are, so measured tristimulus values of a light can be used
+    /// directly.
+    ///
+    /// [`colortemperature`]: ../colortemperature/index.html
+    /// [`XYZColor`]: struct.XYZColor.html
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::XYZColor;
+    /// // sRGB white is D65
+    /// let white = RGBColor::from_hex_code("#ffffff").unwrap().cct();
+    /// assert!((white.cct - 6504.).abs() <= 2.);
+    /// let warm = XYZColor::from_blackbody(3000.).cct();
+    /// assert!((warm.cct - 3000.).abs() <= 0.01);
+    /// assert!(warm.duv.abs() <= 1e-6);
+    /// ```
+    fn cct(&self) -> ColorTemperature {
+        ColorTemperature::from_xyz(self.to_xyz(Illuminant::D65))
+    }
+    /// Approximates the correlated color temperature of this color with McCamy's formula. This is
+    /// much faster than [`cct`](#method.cct), but is only accurate to about 10 kelvin for colors
+    /// near the blackbody locus between about 2800 K and 6500 K.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+    /// assert!((white.approximate_cct() - white.cct().cct).abs() <= 2.);
+    /// ```
+    fn approximate_cct(&self) -> f64 {
+        ColorTemperature::mccamy(self.to_xyz(Illuminant::D65))
+    }
 }
 
 impl Color for XYZColor {
@@ -1090,7 +1104,10 @@ impl Color for RGBColor {
     }
 }
 
-/// An error type that results from an invalid attempt to convert a string into an RGB color.
+/// An error type that results from an invalid attempt to convert a string into an RGB color. The
+/// parsers now return the more detailed [`ColorParseError`], which converts into this.
+///
+/// [`ColorParseError`]: ../parseerror/struct.ColorParseError.html
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
 pub enum RGBParseError {
     /// This indicates that function syntax was acceptable, but the numbers were out of range, such as
@@ -1122,6 +1139,16 @@ impl From<CSSParseError> for RGBParseError {
     }
 }
 
+impl From<ColorParseError> for RGBParseError {
+    fn from(err: ColorParseError) -> RGBParseError {
+        match err.grammar {
+            ParseGrammar::Hex => RGBParseError::InvalidHexSyntax,
+            ParseGrammar::Name => RGBParseError::InvalidX11Name,
+            ParseGrammar::Functional => RGBParseError::InvalidFuncSyntax,
+        }
+    }
+}
+
 impl Error for RGBParseError {
     fn description(&self) -> &str {
         match *self {
@@ -1133,6 +1160,39 @@ impl Error for RGBParseError {
     }
 }
 
+/// Checks that a hex code, with or without a leading `#`, has only hex digits and that the number of
+/// digits is in `lengths`, returning the digits. `expected` describes the allowed lengths for the
+/// error if it isn't.
+pub(crate) fn hex_digits<'a>(
+    hex: &'a str,
+    lengths: &[usize],
+    expected: &'static str,
+) -> Result<&'a str, ColorParseError> {
+    let start = if hex.starts_with('#') { 1 } else { 0 };
+    let digits = &hex[start..];
+    if let Some((i, c)) = digits
+        .char_indices()
+        .find(|&(_i, c)| !c.is_ascii_hexdigit())
+    {
+        let i = start + i;
+        return Err(ColorParseError::new(
+            ParseErrorKind::InvalidCharacters,
+            ParseGrammar::Hex,
+            i..i + c.len_utf8(),
+        )
+        .expecting("a hex digit"));
+    }
+    if !lengths.contains(&digits.len()) {
+        return Err(ColorParseError::new(
+            ParseErrorKind::InvalidSyntax,
+            ParseGrammar::Hex,
+            start..hex.len(),
+        )
+        .expecting(expected));
+    }
+    Ok(digits)
+}
+
 impl RGBColor {
     /// Given a string that represents a hex code, returns the RGB color that the given hex code
     /// represents. Four formats are accepted: `"#rgb"` as a shorthand for `"#rrggbb"`, `#rrggbb` by
@@ -1142,57 +1202,36 @@ impl RGBColor {
     ///
     /// ```
     /// # use scarlet::prelude::*;
-    /// # fn try_main() -> Result<(), RGBParseError> {
+    /// # fn try_main() -> Result<(), ColorParseError> {
     /// let fuchsia = RGBColor::from_hex_code("#ff00ff")?;
     /// // if 3 digits, interprets as doubled
     /// let fuchsia2 = RGBColor::from_hex_code("f0f")?;
     /// assert_eq!(fuchsia.int_rgb_tup(), fuchsia2.int_rgb_tup());
     /// assert_eq!(fuchsia.int_rgb_tup(), (255, 0, 255));
-    /// let err = RGBColor::from_hex_code("#afafa");
-    /// let err2 = RGBColor::from_hex_code("#gafd22");
-    /// assert_eq!(err, err2);
+    /// // errors point to the problem
+    /// let err = RGBColor::from_hex_code("#afafa").unwrap_err();
+    /// assert_eq!(err.to_string(), "invalid hex code at column 2: expected 3 or 6 hex digits");
+    /// let err2 = RGBColor::from_hex_code("#gafd22").unwrap_err();
+    /// assert_eq!(err2.to_string(), "invalid hex code at column 2: expected a hex digit");
     /// # Ok(())
     /// # }
     /// # try_main().unwrap();
     /// ```
-    // otherwise you have really long lines with different reasons for throwing the same error
-    #[allow(clippy::if_same_then_else)]
-    pub fn from_hex_code(hex: &str) -> Result<RGBColor, RGBParseError> {
-        let mut chars: Vec<char> = hex.chars().collect();
-        // check if leading hex, remove if so
-        if chars[0] == '#' {
-            chars.remove(0);
-        }
-        // can only have 3 or 6 characters: error if not so
-        if chars.len() != 3 && chars.len() != 6 {
-            Err(RGBParseError::InvalidHexSyntax)
-        // now split on invalid hex
-        } else if !chars.iter().all(|&c| "0123456789ABCDEFabcdef".contains(c)) {
-            Err(RGBParseError::InvalidHexSyntax)
+    pub fn from_hex_code(hex: &str) -> Result<RGBColor, ColorParseError> {
+        let digits = hex_digits(hex, &[3, 6], "3 or 6 hex digits")?;
+        // the digits are all checked, so parsing them can't fail
+        let component = |digit: &str| u8::from_str_radix(digit, 16).unwrap();
         // split on whether it's #rgb or #rrggbb
-        } else if chars.len() == 6 {
-            let mut rgb: Vec<u8> = Vec::new();
-            for _i in 0..3 {
-                // this should never fail, logically, but if by some miracle it did it'd just
-                // return an OutOfRangeError
-                rgb.push(
-                    u8::from_str_radix(chars.drain(..2).collect::<String>().as_str(), 16).unwrap(),
-                );
-            }
-            Ok(RGBColor::from((rgb[0], rgb[1], rgb[2])))
+        if digits.len() == 6 {
+            Ok(RGBColor::from((
+                component(&digits[0..2]),
+                component(&digits[2..4]),
+                component(&digits[4..6]),
+            )))
         } else {
-            // len must be 3 from earlier
-            let mut rgb: Vec<u8> = Vec::new();
-            for _i in 0..3 {
-                // again, this shouldn't ever fail, but if it did it'd just return an
-                // OutOfRangeError
-                let c: Vec<char> = chars.drain(..1).collect();
-                rgb.push(
-                    u8::from_str_radix(c.iter().chain(c.iter()).collect::<String>().as_str(), 16)
-                        .unwrap(),
-                );
-            }
-            Ok(RGBColor::from((rgb[0], rgb[1], rgb[2])))
+            // len must be 3 from earlier: double each digit
+            let doubled = |i: usize| component(&digits[i..i + 1]) * 17;
+            Ok(RGBColor::from((doubled(0), doubled(1), doubled(2))))
         }
     }
     /// Gets the RGB color corresponding to an X11 color name. Case is ignored.
@@ -1200,19 +1239,19 @@ impl RGBColor {
     ///
     /// ```
     /// # use scarlet::prelude::*;
-    /// # fn try_main() -> Result<(), RGBParseError> {
+    /// # fn try_main() -> Result<(), ColorParseError> {
     /// let fuchsia = RGBColor::from_color_name("fuchsia")?;
     /// let fuchsia2 = RGBColor::from_color_name("FuCHSiA")?;
     /// assert_eq!(fuchsia.int_rgb_tup(), fuchsia2.int_rgb_tup());
     /// assert_eq!(fuchsia.int_rgb_tup(), (255, 0, 255));
-    /// let err = RGBColor::from_color_name("fuccshai");
-    /// let err2 = RGBColor::from_color_name("foobar");
-    /// assert_eq!(err, err2);
+    /// let err = RGBColor::from_color_name("fuccshai").unwrap_err();
+    /// let err2 = RGBColor::from_color_name("foobar").unwrap_err();
+    /// assert_eq!(err.kind, err2.kind);
     /// # Ok(())
     /// # }
     /// # try_main().unwrap();
     /// ```
-    pub fn from_color_name(name: &str) -> Result<RGBColor, RGBParseError> {
+    pub fn from_color_name(name: &str) -> Result<RGBColor, ColorParseError> {
         // this is the full list of X11 color names
         // I used a Python script to process it from this site:
         // https://github.com/bahamas10/css-color-names/blob/master/css-color-names.json
@@ -1227,25 +1266,34 @@ impl RGBColor {
 
         // now just return the converted value or raise one if not in hashmap
         match names_to_codes.get(&name.to_lowercase().as_str()) {
-            None => Err(RGBParseError::InvalidX11Name),
+            None => Err(ColorParseError::new(
+                ParseErrorKind::UnknownName,
+                ParseGrammar::Name,
+                0..name.len(),
+            )),
             Some(x) => Self::from_hex_code(x),
         }
     }
 }
 
 impl FromStr for RGBColor {
-    type Err = RGBParseError;
-
-    fn from_str(s: &str) -> Result<RGBColor, RGBParseError> {
-        match RGBColor::from_hex_code(s) {
-            Err(_e) => match RGBColor::from_color_name(s) {
-                Err(_e) => match parse_rgb_str(s) {
-                    Err(_e) => Err(_e.into()),
-                    Ok(nums) => Ok(RGBColor::from(nums)),
-                },
-                Ok(rgb) => Ok(rgb),
-            },
-            Ok(rgb) => Ok(rgb),
+    type Err = ColorParseError;
+
+    /// Parses a hex code, an X11 color name, or any CSS color function. Which of these the string
+    /// is meant to be is decided from its shape, and the error, if any, is for that syntax.
+    /// `rgb()` functions with commas are read as they always have been, with fractional numbers
+    /// between 0 and 1, and everything else is read with the CSS Color Level 4 parser and converted
+    /// to sRGB, dropping its alpha.
+    fn from_str(s: &str) -> Result<RGBColor, ColorParseError> {
+        match ParseGrammar::guess(s) {
+            ParseGrammar::Hex => RGBColor::from_hex_code(s),
+            ParseGrammar::Name => RGBColor::from_color_name(s),
+            ParseGrammar::Functional => parse_rgb_str(s).map(RGBColor::from).or_else(|_| {
+                parse_css_color(s).map(|parsed| match parsed.color {
+                    CSSColor::RGB(rgb) => rgb,
+                    other => other.convert(),
+                })
+            }),
         }
     }
 }
@@ -1538,16 +1586,25 @@ mod tests {
         assert_eq!(rgb.int_b(), 219);
         // test for error if 7 chars
         let rgb = RGBColor::from_hex_code("#1244444");
-        assert!(match rgb {
-            Err(x) if x == RGBParseError::InvalidHexSyntax => true,
-            _ => false,
-        });
+        assert_eq!(
+            rgb,
+            Err(
+                ColorParseError::new(ParseErrorKind::InvalidSyntax, ParseGrammar::Hex, 1..8)
+                    .expecting("3 or 6 hex digits")
+            )
+        );
         // test for error if invalid hex chars
         let rgb = RGBColor::from_hex_code("#ffggbb");
-        assert!(match rgb {
-            Err(x) if x == RGBParseError::InvalidHexSyntax => true,
-            _ => false,
-        });
+        assert_eq!(
+            rgb,
+            Err(
+                ColorParseError::new(ParseErrorKind::InvalidCharacters, ParseGrammar::Hex, 3..4)
+                    .expecting("a hex digit")
+            )
+        );
+        // test for error if empty
+        assert!(RGBColor::from_hex_code("").is_err());
+        assert!(RGBColor::from_hex_code("#").is_err());
     }
     #[test]
     fn test_rgb_from_name() {
@@ -1557,21 +1614,54 @@ mod tests {
         assert_eq!(rgb.int_b(), 50);
         // test error
         let rgb = RGBColor::from_color_name("thisisnotavalidnamelol");
-        assert!(match rgb {
-            Err(x) if x == RGBParseError::InvalidX11Name => true,
-            _ => false,
-        });
+        assert_eq!(
+            rgb,
+            Err(ColorParseError::new(
+                ParseErrorKind::UnknownName,
+                ParseGrammar::Name,
+                0..22
+            ))
+        );
     }
     #[test]
     fn test_rgb_from_func() {
         let rgb: RGBColor = "rgb(67%, 205, .937)".parse().unwrap();
         assert_eq!(*"#ABCDEF", rgb.to_string());
         assert_eq!(
-            Err(RGBParseError::InvalidFuncSyntax),
+            Err(ColorParseError::new(
+                ParseErrorKind::InvalidCharacters,
+                ParseGrammar::Functional,
+                6..8
+            )
+            .expecting("'%' or an angle unit")),
             "rgb(53%%, 23, 44)".parse::<RGBColor>()
         );
     }
     #[test]
+    fn test_rgb_from_css_func() {
+        let rgb: RGBColor = "rgb(50.5% 20% 10%)".parse().unwrap();
+        assert!((rgb.r - 0.505).abs() <= 1e-10);
+        assert!((rgb.g - 0.2).abs() <= 1e-10);
+        assert!((rgb.b - 0.1).abs() <= 1e-10);
+        // the legacy comma syntax of other functions, and alpha, which is dropped
+        let green: RGBColor = "hsl(120, 50%, 50%)".parse().unwrap();
+        assert_eq!(*"#40BF40", green.to_string());
+        let red: RGBColor = "rgba(255, 0, 0, 0.5)".parse().unwrap();
+        assert_eq!(*"#FF0000", red.to_string());
+        let red: RGBColor = "rgb(255, 0, 0, 0.5)".parse().unwrap();
+        assert_eq!(*"#FF0000", red.to_string());
+        let red: RGBColor = "oklch(62.8% 0.258 29.23deg)".parse().unwrap();
+        assert_eq!(*"#FF0000", red.to_string());
+        assert!("rgb(1 2)".parse::<RGBColor>().is_err());
+    }
+    #[test]
+    fn test_rgb_parse_error_from() {
+        let err = |s: &str| RGBParseError::from(s.parse::<RGBColor>().unwrap_err());
+        assert_eq!(err("#12345G"), RGBParseError::InvalidHexSyntax);
+        assert_eq!(err("notacolor"), RGBParseError::InvalidX11Name);
+        assert_eq!(err("rgb(53%%, 23, 44)"), RGBParseError::InvalidFuncSyntax);
+    }
+    #[test]
     fn test_string_parsing_all() {
         assert_eq!(
             *"#123456",
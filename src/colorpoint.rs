@@ -4,18 +4,20 @@
 //! don't require `From<Coord>`. This makes it easy to provide these for custom
 //! [`Color`](color/trait.Color.html) types.
 
-use super::geo::prelude::*;
-use super::geo::{Closest, LineString, Point};
 use color::{Color, XYZColor};
-use colors::cieluvcolor::CIELUVColor;
 use coord::Coord;
-use visual_gamut::read_cie_spectral_data;
+use illuminants::Illuminant;
+use observer::StandardObserver;
+use visual_gamut;
 
 /// Some errors that might pop up when dealing with colors as coordinates.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ColorCalcError {
     /// Returned when the number of weights given and the number of colors being averaged differ.
     MismatchedWeights,
+    /// Returned when a color's chromaticity is undefined: its XYZ coordinates aren't all finite, or
+    /// it isn't black but `X + 15Y + 3Z` is 0.
+    UndefinedChromaticity,
 }
 
 /// A trait that indicates that the current Color can be embedded in 3D space. This also requires
@@ -54,7 +56,10 @@ pub trait ColorPoint: Color + Into<Coord> + From<Coord> + Clone + Copy {
 
     /// Returns the weighted average of a given set of colors. Weights will be normalized so that they
     /// sum to 1. Each component of the final value will be calculated by summing the components of
-    /// each of the input colors multiplied by their given weight.
+    /// each of the input colors multiplied by their given weight. This doesn't mix colors the way
+    /// paint does: for that, use [`mix_pigments`].
+    ///
+    /// [`mix_pigments`]: ../pigment/fn.mix_pigments.html
     /// # Errors
     /// Returns `ColorCalcError::MismatchedWeights` if the number of colors (`self` and anything in
     /// `others`) and the number of weights mismatch.
@@ -84,65 +89,92 @@ pub trait ColorPoint: Color + Into<Coord> + From<Coord> + Clone + Copy {
     }
 
     /// Returns `true` if the color is outside the range of human vision. Uses the CIE 1931 standard
-    /// observer spectral data.
+    /// observer spectral data. Colors whose chromaticity is undefined count as imaginary: see
+    /// [`try_is_imaginary`](#method.try_is_imaginary) to tell them apart.
     fn is_imaginary(&self) -> bool {
-        let (_wavelengths, xyz_data) = read_cie_spectral_data();
-        // convert to chromaticity coordinates
-        // use the explicit formulae instead of CIELUVColor to reduce rounding errors
-        // we only care about those coordinates
-        let uv_func = |xyz: XYZColor| {
-            let denom = xyz.x + 15.0 * xyz.y + 3.0 * xyz.z;
-            (4.0 * xyz.x / denom, 9.0 * xyz.y / denom)
-        };
-        let self_uv: (f64, f64) = uv_func(self.convert());
-        let uv_data: Vec<(f64, f64)> = xyz_data.into_iter().map(uv_func).collect();
-        let self_point = Point::new(self_uv.0, self_uv.1);
+        self.is_imaginary_with(StandardObserver::CIE1931)
+    }
 
-        // this is an annoying algorithm, so I'm using a crate instead
-        let line: LineString<f64> = uv_data.into();
-        line.contains(&self_point)
+    /// Returns `true` if the color is outside the range of human vision, as the given standard
+    /// observer sees it. The 10° observer's range of vision is a little different from the 2° one.
+    /// Colors whose chromaticity is undefined count as imaginary: see
+    /// [`try_is_imaginary`](#method.try_is_imaginary) to tell them apart.
+    fn is_imaginary_with(&self, observer: StandardObserver) -> bool {
+        self.try_is_imaginary(observer).unwrap_or(true)
+    }
+
+    /// Returns whether the color is outside the range of human vision, as the given standard
+    /// observer sees it: that is, whether its chromaticity is outside of the spectral locus. Black
+    /// is real, and any color with negative luminance is imaginary.
+    /// # Errors
+    /// Returns `ColorCalcError::UndefinedChromaticity` if the color's XYZ coordinates aren't finite,
+    /// or aren't black but have no chromaticity because `X + 15Y + 3Z` is 0.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::cielabcolor::CIELABColor;
+    /// # use scarlet::observer::StandardObserver;
+    /// let observer = StandardObserver::CIE1931;
+    /// let red = RGBColor::from_hex_code("#ff0000").unwrap();
+    /// assert_eq!(red.try_is_imaginary(observer), Ok(false));
+    /// // CIELAB includes yellows far more saturated than any light
+    /// let too_yellow = CIELABColor { l: 50., a: 0., b: 150. };
+    /// assert_eq!(too_yellow.try_is_imaginary(observer), Ok(true));
+    /// ```
+    fn try_is_imaginary(&self, observer: StandardObserver) -> Result<bool, ColorCalcError> {
+        let xyz = self.to_xyz(Illuminant::D50);
+        visual_gamut::is_imaginary([xyz.x, xyz.y, xyz.z], observer)
     }
 
     /// Returns the closest color that can be seen by the human eye. If the color is not imaginary,
-    /// returns itself.
+    /// or its chromaticity is undefined so there's no closest color, returns itself.
     fn closest_real_color(&self) -> Self {
-        // if real color, return itself
-        if !self.is_imaginary() {
-            *self
-        } else {
-            let (_wavelengths, xyz_data) = read_cie_spectral_data();
-            // convert to chromaticity coordinates
-            // use the explicit formulae instead of CIELUVColor to reduce rounding errors
-            // we only care about those coordinates
-            let uv_func = |xyz: XYZColor| {
-                let denom = xyz.x + 15.0 * xyz.y + 3.0 * xyz.z;
-                (4.0 * xyz.x / denom, 9.0 * xyz.y / denom)
-            };
-            // we need to keep luminance data to convert back, so we use CIELUV explicitly
-            let mut self_luv: CIELUVColor = self.convert();
-            let self_uv = (self_luv.u, self_luv.v);
-            let uv_data: Vec<(f64, f64)> = xyz_data.into_iter().map(uv_func).collect();
-            let self_point = Point::new(self_uv.0, self_uv.1);
+        self.closest_real_color_with(StandardObserver::CIE1931)
+    }
 
-            // this is also an annoying algorithm: just use the crate
-            let line: LineString<f64> = uv_data.into();
-            let closest_point = line.closest_point(&self_point);
-            // convert back into original type
-            match closest_point {
-                Closest::Intersection(p) => {
-                    self_luv.u = p.x();
-                    self_luv.v = p.y();
-                }
-                Closest::SinglePoint(p) => {
-                    self_luv.u = p.x();
-                    self_luv.v = p.y();
-                }
-                Closest::Indeterminate => {
-                    // should never happen
-                    panic!("Indeterminate closest point! Please report this error");
-                }
-            }
-            self_luv.convert()
+    /// Returns the closest color that can be seen by the human eye, as the given standard observer
+    /// sees it. If the color is not imaginary for that observer, or its chromaticity is undefined
+    /// so there's no closest color, returns itself: see
+    /// [`try_closest_real_color`](#method.try_closest_real_color) to tell those apart.
+    fn closest_real_color_with(&self, observer: StandardObserver) -> Self {
+        self.try_closest_real_color(observer).unwrap_or(*self)
+    }
+
+    /// Returns the closest color that can be seen by the human eye, as the given standard observer
+    /// sees it: the color with the same luminance whose chromaticity is the closest real one in CIE
+    /// 1976 u'v' coordinates. If the color is not imaginary, returns itself, and colors with
+    /// negative luminance become black.
+    /// # Errors
+    /// Returns `ColorCalcError::UndefinedChromaticity` in the same cases as
+    /// [`try_is_imaginary`](#method.try_is_imaginary).
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::cielabcolor::CIELABColor;
+    /// # use scarlet::observer::StandardObserver;
+    /// let observer = StandardObserver::CIE1931;
+    /// let too_yellow = CIELABColor { l: 50., a: 0., b: 150. };
+    /// let real = too_yellow.try_closest_real_color(observer).unwrap();
+    /// // the lightness is the same, but the chroma is much lower
+    /// assert!((real.l - 50.).abs() <= 1e-10);
+    /// assert!(real.b < 90.);
+    /// assert_eq!(real.try_is_imaginary(observer), Ok(false));
+    /// ```
+    fn try_closest_real_color(&self, observer: StandardObserver) -> Result<Self, ColorCalcError> {
+        let xyz = self.to_xyz(Illuminant::D50);
+        let real = visual_gamut::closest_real_color([xyz.x, xyz.y, xyz.z], observer)?;
+        if real == [xyz.x, xyz.y, xyz.z] {
+            // avoid rounding error from converting back
+            Ok(*self)
+        } else {
+            Ok(Self::from_xyz(XYZColor {
+                x: real[0],
+                y: real[1],
+                z: real[2],
+                illuminant: Illuminant::D50,
+            }))
         }
     }
 
@@ -264,6 +296,40 @@ mod tests {
         assert!((lab1.euclidean_distance(lab2) - 132.70150715).abs() <= 1e-7);
     }
     #[test]
+    fn test_undefined_chromaticity() {
+        let observer = StandardObserver::CIE1931;
+        // black has no chromaticity, but it's real
+        let black: CIELABColor = XYZColor {
+            x: 0.,
+            y: 0.,
+            z: 0.,
+            illuminant: Illuminant::D50,
+        }
+        .convert();
+        assert!(!black.is_imaginary_with(observer));
+        let closest = black.closest_real_color_with(observer);
+        assert_eq!(
+            (closest.l, closest.a, closest.b),
+            (black.l, black.a, black.b)
+        );
+        // colors with no chromaticity at all count as imaginary, and have no closest real color
+        let nan = CIELABColor {
+            l: f64::NAN,
+            a: 0.,
+            b: 0.,
+        };
+        assert_eq!(
+            nan.try_is_imaginary(observer),
+            Err(ColorCalcError::UndefinedChromaticity)
+        );
+        assert_eq!(
+            nan.try_closest_real_color(observer).map(|c| c.a),
+            Err(ColorCalcError::UndefinedChromaticity)
+        );
+        assert!(nan.is_imaginary_with(observer));
+        assert!(nan.closest_real_color_with(observer).l.is_nan());
+    }
+    #[test]
     fn test_grad_scale() {
         let start = RGBColor::from_hex_code("#11457c").unwrap();
         let end = RGBColor::from_hex_code("#774bdc").unwrap();
@@ -0,0 +1,231 @@
+//! This module measures how faithfully a light source renders the colors of objects, compared to a
+//! reference light of the same color temperature: a blackbody for warm lights, and daylight for cool
+//! ones. This works from the spectral power distribution of the source as a [`Spectrum`].
+//!
+//! [`ColorRenderingIndex`] is the CIE color rendering index from CIE 13.3, still the most commonly
+//! quoted figure. It compares the colors of fourteen test samples under the source and the
+//! reference in the old CIE 1964 U\*V\*W\* space. The general index Ra averages the first eight, and
+//! the special indices R9 to R14 cover saturated colors, skin, and foliage: R9, for strong red, is
+//! the one most often quoted alongside Ra for LEDs.
+//!
+//! [`Spectrum`]: ../spectrum/struct.Spectrum.html
+//! [`ColorRenderingIndex`]: struct.ColorRenderingIndex.html
+
+use color::XYZColor;
+use colortemperature::ColorTemperature;
+use illuminants::Illuminant;
+use observer::StandardObserver;
+use spectral_data::{SPD_START, SPD_STEP, TEST_COLOR_SAMPLES};
+use spectrum::Spectrum;
+
+/// The shortest wavelength, in nanometers, that the color rendering index integrates over.
+const MIN_WAVELENGTH: f64 = 380.0;
+/// The longest wavelength, in nanometers, that the color rendering index integrates over.
+const MAX_WAVELENGTH: f64 = 780.0;
+
+/// Gets the reflectances of the fourteen CIE 13.3 test color samples, TCS01 through TCS14, as used
+/// for the color rendering index. They're tabulated every 5 nm from 380 nm to 760 nm.
+/// # Example
+///
+/// ```
+/// # use scarlet::colorrendering::test_color_samples;
+/// let samples = test_color_samples();
+/// assert_eq!(samples.len(), 14);
+/// // TCS09 is a strong red
+/// assert!(samples[8].value_at(650.) > 10. * samples[8].value_at(500.));
+/// ```
+pub fn test_color_samples() -> Vec<Spectrum> {
+    TEST_COLOR_SAMPLES
+        .iter()
+        .map(|values| {
+            Spectrum::uniform(SPD_START, SPD_STEP, values.to_vec()).expect("Sample data is valid.")
+        })
+        .collect()
+}
+
+/// Limits a spectral power distribution to the range the color rendering index integrates over.
+fn windowed<F: Fn(f64) -> f64 + Copy>(power: F) -> impl Fn(f64) -> f64 + Copy {
+    move |wl| {
+        if (MIN_WAVELENGTH..=MAX_WAVELENGTH).contains(&wl) {
+            power(wl)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Gets the color of the light with the given spectral power distribution, normalized so Y = 1.
+fn light_xyz<F: Fn(f64) -> f64>(power: F, observer: StandardObserver) -> [f64; 3] {
+    let white = Spectrum::new(vec![MIN_WAVELENGTH], vec![1.0]).expect("Spectrum is valid.");
+    white.integrate(power, observer)
+}
+
+/// Gets the correlated color temperature of the light with the given spectral power distribution.
+fn cct<F: Fn(f64) -> f64>(power: F) -> f64 {
+    let xyz = light_xyz(power, StandardObserver::CIE1931);
+    ColorTemperature::from_xyz(XYZColor {
+        x: xyz[0],
+        y: xyz[1],
+        z: xyz[2],
+        illuminant: Illuminant::D65,
+    })
+    .cct
+}
+
+/// Gets the CIE 1960 UCS coordinates of the given XYZ values.
+fn uv(xyz: [f64; 3]) -> (f64, f64) {
+    let denom = xyz[0] + 15.0 * xyz[1] + 3.0 * xyz[2];
+    (4.0 * xyz[0] / denom, 6.0 * xyz[1] / denom)
+}
+
+/// The `c` and `d` coordinates the CIE 13.3 von Kries transform works in.
+fn cd((u, v): (f64, f64)) -> (f64, f64) {
+    (
+        (4.0 - u - 10.0 * v) / v,
+        (1.708 * v + 0.404 - 1.481 * u) / v,
+    )
+}
+
+/// The CIE color rendering index of a light source, as defined in CIE 13.3.
+/// # Example
+/// Cool white fluorescent light, CIE illuminant F2, renders colors poorly and strong red very
+/// badly.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colorrendering::ColorRenderingIndex;
+/// # use scarlet::spectrum::Spectrum;
+/// let f2 = Spectrum::from_illuminant(Illuminant::F2).unwrap();
+/// let cri = ColorRenderingIndex::from_spectrum(&f2);
+/// assert_eq!(cri.ra.round(), 64.);
+/// assert!(cri.r[8] < -50.);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ColorRenderingIndex {
+    /// The correlated color temperature of the source, in kelvin, which determines the reference
+    /// illuminant.
+    pub cct: f64,
+    /// The general color rendering index Ra, the mean of R1 through R8. The reference illuminant
+    /// itself has an Ra of 100, and lower is worse: values can be negative.
+    pub ra: f64,
+    /// The special color rendering indices R1 through R14 for each test color sample, in order:
+    /// `r[8]` is R9.
+    pub r: [f64; 14],
+}
+
+impl ColorRenderingIndex {
+    /// Computes the color rendering index of a light source from its spectral power distribution.
+    /// The reference illuminant is a blackbody at the same correlated color temperature if it is
+    /// below 5000 K, and CIE daylight otherwise, which is only defined up to 25000 K. Everything is
+    /// integrated from 380 nm to 780 nm with the CIE 1931 observer, as CIE 13.3 specifies.
+    pub fn from_spectrum(source: &Spectrum) -> ColorRenderingIndex {
+        let test = windowed(|wl| source.value_at(wl));
+        let cct = cct(test);
+        let reference = if cct < 5000.0 {
+            Illuminant::Planckian(cct)
+        } else {
+            Illuminant::Daylight(cct)
+        };
+        let reference = windowed(move |wl| {
+            reference
+                .spectral_power(wl)
+                .expect("Illuminant has a spectrum.")
+        });
+        let observer = StandardObserver::CIE1931;
+        let (c_k, d_k) = cd(uv(light_xyz(test, observer)));
+        let (u_r, v_r) = uv(light_xyz(reference, observer));
+        let (c_r, d_r) = cd((u_r, v_r));
+
+        // CIE 1964 U*V*W*, relative to the reference white
+        let uvw = |y: f64, u: f64, v: f64| {
+            let w = 25.0 * (100.0 * y).cbrt() - 17.0;
+            [13.0 * w * (u - u_r), 13.0 * w * (v - v_r), w]
+        };
+        let mut r = [0.0; 14];
+        for (r_i, sample) in r.iter_mut().zip(test_color_samples()) {
+            let xyz_k = sample.integrate(test, observer);
+            let xyz_r = sample.integrate(reference, observer);
+            // von Kries adaptation of the sample under the source to the reference white
+            let (c, d) = cd(uv(xyz_k));
+            let denom = 16.518 + 1.481 * c_r / c_k * c - d_r / d_k * d;
+            let u_k = (10.872 + 0.404 * c_r / c_k * c - 4.0 * d_r / d_k * d) / denom;
+            let v_k = 5.520 / denom;
+            let (u, v) = uv(xyz_r);
+            let (k, r) = (uvw(xyz_k[1], u_k, v_k), uvw(xyz_r[1], u, v));
+            let delta_e =
+                ((k[0] - r[0]).powi(2) + (k[1] - r[1]).powi(2) + (k[2] - r[2]).powi(2)).sqrt();
+            *r_i = 100.0 - 4.6 * delta_e;
+        }
+        ColorRenderingIndex {
+            cct,
+            ra: r[..8].iter().sum::<f64>() / 8.0,
+            r,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn spectrum(ill: Illuminant) -> Spectrum {
+        Spectrum::from_illuminant(ill).unwrap()
+    }
+
+    #[test]
+    fn test_cri_fluorescent() {
+        // the general color rendering indices published in CIE 15:2004, table T.8.1
+        let published = [
+            76.0, 64.0, 57.0, 51.0, 72.0, 59.0, 90.0, 95.0, 90.0, 81.0, 83.0, 83.0,
+        ];
+        let illuminants = [
+            Illuminant::F1,
+            Illuminant::F2,
+            Illuminant::F3,
+            Illuminant::F4,
+            Illuminant::F5,
+            Illuminant::F6,
+            Illuminant::F7,
+            Illuminant::F8,
+            Illuminant::F9,
+            Illuminant::F10,
+            Illuminant::F11,
+            Illuminant::F12,
+        ];
+        for (&ill, &ra) in illuminants.iter().zip(published.iter()) {
+            let cri = ColorRenderingIndex::from_spectrum(&spectrum(ill));
+            assert!(
+                (cri.ra - ra).abs() <= 0.6,
+                "{:?}: {} != {}",
+                ill,
+                cri.ra,
+                ra
+            );
+        }
+        // the special indices of F2
+        let f2 = ColorRenderingIndex::from_spectrum(&spectrum(Illuminant::F2));
+        let published = [56, 77, 90, 57, 59, 67, 74, 33, -84, 45, 46, 54, 60, 94];
+        for (&r, &r_pub) in f2.r.iter().zip(published.iter()) {
+            assert_eq!(r.round(), f64::from(r_pub));
+        }
+        assert!((f2.cct - 4230.0).abs() <= 10.0);
+    }
+    #[test]
+    fn test_cri_reference() {
+        // the reference illuminants render perfectly
+        for &ill in [
+            Illuminant::A,
+            Illuminant::Planckian(3000.0),
+            Illuminant::D65,
+        ]
+        .iter()
+        {
+            let cri = ColorRenderingIndex::from_spectrum(&spectrum(ill));
+            assert!((cri.ra - 100.0).abs() <= 0.1, "{:?}: {}", ill, cri.ra);
+            for &r in cri.r.iter() {
+                assert!((r - 100.0).abs() <= 0.1);
+            }
+        }
+    }
+}
@@ -8,6 +8,7 @@ use consts::ADOBE_RGB_TRANSFORM as ADOBE_RGB;
 use consts::ADOBE_RGB_TRANSFORM_LU as ADOBE_RGB_LU;
 use coord::Coord;
 use illuminants::Illuminant;
+use rgbcolorspace::RGBColorSpace;
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 /// A color in the Adobe RGB color space. This is a rarer color space, but one that is still pretty
@@ -119,6 +120,11 @@ impl Bound for AdobeRGBColor {
     fn bounds() -> [(f64, f64); 3] {
         [(0., 1.), (0., 1.), (0., 1.)]
     }
+    /// Checks the color against the Adobe RGB primaries without clamping it, as converting to this type
+    /// clamps every component to the gamut.
+    fn in_gamut<T: Color>(color: &T, tolerance: f64) -> bool {
+        RGBColorSpace::ADOBE_RGB.in_gamut(color, tolerance)
+    }
 }
 
 #[cfg(test)]
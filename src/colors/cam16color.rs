@@ -0,0 +1,414 @@
+//! This module implements the [CAM16](https://doi.org/10.1002/col.22131) color appearance model, the
+//! successor to CIECAM02. Unlike every other color space in Scarlet, a color appearance model
+//! doesn't just describe a stimulus: it describes how that stimulus *looks* under a specific set of
+//! viewing conditions, such as how bright the surroundings are, what the eye is adapted to, and how
+//! much of the field of view is taken up by the background. Those conditions are described by the
+//! [`ViewingConditions`] struct. As the [`Color`] trait doesn't have room for them, the
+//! [`Color`] implementation of [`CAM16Color`] uses the default conditions (a typical sRGB viewing
+//! environment), and the `with_conditions` methods take any others.
+//!
+//! [`ViewingConditions`]: struct.ViewingConditions.html
+//! [`CAM16Color`]: struct.CAM16Color.html
+//! [`Color`]: ../../color/trait.Color.html
+
+use std::f64::consts::PI;
+
+use chromaticadaptation::ChromaticAdaptation;
+use color::{Color, XYZColor};
+use consts::CAT16_TRANSFORM as CAT16;
+use consts::CAT16_TRANSFORM_LU as CAT16_LU;
+use illuminants::Illuminant;
+
+/// The relative luminance of the area surrounding the field of view, which affects how strongly
+/// colors are perceived. Each option sets the CAM16 parameters `F`, `c`, and `N_c`.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Surround {
+    /// A surround about as bright as the white of the stimulus, such as viewing surface colors in a
+    /// lit room. This is the usual choice.
+    Average,
+    /// A surround darker than the stimulus, such as watching television in a dim room.
+    Dim,
+    /// An almost completely dark surround, such as projecting slides in a darkened theater.
+    Dark,
+}
+
+impl Surround {
+    /// Returns the triple `(F, c, N_c)` of the degree of adaptation factor, the impact of the
+    /// surround, and the chromatic induction factor.
+    fn parameters(self) -> (f64, f64, f64) {
+        match self {
+            Surround::Average => (1.0, 0.69, 1.0),
+            Surround::Dim => (0.9, 0.59, 0.9),
+            Surround::Dark => (0.8, 0.525, 0.8),
+        }
+    }
+}
+
+/// The conditions under which a color is viewed, as CAM16 describes them.
+/// # Example
+/// The same stimulus looks more colorful under bright light than it does in dim light: this is
+/// called the *Hunt effect*.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::cam16color::{CAM16Color, ViewingConditions};
+/// let red = RGBColor{r: 0.8, g: 0.2, b: 0.2}.to_xyz(Illuminant::D65);
+/// let dim = ViewingConditions{adapting_luminance: 10., ..ViewingConditions::default()};
+/// let bright = ViewingConditions{adapting_luminance: 1000., ..ViewingConditions::default()};
+/// let dim_red = CAM16Color::from_xyz_with_conditions(red, &dim);
+/// let bright_red = CAM16Color::from_xyz_with_conditions(red, &bright);
+/// assert!(bright_red.m > dim_red.m);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ViewingConditions {
+    /// The illuminant, or adopted white, that the viewer is adapted to. Colors are chromatically
+    /// adapted to this illuminant before being run through the model.
+    pub white: Illuminant,
+    /// The luminance of the adapting field, `L_A`, in cd/m². This is usually taken to be 20% of the
+    /// luminance of a white object in the scene.
+    pub adapting_luminance: f64,
+    /// The relative luminance of the background, `Y_b`, on a scale where the white point is 100. A
+    /// medium gray background has a value of 20.
+    pub background_luminance: f64,
+    /// The luminance of the surround relative to the stimulus.
+    pub surround: Surround,
+    /// Whether the observer discounts the illuminant, i.e., whether they are fully adapted to it. This
+    /// is true for surface colors that are recognized as objects, and false for self-luminous
+    /// displays, where the degree of adaptation is computed from the adapting luminance instead.
+    pub discounting: bool,
+}
+
+impl Default for ViewingConditions {
+    /// The viewing conditions of the sRGB reference environment: a D65 white, 64 lux of ambient
+    /// light, a gray background, an average surround, and no discounting of the illuminant.
+    fn default() -> ViewingConditions {
+        ViewingConditions {
+            white: Illuminant::D65,
+            adapting_luminance: 64.0 / PI * 0.2,
+            background_luminance: 20.0,
+            surround: Surround::Average,
+            discounting: false,
+        }
+    }
+}
+
+/// The values that CAM16 derives from the viewing conditions and that are shared by every
+/// conversion using them. These are named as in the paper.
+#[derive(Debug, Copy, Clone)]
+struct CAM16Parameters {
+    c: f64,
+    n_c: f64,
+    n: f64,
+    z: f64,
+    f_l: f64,
+    n_bb: f64,
+    d_rgb: [f64; 3],
+    a_w: f64,
+}
+
+/// The post-adaptation nonlinear compression of a single cone response. This is sign-preserving,
+/// so that imaginary colors still have a sensible inverse.
+fn compress(f_l: f64, x: f64) -> f64 {
+    let p = (f_l * x.abs() / 100.0).powf(0.42);
+    x.signum() * 400.0 * p / (p + 27.13) + 0.1
+}
+
+/// The inverse of `compress`.
+fn decompress(f_l: f64, x: f64) -> f64 {
+    let shifted = x - 0.1;
+    let p = 27.13 * shifted.abs() / (400.0 - shifted.abs());
+    shifted.signum() * 100.0 / f_l * p.powf(1.0 / 0.42)
+}
+
+impl ViewingConditions {
+    /// Computes the derived parameters for these viewing conditions.
+    fn parameters(&self) -> CAM16Parameters {
+        let (f, c, n_c) = self.surround.parameters();
+        // white is normalized so Y = 100, as the model expects
+        let wp = self.white.white_point();
+        let xyz_w = [wp[0] * 100.0, wp[1] * 100.0, wp[2] * 100.0];
+        let l_a = self.adapting_luminance;
+
+        let k = 1.0 / (5.0 * l_a + 1.0);
+        let k4 = k.powi(4);
+        let f_l = 0.2 * k4 * (5.0 * l_a) + 0.1 * (1.0 - k4).powi(2) * (5.0 * l_a).cbrt();
+        let n = self.background_luminance / xyz_w[1];
+        let z = 1.48 + n.sqrt();
+        let n_bb = 0.725 * n.powf(-0.2);
+
+        // the degree of adaptation: 1 means completely adapted to the white
+        let d = if self.discounting {
+            1.0
+        } else {
+            ChromaticAdaptation::degree_of_adaptation(f, l_a)
+        };
+
+        // &* needed because lazy_static uses a different type which implements Deref
+        let rgb_w = &*CAT16 * vector![xyz_w[0], xyz_w[1], xyz_w[2]];
+        let mut d_rgb = [0.0; 3];
+        let mut rgb_aw = [0.0; 3];
+        for i in 0..3 {
+            d_rgb[i] = d * xyz_w[1] / rgb_w[i] + 1.0 - d;
+            rgb_aw[i] = compress(f_l, d_rgb[i] * rgb_w[i]);
+        }
+        let a_w = (2.0 * rgb_aw[0] + rgb_aw[1] + rgb_aw[2] / 20.0 - 0.305) * n_bb;
+        CAM16Parameters {
+            c,
+            n_c,
+            n,
+            z,
+            f_l,
+            n_bb,
+            d_rgb,
+            a_w,
+        }
+    }
+}
+
+/// A color described by its appearance under the CAM16 model. All six of the perceptual correlates
+/// the model computes are stored, but only lightness, chroma, and hue are used to convert back:
+/// the others are for reading off.
+/// # Example
+/// Viewed in a dark surround, colors appear to have less contrast, so the same dark color looks
+/// lighter than it would under an average surround.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::cam16color::{CAM16Color, Surround, ViewingConditions};
+/// let gray = RGBColor{r: 0.3, g: 0.3, b: 0.3}.to_xyz(Illuminant::D65);
+/// let dark = ViewingConditions{surround: Surround::Dark, ..ViewingConditions::default()};
+/// let cam_average: CAM16Color = gray.convert();
+/// let cam_dark = CAM16Color::from_xyz_with_conditions(gray, &dark);
+/// assert!(cam_dark.j > cam_average.j);
+/// // either way, converting back gives the same stimulus
+/// let gray2 = cam_dark.to_xyz_with_conditions(Illuminant::D65, &dark);
+/// assert!(gray.distance(&gray2) <= 1e-10);
+/// ```
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct CAM16Color {
+    /// The lightness `J`, the brightness of the color relative to the brightness of white. White
+    /// has a lightness of 100.
+    pub j: f64,
+    /// The chroma `C`, the colorfulness of the color relative to the brightness of white.
+    pub c: f64,
+    /// The hue angle `h`, in degrees between 0 and 360.
+    pub h: f64,
+    /// The colorfulness `M`: how much chromatic content is perceived, in absolute terms. Unlike
+    /// chroma, this increases with the luminance of the scene.
+    pub m: f64,
+    /// The saturation `s`: colorfulness relative to the color's own brightness.
+    pub s: f64,
+    /// The brightness `Q`: how much light the color appears to emit, in absolute terms.
+    pub q: f64,
+}
+
+impl CAM16Color {
+    /// Computes the appearance of the given color under the given viewing conditions. The color is
+    /// first chromatically adapted to the white of the viewing conditions if its illuminant differs.
+    pub fn from_xyz_with_conditions(xyz: XYZColor, conditions: &ViewingConditions) -> CAM16Color {
+        let params = conditions.parameters();
+        let xyz_c = xyz.color_adapt(conditions.white);
+        // the model works on a scale where white has Y = 100
+        let rgb = &*CAT16 * vector![xyz_c.x * 100.0, xyz_c.y * 100.0, xyz_c.z * 100.0];
+        let r_a = compress(params.f_l, params.d_rgb[0] * rgb[0]);
+        let g_a = compress(params.f_l, params.d_rgb[1] * rgb[1]);
+        let b_a = compress(params.f_l, params.d_rgb[2] * rgb[2]);
+
+        // opponent color dimensions
+        let a = r_a - 12.0 * g_a / 11.0 + b_a / 11.0;
+        let b = (r_a + g_a - 2.0 * b_a) / 9.0;
+        let unbounded_h = b.atan2(a).to_degrees();
+        let h = if unbounded_h < 0.0 {
+            unbounded_h + 360.0
+        } else {
+            unbounded_h
+        };
+        // eccentricity factor
+        let e_t = 0.25 * ((h.to_radians() + 2.0).cos() + 3.8);
+
+        // achromatic response, and from it lightness and brightness
+        let achromatic = (2.0 * r_a + g_a + b_a / 20.0 - 0.305) * params.n_bb;
+        let j = 100.0 * (achromatic / params.a_w).powf(params.c * params.z);
+        let q = (4.0 / params.c) * (j / 100.0).sqrt() * (params.a_w + 4.0) * params.f_l.powf(0.25);
+
+        // chroma and the measures derived from it
+        let t = (50000.0 / 13.0 * params.n_c * params.n_bb * e_t * a.hypot(b))
+            / (r_a + g_a + 21.0 / 20.0 * b_a);
+        let c = t.powf(0.9) * (j / 100.0).sqrt() * (1.64 - 0.29f64.powf(params.n)).powf(0.73);
+        let m = c * params.f_l.powf(0.25);
+        let s = if q == 0.0 {
+            0.0
+        } else {
+            100.0 * (m / q).sqrt()
+        };
+        CAM16Color { j, c, h, m, s, q }
+    }
+
+    /// Builds a color from its lightness `J`, colorfulness `M`, and hue `h`, filling in the other
+    /// correlates as they would appear under the given viewing conditions. This is the form that
+    /// the CAM16 uniform color spaces store colors in.
+    pub fn from_jmh(j: f64, m: f64, h: f64, conditions: &ViewingConditions) -> CAM16Color {
+        let params = conditions.parameters();
+        let f_l_4 = params.f_l.powf(0.25);
+        let q = (4.0 / params.c) * (j / 100.0).sqrt() * (params.a_w + 4.0) * f_l_4;
+        let s = if q == 0.0 {
+            0.0
+        } else {
+            100.0 * (m / q).sqrt()
+        };
+        CAM16Color {
+            j,
+            c: m / f_l_4,
+            h,
+            m,
+            s,
+            q,
+        }
+    }
+
+    /// Finds the stimulus that has this appearance under the given viewing conditions, using the
+    /// lightness, chroma, and hue of the color. The result is chromatically adapted from the white
+    /// of the viewing conditions to the given illuminant.
+    pub fn to_xyz_with_conditions(
+        &self,
+        illuminant: Illuminant,
+        conditions: &ViewingConditions,
+    ) -> XYZColor {
+        let params = conditions.parameters();
+        let t = if self.j == 0.0 {
+            0.0
+        } else {
+            (self.c / ((self.j / 100.0).sqrt() * (1.64 - 0.29f64.powf(params.n)).powf(0.73)))
+                .powf(1.0 / 0.9)
+        };
+        let h_rad = self.h.to_radians();
+        let e_t = 0.25 * ((h_rad + 2.0).cos() + 3.8);
+        let achromatic = params.a_w * (self.j / 100.0).powf(1.0 / (params.c * params.z));
+
+        let p_2 = achromatic / params.n_bb + 0.305;
+        let p_3 = 21.0 / 20.0;
+        let (sin, cos) = h_rad.sin_cos();
+        // recover the opponent dimensions: which formula is numerically stable depends on the hue
+        let (a, b) = if t == 0.0 {
+            (0.0, 0.0)
+        } else {
+            let p_1 = (50000.0 / 13.0 * params.n_c * params.n_bb) * e_t / t;
+            if sin.abs() >= cos.abs() {
+                let p_4 = p_1 / sin;
+                let b = p_2 * (2.0 + p_3) * (460.0 / 1403.0)
+                    / (p_4 + (2.0 + p_3) * (220.0 / 1403.0) * (cos / sin) - 27.0 / 1403.0
+                        + p_3 * (6300.0 / 1403.0));
+                (b * cos / sin, b)
+            } else {
+                let p_5 = p_1 / cos;
+                let a = p_2 * (2.0 + p_3) * (460.0 / 1403.0)
+                    / (p_5 + (2.0 + p_3) * (220.0 / 1403.0)
+                        - (27.0 / 1403.0 - p_3 * (6300.0 / 1403.0)) * (sin / cos));
+                (a, a * sin / cos)
+            }
+        };
+
+        let r_a = (460.0 * p_2 + 451.0 * a + 288.0 * b) / 1403.0;
+        let g_a = (460.0 * p_2 - 891.0 * a - 261.0 * b) / 1403.0;
+        let b_a = (460.0 * p_2 - 220.0 * a - 6300.0 * b) / 1403.0;
+
+        let r = decompress(params.f_l, r_a) / params.d_rgb[0];
+        let g = decompress(params.f_l, g_a) / params.d_rgb[1];
+        let b = decompress(params.f_l, b_a) / params.d_rgb[2];
+        // use LU decomposition for accuracy
+        let xyz = CAT16_LU
+            .solve(vector![r, g, b])
+            .expect("Matrix is invertible.");
+        XYZColor {
+            x: xyz[0] / 100.0,
+            y: xyz[1] / 100.0,
+            z: xyz[2] / 100.0,
+            illuminant: conditions.white,
+        }
+        .color_adapt(illuminant)
+    }
+}
+
+impl Color for CAM16Color {
+    /// Converts from XYZ to CAM16 using the default viewing conditions. Use
+    /// [`from_xyz_with_conditions`](#method.from_xyz_with_conditions) for any others.
+    fn from_xyz(xyz: XYZColor) -> CAM16Color {
+        CAM16Color::from_xyz_with_conditions(xyz, &ViewingConditions::default())
+    }
+    /// Converts from CAM16 to XYZ using the default viewing conditions. Use
+    /// [`to_xyz_with_conditions`](#method.to_xyz_with_conditions) for any others.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        self.to_xyz_with_conditions(illuminant, &ViewingConditions::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_cam16_reference_values() {
+        // reference values from the colour-science Python package
+        let white = Illuminant::Custom([95.05, 100.0, 108.88]);
+        let conditions = ViewingConditions {
+            white,
+            adapting_luminance: 318.31,
+            background_luminance: 20.0,
+            surround: Surround::Average,
+            discounting: false,
+        };
+        let xyz = XYZColor {
+            x: 0.1901,
+            y: 0.2,
+            z: 0.2178,
+            illuminant: white,
+        };
+        let cam = CAM16Color::from_xyz_with_conditions(xyz, &conditions);
+        assert!((cam.j - 41.73120791).abs() <= 1e-4);
+        assert!((cam.c - 0.10335574).abs() <= 1e-4);
+        assert!((cam.h - 217.06795977).abs() <= 1e-2);
+        assert!((cam.s - 2.34501507).abs() <= 1e-4);
+        assert!((cam.q - 195.37170899).abs() <= 1e-4);
+        assert!((cam.m - 0.10743677).abs() <= 1e-4);
+    }
+    #[test]
+    fn test_cam16_xyz_conversion() {
+        let xyz = XYZColor {
+            x: 0.4,
+            y: 0.2,
+            z: 0.6,
+            illuminant: Illuminant::D65,
+        };
+        let cam: CAM16Color = xyz.convert();
+        let xyz2: XYZColor = cam.convert();
+        assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+    }
+    #[test]
+    fn test_cam16_xyz_conversion_conditions() {
+        let conditions = ViewingConditions {
+            white: Illuminant::D50,
+            adapting_luminance: 200.0,
+            background_luminance: 10.0,
+            surround: Surround::Dim,
+            discounting: true,
+        };
+        let xyz = XYZColor {
+            x: 0.3,
+            y: 0.53,
+            z: 0.65,
+            illuminant: Illuminant::D65,
+        };
+        let cam = CAM16Color::from_xyz_with_conditions(xyz, &conditions);
+        let xyz2 = cam.to_xyz_with_conditions(Illuminant::D65, &conditions);
+        assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+        // gray stays gray, as long as the observer is fully adapted
+        let gray = CAM16Color::from_xyz_with_conditions(
+            XYZColor::white_point(Illuminant::D50),
+            &conditions,
+        );
+        assert!(gray.c <= 1e-10);
+        assert!((gray.j - 100.0).abs() <= 1e-10);
+    }
+}
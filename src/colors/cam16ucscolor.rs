@@ -0,0 +1,236 @@
+//! This module implements the three uniform color spaces built on top of the
+//! [CAM16](../cam16color/index.html) color appearance model: CAM16-UCS, CAM16-LCD, and
+//! CAM16-SCD. Each one takes CAM16's lightness `J`, colorfulness `M`, and hue `h`, compresses
+//! lightness and colorfulness so that equal distances look like equal differences, and then lays
+//! them out as a rectangular space `J'a'b'`, just like CIELAB. They differ only in what data they
+//! were fit to: UCS is the general-purpose space, LCD is fit to large color differences, and SCD is
+//! fit to small color differences. As rectangular spaces, they can all be used as a
+//! [`ColorPoint`](../../colorpoint/trait.ColorPoint.html), and the Euclidean distance in each one
+//! is a color difference formula in its own right: see the `delta_e` methods.
+//!
+//! Like [`CAM16Color`](../cam16color/struct.CAM16Color.html), conversions through the [`Color`]
+//! trait use the default viewing conditions, and the `with_conditions` methods take any others.
+//!
+//! [`Color`]: ../../color/trait.Color.html
+
+use super::cam16color::{CAM16Color, ViewingConditions};
+use color::{Color, XYZColor};
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// The coefficients that define one of the CAM16 uniform color spaces.
+struct UniformCoefficients {
+    /// The lightness weight in the color difference formula.
+    k_l: f64,
+    /// The lightness compression factor.
+    c_1: f64,
+    /// The colorfulness compression factor.
+    c_2: f64,
+}
+
+static UCS_COEFFICIENTS: UniformCoefficients = UniformCoefficients {
+    k_l: 1.0,
+    c_1: 0.007,
+    c_2: 0.0228,
+};
+
+static LCD_COEFFICIENTS: UniformCoefficients = UniformCoefficients {
+    k_l: 0.77,
+    c_1: 0.007,
+    c_2: 0.0053,
+};
+
+static SCD_COEFFICIENTS: UniformCoefficients = UniformCoefficients {
+    k_l: 1.24,
+    c_1: 0.007,
+    c_2: 0.0363,
+};
+
+impl UniformCoefficients {
+    /// Gets the `(J', a', b')` coordinates of a CAM16 color.
+    fn to_jab(&self, cam: &CAM16Color) -> (f64, f64, f64) {
+        let j = (1.0 + 100.0 * self.c_1) * cam.j / (1.0 + self.c_1 * cam.j);
+        let m = (1.0 + self.c_2 * cam.m).ln() / self.c_2;
+        let (sin, cos) = cam.h.to_radians().sin_cos();
+        (j, m * cos, m * sin)
+    }
+    /// Gets the CAM16 color with the given `(J', a', b')` coordinates.
+    fn cam16_from_jab(&self, j: f64, a: f64, b: f64, conditions: &ViewingConditions) -> CAM16Color {
+        let cam_j = j / (1.0 + 100.0 * self.c_1 - self.c_1 * j);
+        let cam_m = ((self.c_2 * a.hypot(b)).exp() - 1.0) / self.c_2;
+        let unbounded_h = b.atan2(a).to_degrees();
+        let h = if unbounded_h < 0.0 {
+            unbounded_h + 360.0
+        } else {
+            unbounded_h
+        };
+        CAM16Color::from_jmh(cam_j, cam_m, h, conditions)
+    }
+    /// The color difference between two sets of `(J', a', b')` coordinates.
+    fn delta_e(&self, jab1: (f64, f64, f64), jab2: (f64, f64, f64)) -> f64 {
+        (((jab1.0 - jab2.0) / self.k_l).powi(2)
+            + (jab1.1 - jab2.1).powi(2)
+            + (jab1.2 - jab2.2).powi(2))
+        .sqrt()
+    }
+}
+
+/// Defines a color type for one of the CAM16 uniform color spaces, given the coefficients that
+/// define it. The docs and attributes given are put on the struct.
+macro_rules! cam16_uniform_color {
+    ($(#[$attr:meta])* pub struct $name:ident => $coefficients:expr;) => {
+        $(#[$attr])*
+        #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+        pub struct $name {
+            /// The compressed lightness `J'`. Ranges from 0 for black to 100 for white.
+            pub j: f64,
+            /// The red-green opponent axis `a'`.
+            pub a: f64,
+            /// The yellow-blue opponent axis `b'`.
+            pub b: f64,
+        }
+
+        impl $name {
+            /// Gets the coordinates of a CAM16 color in this space.
+            pub fn from_cam16(cam: &CAM16Color) -> $name {
+                let (j, a, b) = $coefficients.to_jab(cam);
+                $name { j, a, b }
+            }
+            /// Gets the CAM16 color with these coordinates, as it appears under the given viewing
+            /// conditions.
+            pub fn to_cam16(&self, conditions: &ViewingConditions) -> CAM16Color {
+                $coefficients.cam16_from_jab(self.j, self.a, self.b, conditions)
+            }
+            /// Converts a color to this space under the given viewing conditions.
+            pub fn from_xyz_with_conditions(xyz: XYZColor, conditions: &ViewingConditions) -> Self {
+                Self::from_cam16(&CAM16Color::from_xyz_with_conditions(xyz, conditions))
+            }
+            /// Converts from this space under the given viewing conditions to an XYZ color in the
+            /// given illuminant.
+            pub fn to_xyz_with_conditions(
+                &self,
+                illuminant: Illuminant,
+                conditions: &ViewingConditions,
+            ) -> XYZColor {
+                self.to_cam16(conditions)
+                    .to_xyz_with_conditions(illuminant, conditions)
+            }
+            /// The color difference ΔE' between this color and another, which is the distance
+            /// between them in this space with lightness weighted by `K_L`. Like CIEDE2000, a
+            /// value of about 1 is a just-noticeable difference.
+            pub fn delta_e(&self, other: &$name) -> f64 {
+                $coefficients.delta_e((self.j, self.a, self.b), (other.j, other.a, other.b))
+            }
+        }
+
+        impl Color for $name {
+            /// Converts from XYZ using the default viewing conditions.
+            fn from_xyz(xyz: XYZColor) -> $name {
+                $name::from_xyz_with_conditions(xyz, &ViewingConditions::default())
+            }
+            /// Converts to XYZ using the default viewing conditions.
+            fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+                self.to_xyz_with_conditions(illuminant, &ViewingConditions::default())
+            }
+        }
+
+        impl From<Coord> for $name {
+            fn from(c: Coord) -> $name {
+                $name {
+                    j: c.x,
+                    a: c.y,
+                    b: c.z,
+                }
+            }
+        }
+
+        impl From<$name> for Coord {
+            fn from(val: $name) -> Self {
+                Coord {
+                    x: val.j,
+                    y: val.a,
+                    z: val.b,
+                }
+            }
+        }
+    };
+}
+
+cam16_uniform_color! {
+    /// A color in the CAM16-UCS uniform color space, the general-purpose uniform space derived from
+    /// CAM16. This is a good choice for gradients and averaging when CIELAB's uniformity isn't enough.
+    /// # Example
+    /// CIELAB overstates the difference between very different colors: CAM16-UCS does a better job.
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::{CAM16UCSColor, CIELABColor};
+    /// let yellow = RGBColor{r: 1., g: 1., b: 0.};
+    /// let blue = RGBColor{r: 0., g: 0., b: 1.};
+    /// let lab_yellow: CIELABColor = yellow.convert();
+    /// let lab_blue: CIELABColor = blue.convert();
+    /// let ucs_yellow: CAM16UCSColor = yellow.convert();
+    /// let ucs_blue: CAM16UCSColor = blue.convert();
+    /// // about 232 in CIELAB, and about 96 in CAM16-UCS
+    /// assert!(lab_yellow.euclidean_distance(lab_blue) > 2. * ucs_yellow.delta_e(&ucs_blue));
+    /// // a gradient in CAM16-UCS avoids the gray midpoint a gradient in sRGB has
+    /// let mid: RGBColor = ucs_yellow.midpoint(ucs_blue).convert();
+    /// assert!(mid.chroma() > yellow.midpoint(blue).chroma());
+    /// ```
+    pub struct CAM16UCSColor => UCS_COEFFICIENTS;
+}
+
+cam16_uniform_color! {
+    /// A color in the CAM16-LCD uniform color space, fit to data on large color differences. Prefer
+    /// this over CAM16-UCS when comparing colors that are obviously different, such as the entries of
+    /// a categorical palette.
+    pub struct CAM16LCDColor => LCD_COEFFICIENTS;
+}
+
+cam16_uniform_color! {
+    /// A color in the CAM16-SCD uniform color space, fit to data on small color differences. Prefer
+    /// this over CAM16-UCS for tolerancing and judging whether two colors match.
+    pub struct CAM16SCDColor => SCD_COEFFICIENTS;
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::RGBColor;
+    use colorpoint::ColorPoint;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_cam16_ucs_xyz_conversion() {
+        let xyz = XYZColor {
+            x: 0.4,
+            y: 0.2,
+            z: 0.6,
+            illuminant: Illuminant::D65,
+        };
+        let ucs: CAM16UCSColor = xyz.convert();
+        let lcd: CAM16LCDColor = xyz.convert();
+        let scd: CAM16SCDColor = xyz.convert();
+        assert!(xyz.distance(&ucs.convert::<XYZColor>()) <= TEST_PRECISION);
+        assert!(xyz.distance(&lcd.convert::<XYZColor>()) <= TEST_PRECISION);
+        assert!(xyz.distance(&scd.convert::<XYZColor>()) <= TEST_PRECISION);
+    }
+    #[test]
+    fn test_cam16_ucs_white() {
+        let white: CAM16UCSColor = XYZColor::white_point(Illuminant::D65).convert();
+        // white has J = 100, which the compression keeps fixed
+        assert!((white.j - 100.0).abs() <= 1e-8);
+    }
+    #[test]
+    fn test_cam16_ucs_delta_e() {
+        let c1: CAM16UCSColor = RGBColor::from_hex_code("#123456").unwrap().convert();
+        let c2: CAM16UCSColor = RGBColor::from_hex_code("#123556").unwrap().convert();
+        let c3: CAM16UCSColor = RGBColor::from_hex_code("#333333").unwrap().convert();
+        assert!(c1.delta_e(&c2) < 1.0);
+        assert!(c1.delta_e(&c3) > 1.0);
+        assert!((c1.delta_e(&c3) - c3.delta_e(&c1)).abs() <= 1e-12);
+        // with K_L = 1, the color difference is just the Euclidean distance
+        assert!((c1.delta_e(&c3) - c1.euclidean_distance(c3)).abs() <= 1e-12);
+    }
+}
@@ -6,6 +6,7 @@
 //! Lab](https://en.wikipedia.org/wiki/Lab_color_space), but for convenience they are just `L`, `a`,
 //! and `b` in this module.
 
+use bound::Bound;
 use color::{Color, XYZColor};
 use coord::Coord;
 use illuminants::Illuminant;
@@ -65,33 +66,12 @@ impl Color for CIELABColor {
     /// converted to D50 outside of CIELAB conversion. This in line with programs like Photoshop,
     /// which also use CIELAB D50.
     fn from_xyz(xyz: XYZColor) -> CIELABColor {
-        // TODO: are the bounds for a and b right? -128 to 127?
-        // https://en.wikipedia.org/wiki/Lab_color_space#CIELAB-CIEXYZ_conversions
-        let f = |x: &f64| {
-            let delta: f64 = 6.0 / 29.0;
-            if *x <= delta.powf(3.0) {
-                x / (3.0 * delta * delta) + 4.0 / 29.0
-            } else {
-                x.powf(1.0 / 3.0)
-            }
-        };
         // now get the XYZ coordinates normalized using D50: convert to that beforehand if not
-        let white_point = Illuminant::D50.white_point();
         let xyz_adapted = xyz.color_adapt(Illuminant::D50);
-        let xyz_scaled = [
-            xyz_adapted.x / white_point[0],
-            xyz_adapted.y / white_point[1],
-            xyz_adapted.z / white_point[2],
-        ];
-        let xyz_transformed: Vec<f64> = xyz_scaled.iter().map(f).collect();
-
-        // xyz_transformed was modified to allow for human nonlinearity of color vision
-        // so this is just simple linear formulae
-        // note how a and b are opponent color axes
-        let l = 116.0 * xyz_transformed[1] - 16.0;
-        let a = 500.0 * (xyz_transformed[0] - xyz_transformed[1]);
-        let b = 200.0 * (xyz_transformed[1] - xyz_transformed[2]);
-        CIELABColor { l, a, b }
+        CIELABColor::from_xyz_relative_to(
+            [xyz_adapted.x, xyz_adapted.y, xyz_adapted.z],
+            Illuminant::D50.white_point(),
+        )
     }
     /// Returns an XYZ color that corresponds to the CIELAB color. Note that, because implicitly every
     /// CIELAB color is D50, conversion is done by first converting to a D50 XYZ color and then using
@@ -143,12 +123,77 @@ impl Into<Coord> for CIELABColor {
     }
 }
 
+impl CIELABColor {
+    /// Gets the CIELAB coordinates of the given XYZ values relative to the given reference white,
+    /// with no chromatic adaptation.
+    pub(crate) fn from_xyz_relative_to(xyz: [f64; 3], white_point: [f64; 3]) -> CIELABColor {
+        // TODO: are the bounds for a and b right? -128 to 127?
+        // https://en.wikipedia.org/wiki/Lab_color_space#CIELAB-CIEXYZ_conversions
+        let f = |x: &f64| {
+            let delta: f64 = 6.0 / 29.0;
+            if *x <= delta.powf(3.0) {
+                x / (3.0 * delta * delta) + 4.0 / 29.0
+            } else {
+                x.powf(1.0 / 3.0)
+            }
+        };
+        let xyz_scaled = [
+            xyz[0] / white_point[0],
+            xyz[1] / white_point[1],
+            xyz[2] / white_point[2],
+        ];
+        let xyz_transformed: Vec<f64> = xyz_scaled.iter().map(f).collect();
+
+        // xyz_transformed was modified to allow for human nonlinearity of color vision
+        // so this is just simple linear formulae
+        // note how a and b are opponent color axes
+        let l = 116.0 * xyz_transformed[1] - 16.0;
+        let a = 500.0 * (xyz_transformed[0] - xyz_transformed[1]);
+        let b = 200.0 * (xyz_transformed[1] - xyz_transformed[2]);
+        CIELABColor { l, a, b }
+    }
+    /// Returns whether this color can be shown in the given RGB color space, or any other space with
+    /// bounds, such as [`RGBColor`] for sRGB or [`DisplayP3Color`]. Components can be outside of the
+    /// bounds of the space by up to 1e-4, which is much less than a step of 8-bit color, to allow
+    /// for the rounding error of converting between white points: otherwise, even white isn't
+    /// displayable.
+    ///
+    /// [`RGBColor`]: ../../color/struct.RGBColor.html
+    /// [`DisplayP3Color`]: ../displayp3color/struct.DisplayP3Color.html
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::{CIELABColor, DisplayP3Color, Rec2020Color};
+    /// let white = CIELABColor{l: 100., a: 0., b: 0.};
+    /// assert!(white.is_displayable_in::<RGBColor>());
+    /// // a vivid red that only wide-gamut displays can show
+    /// let red = CIELABColor{l: 55., a: 85., b: 75.};
+    /// assert!(!red.is_displayable_in::<RGBColor>());
+    /// assert!(red.is_displayable_in::<DisplayP3Color>());
+    /// assert!(red.is_displayable_in::<Rec2020Color>());
+    /// ```
+    pub fn is_displayable_in<T: Bound>(&self) -> bool {
+        T::in_gamut(self, 1e-4)
+    }
+}
+
+// every color a surface can have under D50 or D65, and every color Rec. 2020 can show, has a from
+// about -167 to 154 and b from about -140 to 145: these bounds round those outwards
+impl Bound for CIELABColor {
+    fn bounds() -> [(f64, f64); 3] {
+        [(0., 100.), (-170., 160.), (-140., 150.)]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
     use super::*;
     use color::RGBColor;
+    use colors::{AdobeRGBColor, ROMMRGBColor, Rec2020Color};
     use consts::TEST_PRECISION;
+    use rgbcolorspace::RGBColorSpace;
 
     #[test]
     fn test_cielab_xyz_conversion_d50() {
@@ -188,4 +233,46 @@ mod tests {
         let _color2: RGBColor = _color1.convert();
         let _color3: CIELABColor = _color2.convert();
     }
+    #[test]
+    fn test_is_displayable_in() {
+        for &l in [5.0, 30.0, 60.0, 90.0].iter() {
+            for &a in [-100.0, -40.0, 0.0, 40.0, 100.0].iter() {
+                for &b in [-100.0, -40.0, 0.0, 40.0, 100.0].iter() {
+                    let lab = CIELABColor { l, a, b };
+                    // the runtime description of each space agrees with its type
+                    assert_eq!(
+                        lab.is_displayable_in::<RGBColor>(),
+                        RGBColorSpace::SRGB.in_gamut(&lab, 1e-4)
+                    );
+                    assert_eq!(
+                        lab.is_displayable_in::<Rec2020Color>(),
+                        RGBColorSpace::REC2020.in_gamut(&lab, 1e-4)
+                    );
+                    // sRGB is inside Adobe RGB, which is inside Rec. 2020
+                    let in_adobe = RGBColorSpace::ADOBE_RGB.in_gamut(&lab, 1e-4);
+                    if lab.is_displayable_in::<RGBColor>() {
+                        assert!(in_adobe);
+                    }
+                    if in_adobe {
+                        assert!(lab.is_displayable_in::<Rec2020Color>());
+                    }
+                    // Adobe RGB clamps when converting, but that doesn't hide colors outside it
+                    assert_eq!(lab.is_displayable_in::<AdobeRGBColor>(), in_adobe);
+                }
+            }
+        }
+        // not even ProPhoto RGB can show colors this saturated
+        let far = CIELABColor {
+            l: 50.0,
+            a: 500.0,
+            b: 0.0,
+        };
+        assert!(!far.is_displayable_in::<AdobeRGBColor>());
+        assert!(!far.is_displayable_in::<ROMMRGBColor>());
+        // grays are always displayable
+        for &l in [0.0, 50.0, 100.0].iter() {
+            let gray = CIELABColor { l, a: 0.0, b: 0.0 };
+            assert!(gray.is_displayable_in::<RGBColor>());
+        }
+    }
 }
@@ -3,6 +3,7 @@
 //! CIEHCL, which uses CIELUV internally.
 
 use super::cielabcolor::CIELABColor;
+use bound::Bound;
 use color::{Color, XYZColor};
 use coord::Coord;
 use illuminants::Illuminant;
@@ -102,6 +103,14 @@ impl Into<Coord> for CIELCHColor {
     }
 }
 
+// every color a surface can have under D50 or D65, and every color Rec. 2020 can show, has a chroma
+// below about 207
+impl Bound for CIELCHColor {
+    fn bounds() -> [(f64, f64); 3] {
+        [(0., 100.), (0., 210.), (0., 360.)]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
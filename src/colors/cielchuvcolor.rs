@@ -2,6 +2,7 @@
 //! CIELUV space, akin to the relationship between CIELAB and CIELCH.
 
 use super::cieluvcolor::CIELUVColor;
+use bound::Bound;
 use color::{Color, XYZColor};
 use coord::Coord;
 use illuminants::Illuminant;
@@ -94,6 +95,14 @@ impl Into<Coord> for CIELCHuvColor {
     }
 }
 
+// every color a surface can have under D50 or D65, and every color Rec. 2020 can show, has a chroma
+// below about 270
+impl Bound for CIELCHuvColor {
+    fn bounds() -> [(f64, f64); 3] {
+        [(0., 100.), (0., 280.), (0., 360.)]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
@@ -2,6 +2,7 @@
 //! CIELAB. CIELUV is very similar to CIELAB, but with the difference that u and v are roughly
 //! equivalent to red and green and luminance is then used to calculate the blue part.
 
+use bound::Bound;
 use color::{Color, XYZColor};
 use coord::Coord;
 use illuminants::Illuminant;
@@ -127,6 +128,14 @@ impl Into<Coord> for CIELUVColor {
     }
 }
 
+// every color a surface can have under D50 or D65, and every color Rec. 2020 can show, has u from
+// about -162 to 269 and v from about -133 to 108: these bounds round those outwards
+impl Bound for CIELUVColor {
+    fn bounds() -> [(f64, f64); 3] {
+        [(0., 100.), (-170., 270.), (-140., 110.)]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
@@ -0,0 +1,210 @@
+//! This module implements a color type for any RGB color space, described at runtime by an
+//! [`RGBColorSpace`](../../rgbcolorspace/struct.RGBColorSpace.html). Instead of hardcoding a
+//! matrix and transfer function like [`AdobeRGBColor`](../adobergbcolor/struct.AdobeRGBColor.html)
+//! does, [`CustomRGBColor`] takes a type parameter naming the space, and derives everything it
+//! needs from the space's primaries, white point, and transfer function.
+//!
+//! [`CustomRGBColor`]: struct.CustomRGBColor.html
+
+use bound::Bound;
+use color::{Color, XYZColor};
+use coord::Coord;
+use illuminants::Illuminant;
+use rgbcolorspace::RGBSpace;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A color in an arbitrary RGB color space, given by the type parameter `S`. Components range from
+/// 0 to 1 for colors inside the space's gamut. Unlike [`RGBColor`](../../color/struct.RGBColor.html),
+/// out-of-gamut colors are not clipped when converting: they simply have components outside that
+/// range, which [`Bound::clamp`](../../bound/trait.Bound.html#method.clamp) can fix if needed.
+/// # Example
+/// Wide-gamut spaces can represent colors that sRGB can't.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::CustomRGBColor;
+/// # use scarlet::rgbcolorspace::{RGBColorSpace, RGBSpace};
+/// struct ProPhoto;
+/// impl RGBSpace for ProPhoto {
+///     fn color_space() -> RGBColorSpace {
+///         RGBColorSpace::PROPHOTO_RGB
+///     }
+/// }
+/// let green = CustomRGBColor::<ProPhoto>::new(0.1, 0.9, 0.1);
+/// let srgb_green: RGBColor = green.convert();
+/// // far too saturated for sRGB
+/// assert!(srgb_green.r < 0.0);
+/// ```
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct CustomRGBColor<S: RGBSpace> {
+    /// The red primary component. This is a float that should range between 0 and 1.
+    pub r: f64,
+    /// The green primary component. This is a float that should range between 0 and 1.
+    pub g: f64,
+    /// The blue primary component. This is a float that should range between 0 and 1.
+    pub b: f64,
+    #[serde(skip)]
+    space: PhantomData<S>,
+}
+
+impl<S: RGBSpace> CustomRGBColor<S> {
+    /// Creates a new color with the given components.
+    pub fn new(r: f64, g: f64, b: f64) -> CustomRGBColor<S> {
+        CustomRGBColor {
+            r,
+            g,
+            b,
+            space: PhantomData,
+        }
+    }
+}
+
+// these are implemented by hand because deriving them would require S to implement them as well,
+// and there's no reason the marker type should need to
+
+impl<S: RGBSpace> Clone for CustomRGBColor<S> {
+    fn clone(&self) -> CustomRGBColor<S> {
+        *self
+    }
+}
+
+impl<S: RGBSpace> Copy for CustomRGBColor<S> {}
+
+impl<S: RGBSpace> fmt::Debug for CustomRGBColor<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CustomRGBColor")
+            .field("r", &self.r)
+            .field("g", &self.g)
+            .field("b", &self.b)
+            .finish()
+    }
+}
+
+impl<S: RGBSpace> Color for CustomRGBColor<S> {
+    /// Converts a given XYZ color to this RGB space, chromatically adapting it to the space's white
+    /// point first. Colors outside of the gamut are not clipped.
+    fn from_xyz(xyz: XYZColor) -> CustomRGBColor<S> {
+        let rgb = S::color_space().xyz_to_rgb(xyz);
+        CustomRGBColor::new(rgb[0], rgb[1], rgb[2])
+    }
+    /// Converts from this RGB space to an XYZ color in a given illuminant (via chromatic
+    /// adaptation).
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        S::color_space().rgb_to_xyz([self.r, self.g, self.b], illuminant)
+    }
+}
+
+impl<S: RGBSpace> From<Coord> for CustomRGBColor<S> {
+    fn from(c: Coord) -> CustomRGBColor<S> {
+        CustomRGBColor::new(c.x, c.y, c.z)
+    }
+}
+
+impl<S: RGBSpace> From<CustomRGBColor<S>> for Coord {
+    fn from(val: CustomRGBColor<S>) -> Self {
+        Coord {
+            x: val.r,
+            y: val.g,
+            z: val.b,
+        }
+    }
+}
+
+impl<S: RGBSpace> Bound for CustomRGBColor<S> {
+    fn bounds() -> [(f64, f64); 3] {
+        [(0., 1.), (0., 1.), (0., 1.)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::RGBColor;
+    use consts::STANDARD_RGB_TRANSFORM;
+    use consts::TEST_PRECISION;
+    use rgbcolorspace::RGBColorSpace;
+
+    struct Srgb;
+    impl RGBSpace for Srgb {
+        fn color_space() -> RGBColorSpace {
+            RGBColorSpace::SRGB
+        }
+    }
+
+    struct AdobeRGB;
+    impl RGBSpace for AdobeRGB {
+        fn color_space() -> RGBColorSpace {
+            RGBColorSpace::ADOBE_RGB
+        }
+    }
+
+    #[test]
+    fn test_custom_rgb_xyz_conversion() {
+        let xyz1 = XYZColor {
+            x: 0.4,
+            y: 0.2,
+            z: 0.5,
+            illuminant: Illuminant::D50,
+        };
+        let xyz2 = CustomRGBColor::<AdobeRGB>::from_xyz(xyz1).to_xyz(Illuminant::D50);
+        assert!(xyz1.approx_equal(&xyz2));
+        assert!(xyz1.distance(&xyz2) <= TEST_PRECISION);
+    }
+    #[test]
+    fn test_custom_rgb_matches_spec() {
+        // the sRGB spec rounds its matrix to 4 decimal places
+        let srgb = RGBColorSpace::SRGB.xyz_to_rgb_matrix();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((srgb[i][j] - STANDARD_RGB_TRANSFORM[[i, j]]).abs() <= 1e-3);
+            }
+        }
+        // the Adobe RGB spec gives its matrix to 7 decimal places, but uses a slightly different
+        // white point
+        let adobe = RGBColorSpace::ADOBE_RGB.xyz_to_rgb_matrix();
+        let adobe_spec = [
+            [2.0413690, -0.5649464, -0.3446944],
+            [-0.9692660, 1.8760108, 0.0415560],
+            [0.0134474, -0.1183897, 1.0154096],
+        ];
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((adobe[i][j] - adobe_spec[i][j]).abs() <= 1e-4);
+            }
+        }
+        // near 0, tiny differences in linear light become large differences once encoded, so only
+        // compare colors well within the gamut
+        for hex in ["#ffffff", "#123456", "#bada55", "#808080"].iter() {
+            let rgb = RGBColor::from_hex_code(hex).unwrap();
+            let custom: CustomRGBColor<Srgb> = rgb.convert();
+            assert!((custom.r - rgb.r).abs() <= 1e-3);
+            assert!((custom.g - rgb.g).abs() <= 1e-3);
+            assert!((custom.b - rgb.b).abs() <= 1e-3);
+        }
+    }
+    #[test]
+    fn test_custom_rgb_white() {
+        let white = CustomRGBColor::<AdobeRGB>::new(1.0, 1.0, 1.0).to_xyz(Illuminant::D65);
+        let wp = Illuminant::D65.white_point();
+        assert!((white.x - wp[0]).abs() <= 1e-14);
+        assert!((white.y - wp[1]).abs() <= 1e-14);
+        assert!((white.z - wp[2]).abs() <= 1e-14);
+    }
+    #[test]
+    fn test_custom_rgb_out_of_gamut() {
+        // Adobe RGB's green primary is far outside of sRGB: make sure that converting it to sRGB
+        // and back doesn't clip it
+        let green = CustomRGBColor::<AdobeRGB>::new(0.0, 1.0, 0.0);
+        let srgb: CustomRGBColor<Srgb> = green.convert();
+        assert!(srgb.r < 0.0);
+        let back: CustomRGBColor<AdobeRGB> = srgb.convert();
+        // a pure power law is very steep near 0, so rounding errors in linear light on the order of
+        // 1e-16 become much larger once encoded
+        assert!((back.r - green.r).abs() <= 1e-6);
+        assert!((back.g - green.g).abs() <= 1e-10);
+        assert!((back.b - green.b).abs() <= 1e-6);
+    }
+}
@@ -0,0 +1,93 @@
+//! A module that implements the Display P3 color space, used by most recent Apple devices and
+//! many other wide-gamut displays. It shares sRGB's white point and transfer function, but uses the
+//! much more saturated primaries of the DCI-P3 cinema standard, covering about 25% more colors than
+//! sRGB.
+
+use rgbcolorspace::RGBColorSpace;
+
+rgb_space_color! {
+    /// A color in the Display P3 color space. Components range from 0 to 1 for colors the space can
+    /// represent. Converting a color outside of the gamut doesn't clip it, so use
+    /// [`Bound::clamp`](../../bound/trait.Bound.html#method.clamp) before displaying it if needed.
+    /// # Example
+    /// Every sRGB color is also in Display P3, but not the other way around.
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::DisplayP3Color;
+    /// let srgb_red: DisplayP3Color = RGBColor{r: 1., g: 0., b: 0.}.convert();
+    /// // sRGB red is not the most saturated red Display P3 has
+    /// assert!(srgb_red.g > 0.15);
+    /// let p3_red = DisplayP3Color{r: 1., g: 0., b: 0.};
+    /// let too_red: RGBColor = p3_red.convert();
+    /// assert!(too_red.r > 1.0);
+    /// // hex codes work just like RGBColor's
+    /// assert_eq!(p3_red.to_string(), "#FF0000");
+    /// assert_eq!(DisplayP3Color::from_hex_code("#3a7bd5").unwrap().to_string(), "#3A7BD5");
+    /// ```
+    pub struct DisplayP3Color => RGBColorSpace::DISPLAY_P3;
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use bound::Bound;
+    use color::{Color, RGBColor, XYZColor};
+    use consts::TEST_PRECISION;
+    use illuminants::Illuminant;
+    use parseerror::{ColorParseError, ParseErrorKind, ParseGrammar};
+
+    #[test]
+    fn test_display_p3_xyz_conversion() {
+        let xyz1 = XYZColor {
+            x: 0.4,
+            y: 0.2,
+            z: 0.5,
+            illuminant: Illuminant::D75,
+        };
+        let xyz2 = DisplayP3Color::from_xyz(xyz1).to_xyz(Illuminant::D75);
+        assert!(xyz1.approx_equal(&xyz2));
+        assert!(xyz1.distance(&xyz2) <= TEST_PRECISION);
+    }
+    #[test]
+    fn test_display_p3_reference_values() {
+        // the CSS Color 4 spec gives sRGB red as color(display-p3 0.9175 0.2003 0.1386)
+        let red: DisplayP3Color = RGBColor {
+            r: 1.,
+            g: 0.,
+            b: 0.,
+        }
+        .convert();
+        assert!((red.r - 0.9175).abs() <= 1e-3);
+        assert!((red.g - 0.2003).abs() <= 1e-3);
+        assert!((red.b - 0.1386).abs() <= 1e-3);
+    }
+    #[test]
+    fn test_display_p3_clamping() {
+        let green = DisplayP3Color {
+            r: -0.1,
+            g: 1.2,
+            b: 0.5,
+        };
+        // clamping converts out of this space and back again, so it isn't exact
+        let clamped = DisplayP3Color::clamp(green);
+        assert!((clamped.r - 0.).abs() <= 1e-10);
+        assert!((clamped.g - 1.).abs() <= 1e-10);
+        assert!((clamped.b - 0.5).abs() <= 1e-10);
+    }
+    #[test]
+    fn test_display_p3_hex_round_trip() {
+        for hex in ["#000000", "#FFFFFF", "#123456", "#BADA55"].iter() {
+            let color: DisplayP3Color = hex.parse().unwrap();
+            assert_eq!(&color.to_string(), hex);
+        }
+        assert_eq!(
+            "#12345".parse::<DisplayP3Color>(),
+            Err(
+                ColorParseError::new(ParseErrorKind::InvalidSyntax, ParseGrammar::Hex, 1..6)
+                    .expecting("3 or 6 hex digits")
+            )
+        );
+    }
+}
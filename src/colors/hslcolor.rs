@@ -24,8 +24,9 @@ use std::str::FromStr;
 use bound::Bound;
 use color::{Color, RGBColor, XYZColor};
 use coord::Coord;
-use csscolor::{parse_hsl_hsv_tuple, CSSParseError};
+use csscolor::{parse_hsl_hsv_tuple, strip_function_name};
 use illuminants::Illuminant;
+use parseerror::ColorParseError;
 
 /// A color in the HSL color space, a direct transformation of the sRGB space. sHSL is used to
 /// distinguish this space from a similar transformation of a different RGB space, which can cause
@@ -184,20 +185,17 @@ impl Bound for HSLColor {
 }
 
 impl FromStr for HSLColor {
-    type Err = CSSParseError;
+    type Err = ColorParseError;
 
-    fn from_str(s: &str) -> Result<HSLColor, CSSParseError> {
-        if !s.starts_with("hsl(") {
-            return Err(CSSParseError::InvalidColorSyntax);
-        }
-        let tup: String = s.chars().skip(3).collect::<String>();
-        match parse_hsl_hsv_tuple(&tup) {
+    fn from_str(s: &str) -> Result<HSLColor, ColorParseError> {
+        let tup = strip_function_name(s, &["hsl"], "'hsl'")?;
+        match parse_hsl_hsv_tuple(tup) {
             Ok(res) => Ok(HSLColor {
                 h: res.0,
                 s: res.1,
                 l: res.2,
             }),
-            Err(_e) => Err(_e),
+            Err(e) => Err(e.offset(s.len() - tup.len())),
         }
     }
 }
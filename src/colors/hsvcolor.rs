@@ -12,8 +12,9 @@ use std::str::FromStr;
 use bound::Bound;
 use color::{Color, RGBColor, XYZColor};
 use coord::Coord;
-use csscolor::{parse_hsl_hsv_tuple, CSSParseError};
+use csscolor::{parse_hsl_hsv_tuple, strip_function_name};
 use illuminants::Illuminant;
+use parseerror::ColorParseError;
 
 /// An HSV color, defining parameters for hue, saturation, and value from the RGB space. This is sHSV
 /// to be exact, but the derivation from the sRGB space is assumed as it matches the vast majority of
@@ -157,20 +158,17 @@ impl Bound for HSVColor {
 }
 
 impl FromStr for HSVColor {
-    type Err = CSSParseError;
+    type Err = ColorParseError;
 
-    fn from_str(s: &str) -> Result<HSVColor, CSSParseError> {
-        if !s.starts_with("hsv(") {
-            return Err(CSSParseError::InvalidColorSyntax);
-        }
-        let tup: String = s.chars().skip(3).collect::<String>();
-        match parse_hsl_hsv_tuple(&tup) {
+    fn from_str(s: &str) -> Result<HSVColor, ColorParseError> {
+        let tup = strip_function_name(s, &["hsv"], "'hsv'")?;
+        match parse_hsl_hsv_tuple(tup) {
             Ok(res) => Ok(HSVColor {
                 h: res.0,
                 s: res.1,
                 v: res.2,
             }),
-            Err(_e) => Err(_e),
+            Err(e) => Err(e.offset(s.len() - tup.len())),
         }
     }
 }
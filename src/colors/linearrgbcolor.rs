@@ -0,0 +1,148 @@
+//! A module that implements linear-light sRGB: the same primaries and white point as
+//! [`RGBColor`](../../color/struct.RGBColor.html), but without the sRGB transfer function.
+//! Components are proportional to the amount of light each primary emits, which makes this the
+//! space to mix light in: adding, scaling, and averaging linear RGB colors does what the physics
+//! of light does, which isn't true of gamma-encoded sRGB.
+
+use bound::Bound;
+use color::{Color, XYZColor};
+use consts::STANDARD_RGB_TRANSFORM as SRGB;
+use consts::STANDARD_RGB_TRANSFORM_LU as SRGB_LU;
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// A color in linear-light sRGB. The components range from 0 to 1 for colors inside the sRGB gamut,
+/// just like [`RGBColor`](../../color/struct.RGBColor.html), but are not gamma-encoded: a value of
+/// 0.5 emits half as much light as 1, which in sRGB would be a value of about 0.735. Conversions
+/// between the two use the same matrix, so they only differ by the transfer function.
+/// # Example
+/// Mixing red and green light gives a bright yellow. Averaging the gamma-encoded sRGB values
+/// instead gives a yellow that's too dark.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::LinearRGBColor;
+/// let red = RGBColor{r: 1., g: 0., b: 0.};
+/// let green = RGBColor{r: 0., g: 1., b: 0.};
+/// let encoded_mix = red.midpoint(green);
+/// let linear_red: LinearRGBColor = red.convert();
+/// let linear_green: LinearRGBColor = green.convert();
+/// let linear_mix: RGBColor = linear_red.midpoint(linear_green).convert();
+/// assert_eq!(encoded_mix.to_string(), "#808000");
+/// assert_eq!(linear_mix.to_string(), "#BCBC00");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinearRGBColor {
+    /// The red component, proportional to the light emitted by the red primary.
+    pub r: f64,
+    /// The green component, proportional to the light emitted by the green primary.
+    pub g: f64,
+    /// The blue component, proportional to the light emitted by the blue primary.
+    pub b: f64,
+}
+
+impl Color for LinearRGBColor {
+    /// Converts a given XYZ color to linear sRGB. Like sRGB, this is implicitly D65, so any other
+    /// illuminant is chromatically adapted first. Colors outside of the gamut are not clipped.
+    fn from_xyz(xyz: XYZColor) -> LinearRGBColor {
+        let xyz_d65 = xyz.color_adapt(Illuminant::D65);
+        // &* needed because lazy_static uses a different type which implements Deref
+        let rgb = &*SRGB * vector![xyz_d65.x, xyz_d65.y, xyz_d65.z];
+        LinearRGBColor {
+            r: rgb[0],
+            g: rgb[1],
+            b: rgb[2],
+        }
+    }
+    /// Converts from linear sRGB to an XYZ color in a given illuminant (via chromatic adaptation).
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        // use LU decomposition for accuracy, like RGBColor does
+        let xyz = SRGB_LU
+            .solve(vector![self.r, self.g, self.b])
+            .expect("Matrix is invertible.");
+        XYZColor {
+            x: xyz[0],
+            y: xyz[1],
+            z: xyz[2],
+            illuminant: Illuminant::D65,
+        }
+        .color_adapt(illuminant)
+    }
+}
+
+impl From<Coord> for LinearRGBColor {
+    fn from(c: Coord) -> LinearRGBColor {
+        LinearRGBColor {
+            r: c.x,
+            g: c.y,
+            b: c.z,
+        }
+    }
+}
+
+impl From<LinearRGBColor> for Coord {
+    fn from(val: LinearRGBColor) -> Self {
+        Coord {
+            x: val.r,
+            y: val.g,
+            z: val.b,
+        }
+    }
+}
+
+impl Bound for LinearRGBColor {
+    fn bounds() -> [(f64, f64); 3] {
+        [(0., 1.), (0., 1.), (0., 1.)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::RGBColor;
+    use colorpoint::ColorPoint;
+    use consts::TEST_PRECISION;
+    use rgbcolorspace::TransferFunction;
+
+    #[test]
+    fn test_linear_rgb_xyz_conversion() {
+        let xyz1 = XYZColor {
+            x: 0.4,
+            y: 0.2,
+            z: 0.5,
+            illuminant: Illuminant::D50,
+        };
+        let xyz2 = LinearRGBColor::from_xyz(xyz1).to_xyz(Illuminant::D50);
+        assert!(xyz1.approx_equal(&xyz2));
+        assert!(xyz1.distance(&xyz2) <= TEST_PRECISION);
+    }
+    #[test]
+    fn test_linear_rgb_matches_srgb() {
+        // converting from sRGB should just undo the transfer function
+        let rgb = RGBColor::from_hex_code("#3a7bd5").unwrap();
+        let linear: LinearRGBColor = rgb.convert();
+        assert!((linear.r - TransferFunction::SRGB.decode(rgb.r)).abs() <= 1e-12);
+        assert!((linear.g - TransferFunction::SRGB.decode(rgb.g)).abs() <= 1e-12);
+        assert!((linear.b - TransferFunction::SRGB.decode(rgb.b)).abs() <= 1e-12);
+    }
+    #[test]
+    fn test_linear_rgb_weighted_average() {
+        // a quarter of the light of white is a gray that's much lighter than a quarter of the way
+        let black = LinearRGBColor {
+            r: 0.,
+            g: 0.,
+            b: 0.,
+        };
+        let white = LinearRGBColor {
+            r: 1.,
+            g: 1.,
+            b: 1.,
+        };
+        let gray = black.weighted_average(vec![white], vec![3., 1.]).unwrap();
+        assert!((gray.r - 0.25).abs() <= 1e-15);
+        let rgb_gray: RGBColor = gray.convert();
+        assert!((rgb_gray.r - TransferFunction::SRGB.encode(0.25)).abs() <= 1e-12);
+        assert!(rgb_gray.r > 0.5);
+    }
+}
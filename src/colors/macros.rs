@@ -0,0 +1,104 @@
+//! Macros shared by the color types in this module.
+
+/// Defines a color type for one of the fixed RGB color spaces in
+/// [`RGBColorSpace`](../../rgbcolorspace/struct.RGBColorSpace.html), such as Display P3. The type
+/// gets public `r`, `g`, and `b` fields, conversion through the space's matrices, `Coord`
+/// conversions, 0-1 bounds, and hex code parsing and display just like `RGBColor`'s. The docs and
+/// attributes given are put on the struct.
+macro_rules! rgb_space_color {
+    ($(#[$attr:meta])* pub struct $name:ident => $space:expr;) => {
+        $(#[$attr])*
+        #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+        pub struct $name {
+            /// The red primary component. This is a float that should range between 0 and 1.
+            pub r: f64,
+            /// The green primary component. This is a float that should range between 0 and 1.
+            pub g: f64,
+            /// The blue primary component. This is a float that should range between 0 and 1.
+            pub b: f64,
+        }
+
+        impl $name {
+            /// Given a hex code, returns the color with those components, using the same formats as
+            /// [`RGBColor::from_hex_code`](../../color/struct.RGBColor.html#method.from_hex_code).
+            pub fn from_hex_code(
+                hex: &str,
+            ) -> Result<$name, $crate::parseerror::ColorParseError> {
+                let rgb = $crate::color::RGBColor::from_hex_code(hex)?;
+                Ok($name {
+                    r: rgb.r,
+                    g: rgb.g,
+                    b: rgb.b,
+                })
+            }
+        }
+
+        impl $crate::color::Color for $name {
+            /// Converts a given XYZ color to this space. The space has a fixed white point, so any
+            /// color will be converted to that illuminant before conversion.
+            fn from_xyz(xyz: $crate::color::XYZColor) -> $name {
+                let rgb = $space.xyz_to_rgb(xyz);
+                $name {
+                    r: rgb[0],
+                    g: rgb[1],
+                    b: rgb[2],
+                }
+            }
+            /// Converts from this space to an XYZ color in a given illuminant (via chromatic
+            /// adaptation).
+            fn to_xyz(
+                &self,
+                illuminant: $crate::illuminants::Illuminant,
+            ) -> $crate::color::XYZColor {
+                $space.rgb_to_xyz([self.r, self.g, self.b], illuminant)
+            }
+        }
+
+        impl From<$crate::coord::Coord> for $name {
+            fn from(c: $crate::coord::Coord) -> $name {
+                $name {
+                    r: c.x,
+                    g: c.y,
+                    b: c.z,
+                }
+            }
+        }
+
+        impl From<$name> for $crate::coord::Coord {
+            fn from(val: $name) -> Self {
+                $crate::coord::Coord {
+                    x: val.r,
+                    y: val.g,
+                    z: val.b,
+                }
+            }
+        }
+
+        impl $crate::bound::Bound for $name {
+            fn bounds() -> [(f64, f64); 3] {
+                [(0., 1.), (0., 1.), (0., 1.)]
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            /// Writes the color as a hex code, clamping and rounding each component to 8 bits like
+            /// [`RGBColor`](../../color/struct.RGBColor.html) does.
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                let rgb = $crate::color::RGBColor {
+                    r: self.r,
+                    g: self.g,
+                    b: self.b,
+                };
+                write!(f, "{}", rgb.to_string())
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = $crate::parseerror::ColorParseError;
+
+            fn from_str(s: &str) -> Result<$name, $crate::parseerror::ColorParseError> {
+                $name::from_hex_code(s)
+            }
+        }
+    };
+}
@@ -2,21 +2,42 @@
 //! each main type is imported into this module's namespace directly.
 //!
 //! [`Color`]: ../color/trait.Color.html
+
+// the macros have to be defined before the modules that use them
+#[macro_use]
+mod macros;
+
 pub mod adobergbcolor;
+pub mod cam16color;
+pub mod cam16ucscolor;
 pub mod cielabcolor;
 pub mod cielchcolor;
 pub mod cielchuvcolor;
 pub mod cieluvcolor;
+pub mod customrgbcolor;
+pub mod displayp3color;
 pub mod hslcolor;
 pub mod hsvcolor;
+pub mod linearrgbcolor;
+pub mod oklabcolor;
+pub mod oklchcolor;
+pub mod rec2020color;
 pub mod rommrgbcolor;
 
 // for convenience, use this namespace for the color objects
 pub use self::adobergbcolor::AdobeRGBColor;
+pub use self::cam16color::CAM16Color;
+pub use self::cam16ucscolor::{CAM16LCDColor, CAM16SCDColor, CAM16UCSColor};
 pub use self::cielabcolor::CIELABColor;
 pub use self::cielchcolor::CIELCHColor;
 pub use self::cielchuvcolor::CIELCHuvColor;
 pub use self::cieluvcolor::CIELUVColor;
+pub use self::customrgbcolor::CustomRGBColor;
+pub use self::displayp3color::DisplayP3Color;
 pub use self::hslcolor::HSLColor;
 pub use self::hsvcolor::HSVColor;
+pub use self::linearrgbcolor::LinearRGBColor;
+pub use self::oklabcolor::OklabColor;
+pub use self::oklchcolor::OklchColor;
+pub use self::rec2020color::Rec2020Color;
 pub use self::rommrgbcolor::ROMMRGBColor;
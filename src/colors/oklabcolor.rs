@@ -0,0 +1,171 @@
+//! This module implements the [Oklab color space](https://bottosson.github.io/posts/oklab/), a
+//! perceptual color space designed by Björn Ottosson as a drop-in replacement for CIELAB in image
+//! processing. Like CIELAB, it has a lightness axis and two opponent color axes, but it was fit to
+//! modern color appearance data so that blending, hue, and chroma behave better, especially for the
+//! saturated blues that CIELAB is notorious for bending toward purple. It is the space CSS Color 4
+//! recommends for gradients and gamut mapping.
+
+use bound::Bound;
+use color::{Color, XYZColor};
+use consts::OKLAB_LAB_TRANSFORM as OKLAB_LAB;
+use consts::OKLAB_LAB_TRANSFORM_LU as OKLAB_LAB_LU;
+use consts::OKLAB_LMS_TRANSFORM as OKLAB_LMS;
+use consts::OKLAB_LMS_TRANSFORM_LU as OKLAB_LMS_LU;
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// A color in the Oklab color space. The `l` component ranges from 0 (black) to 1 (diffuse white),
+/// and `a` and `b` are opponent axes that are roughly within -0.4 and 0.4 for colors that can be
+/// seen. Because it is a rectangular space with good perceptual uniformity, averaging and gradients
+/// in Oklab look even to the eye.
+/// # Example
+/// A gradient from blue to white in CIELAB passes through purple: in Oklab, it stays blue.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::{CIELABColor, OklabColor};
+/// let blue = RGBColor{r: 0., g: 0., b: 1.};
+/// let white = RGBColor{r: 1., g: 1., b: 1.};
+/// let lab_mid: RGBColor = blue.convert::<CIELABColor>()
+///     .midpoint(white.convert())
+///     .convert();
+/// let oklab_mid: RGBColor = blue.convert::<OklabColor>()
+///     .midpoint(white.convert())
+///     .convert();
+/// // the CIELAB midpoint has noticeably more red in it than the Oklab one
+/// assert!(lab_mid.r - oklab_mid.r > 0.05);
+/// ```
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct OklabColor {
+    /// The perceived lightness of the color, from 0 for black to 1 for the white point.
+    pub l: f64,
+    /// The green-red opponent axis: negative values are green and positive values are red. Ranges
+    /// roughly between -0.4 and 0.4 for visible colors.
+    pub a: f64,
+    /// The blue-yellow opponent axis: negative values are blue and positive values are
+    /// yellow. Ranges roughly between -0.4 and 0.4 for visible colors.
+    pub b: f64,
+}
+
+impl Color for OklabColor {
+    /// Converts a given XYZ color to Oklab. Oklab is defined relative to D65, so any other
+    /// illuminant is chromatically adapted to D65 first.
+    fn from_xyz(xyz: XYZColor) -> OklabColor {
+        let xyz_c = xyz.color_adapt(Illuminant::D65);
+        // first, get the approximate cone responses
+        // &* needed because lazy_static uses a different type which implements Deref
+        let lms = &*OKLAB_LMS * vector![xyz_c.x, xyz_c.y, xyz_c.z];
+        // apply the nonlinearity: cbrt, unlike powf, is defined for negative inputs, which can
+        // happen for imaginary colors
+        let lms_prime = vector![lms[0].cbrt(), lms[1].cbrt(), lms[2].cbrt()];
+        // and then project onto the opponent axes
+        let lab = &*OKLAB_LAB * lms_prime;
+        OklabColor {
+            l: lab[0],
+            a: lab[1],
+            b: lab[2],
+        }
+    }
+    /// Converts from Oklab back to XYZ. Oklab is implicitly D65, so the result is chromatically
+    /// adapted from D65 to the given illuminant.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        // invert each step of from_xyz, using LU decomposition for accuracy
+        let lms_prime = OKLAB_LAB_LU
+            .solve(vector![self.l, self.a, self.b])
+            .expect("Matrix is invertible.");
+        let lms = vector![
+            lms_prime[0].powi(3),
+            lms_prime[1].powi(3),
+            lms_prime[2].powi(3)
+        ];
+        let xyz = OKLAB_LMS_LU.solve(lms).expect("Matrix is invertible.");
+        XYZColor {
+            x: xyz[0],
+            y: xyz[1],
+            z: xyz[2],
+            illuminant: Illuminant::D65,
+        }
+        .color_adapt(illuminant)
+    }
+}
+
+impl From<Coord> for OklabColor {
+    fn from(c: Coord) -> OklabColor {
+        OklabColor {
+            l: c.x,
+            a: c.y,
+            b: c.z,
+        }
+    }
+}
+
+impl From<OklabColor> for Coord {
+    fn from(val: OklabColor) -> Self {
+        Coord {
+            x: val.l,
+            y: val.a,
+            z: val.b,
+        }
+    }
+}
+
+impl Bound for OklabColor {
+    fn bounds() -> [(f64, f64); 3] {
+        [(0., 1.), (-0.4, 0.4), (-0.4, 0.4)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_oklab_xyz_conversion_d65() {
+        let xyz = XYZColor {
+            x: 0.4,
+            y: 0.2,
+            z: 0.6,
+            illuminant: Illuminant::D65,
+        };
+        let oklab = OklabColor::from_xyz(xyz);
+        let xyz2 = oklab.to_xyz(Illuminant::D65);
+        assert!(xyz.approx_equal(&xyz2));
+        assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+    }
+    #[test]
+    fn test_oklab_xyz_conversion_d50() {
+        let xyz = XYZColor {
+            x: 0.3,
+            y: 0.53,
+            z: 0.65,
+            illuminant: Illuminant::D50,
+        };
+        let oklab: OklabColor = xyz.convert();
+        let xyz2: XYZColor = oklab.convert();
+        assert!(xyz2.approx_visually_equal(&xyz));
+        assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+    }
+    #[test]
+    fn test_oklab_reference_values() {
+        // these test values are from the original Oklab post
+        let cases = [
+            ([0.950, 1.000, 1.089], [1.000, 0.000, 0.000]),
+            ([1.000, 0.000, 0.000], [0.450, 1.236, -0.019]),
+            ([0.000, 1.000, 0.000], [0.922, -0.671, 0.263]),
+            ([0.000, 0.000, 1.000], [0.153, -1.415, -0.449]),
+        ];
+        for &(xyz, lab) in cases.iter() {
+            let oklab = OklabColor::from_xyz(XYZColor {
+                x: xyz[0],
+                y: xyz[1],
+                z: xyz[2],
+                illuminant: Illuminant::D65,
+            });
+            assert!((oklab.l - lab[0]).abs() <= 1e-3);
+            assert!((oklab.a - lab[1]).abs() <= 1e-3);
+            assert!((oklab.b - lab[2]).abs() <= 1e-3);
+        }
+    }
+}
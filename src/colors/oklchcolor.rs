@@ -0,0 +1,123 @@
+//! This module implements Oklch, the cylindrical form of [Oklab](../oklabcolor/index.html), just
+//! as CIELCH is the cylindrical form of CIELAB. Its hue is much more stable than CIELCH's when
+//! chroma changes, which makes it the preferred space for building palettes by hand.
+
+use super::oklabcolor::OklabColor;
+use bound::Bound;
+use color::{Color, XYZColor};
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// A color in the Oklch color space: Oklab with chroma and hue instead of two opponent axes.
+/// # Example
+/// Build a set of equally-light, equally-colorful swatches by only rotating hue.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::OklchColor;
+/// let swatches: Vec<RGBColor> = (0..6)
+///     .map(|i| OklchColor{l: 0.7, c: 0.1, h: 60. * i as f64}.convert())
+///     .collect();
+/// for swatch in swatches.iter() {
+///     let back: OklchColor = swatch.convert();
+///     // rounding to sRGB barely moves the lightness
+///     assert!((back.l - 0.7).abs() <= 0.01);
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct OklchColor {
+    /// The lightness component, identical to Oklab's. Ranges between 0 and 1.
+    pub l: f64,
+    /// The chroma component: the distance from the neutral axis in Oklab. It is 0 for grays and
+    /// rarely goes above 0.4 for real colors, although as in every cylindrical space the maximum
+    /// depends strongly on lightness and hue.
+    pub c: f64,
+    /// The hue component, in degrees from 0 to 360. Roughly, 30 degrees is pink-red, 110 is
+    /// yellow, 140 is green, and 265 is blue.
+    pub h: f64,
+}
+
+impl Color for OklchColor {
+    /// Converts from XYZ to Oklch by way of Oklab.
+    fn from_xyz(xyz: XYZColor) -> OklchColor {
+        let lab = OklabColor::from_xyz(xyz);
+        let c = lab.b.hypot(lab.a);
+        // atan2 gives a value between -180 and 180 degrees: shift negative values into range
+        let unbounded_h = lab.b.atan2(lab.a).to_degrees();
+        let h = if unbounded_h < 0.0 {
+            unbounded_h + 360.0
+        } else {
+            unbounded_h
+        };
+        OklchColor { l: lab.l, c, h }
+    }
+    /// Converts from Oklch back to XYZ by way of Oklab, chromatically adapting it as Oklab does.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        let (sin, cos) = self.h.to_radians().sin_cos();
+        OklabColor {
+            l: self.l,
+            a: self.c * cos,
+            b: self.c * sin,
+        }
+        .to_xyz(illuminant)
+    }
+}
+
+impl From<Coord> for OklchColor {
+    fn from(c: Coord) -> OklchColor {
+        OklchColor {
+            l: c.x,
+            c: c.y,
+            h: c.z,
+        }
+    }
+}
+
+impl From<OklchColor> for Coord {
+    fn from(val: OklchColor) -> Self {
+        Coord {
+            x: val.l,
+            y: val.c,
+            z: val.h,
+        }
+    }
+}
+
+impl Bound for OklchColor {
+    fn bounds() -> [(f64, f64); 3] {
+        [(0., 1.), (0., 0.4), (0., 360.)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_oklch_xyz_conversion() {
+        let xyz = XYZColor {
+            x: 0.2,
+            y: 0.42,
+            z: 0.23,
+            illuminant: Illuminant::D65,
+        };
+        let lch: OklchColor = xyz.convert();
+        let xyz2: XYZColor = lch.convert();
+        assert!(xyz2.approx_visually_equal(&xyz));
+        assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+    }
+    #[test]
+    fn test_oklch_matches_oklab() {
+        let lab = OklabColor {
+            l: 0.6,
+            a: -0.1,
+            b: -0.05,
+        };
+        let lch: OklchColor = lab.convert();
+        assert!((lch.c - 0.1f64.hypot(0.05)).abs() <= 1e-10);
+        assert!(lch.h > 180. && lch.h < 270.);
+        assert!(lab.distance(&lch) <= TEST_PRECISION);
+    }
+}
@@ -0,0 +1,92 @@
+//! A module that implements the Rec. 2020 color space from ITU-R BT.2020, the standard for
+//! ultra-high-definition television. Its primaries are monochromatic lights on the spectral locus, so
+//! its gamut is far wider than sRGB or Display P3, covering about 75% of all visible colors. No
+//! current display can show all of it.
+
+use rgbcolorspace::RGBColorSpace;
+
+rgb_space_color! {
+    /// A color in the Rec. 2020 color space. Components range from 0 to 1 for colors the space can
+    /// represent. Converting a color outside of the gamut doesn't clip it, so use
+    /// [`Bound::clamp`](../../bound/trait.Bound.html#method.clamp) before displaying it if needed.
+    /// # Example
+    /// Display P3's green is well inside Rec. 2020, even though it's outside of sRGB.
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::{DisplayP3Color, Rec2020Color};
+    /// let p3_green = DisplayP3Color{r: 0., g: 1., b: 0.};
+    /// let rec2020_green: Rec2020Color = p3_green.convert();
+    /// assert!(rec2020_green.r > 0.0 && rec2020_green.g < 1.0 && rec2020_green.b > 0.0);
+    /// let srgb_green: RGBColor = p3_green.convert();
+    /// assert!(srgb_green.r < 0.0);
+    /// // hex codes work just like RGBColor's
+    /// assert_eq!(Rec2020Color{r: 0., g: 1., b: 0.}.to_string(), "#00FF00");
+    /// assert_eq!(Rec2020Color::from_hex_code("#3a7bd5").unwrap().to_string(), "#3A7BD5");
+    /// ```
+    pub struct Rec2020Color => RGBColorSpace::REC2020;
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use bound::Bound;
+    use color::{Color, RGBColor, XYZColor};
+    use consts::TEST_PRECISION;
+    use illuminants::Illuminant;
+    use parseerror::{ColorParseError, ParseErrorKind, ParseGrammar};
+
+    #[test]
+    fn test_rec2020_xyz_conversion() {
+        let xyz1 = XYZColor {
+            x: 0.4,
+            y: 0.2,
+            z: 0.5,
+            illuminant: Illuminant::D75,
+        };
+        let xyz2 = Rec2020Color::from_xyz(xyz1).to_xyz(Illuminant::D75);
+        assert!(xyz1.approx_equal(&xyz2));
+        assert!(xyz1.distance(&xyz2) <= TEST_PRECISION);
+    }
+    #[test]
+    fn test_rec2020_reference_values() {
+        // the CSS Color 4 spec gives sRGB red as color(rec2020 0.7919 0.2310 0.0738)
+        let red: Rec2020Color = RGBColor {
+            r: 1.,
+            g: 0.,
+            b: 0.,
+        }
+        .convert();
+        assert!((red.r - 0.7919).abs() <= 1e-3);
+        assert!((red.g - 0.2310).abs() <= 1e-3);
+        assert!((red.b - 0.0738).abs() <= 1e-3);
+    }
+    #[test]
+    fn test_rec2020_clamping() {
+        let green = Rec2020Color {
+            r: -0.1,
+            g: 1.2,
+            b: 0.5,
+        };
+        // clamping converts out of this space and back again, so it isn't exact
+        let clamped = Rec2020Color::clamp(green);
+        assert!((clamped.r - 0.).abs() <= 1e-10);
+        assert!((clamped.g - 1.).abs() <= 1e-10);
+        assert!((clamped.b - 0.5).abs() <= 1e-10);
+    }
+    #[test]
+    fn test_rec2020_hex_round_trip() {
+        for hex in ["#000000", "#FFFFFF", "#123456", "#BADA55"].iter() {
+            let color: Rec2020Color = hex.parse().unwrap();
+            assert_eq!(&color.to_string(), hex);
+        }
+        assert_eq!(
+            "#12345".parse::<Rec2020Color>(),
+            Err(
+                ColorParseError::new(ParseErrorKind::InvalidSyntax, ParseGrammar::Hex, 1..6)
+                    .expecting("3 or 6 hex digits")
+            )
+        );
+    }
+}
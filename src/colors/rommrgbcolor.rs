@@ -14,6 +14,7 @@ use consts::ROMM_RGB_TRANSFORM as ROMM;
 use consts::ROMM_RGB_TRANSFORM_LU as ROMM_LU;
 use coord::Coord;
 use illuminants::Illuminant;
+use rgbcolorspace::RGBColorSpace;
 
 /// A color in the ROMM RGB color space, also known as the ProPhoto RGB space. This is a very wide RGB
 /// gamut, wider than both Adobe RGB and sRGB, but the tradeoff is that the colors it uses as
@@ -180,6 +181,11 @@ impl Bound for ROMMRGBColor {
     fn bounds() -> [(f64, f64); 3] {
         [(0., 1.), (0., 1.), (0., 1.)]
     }
+    /// Checks the color against the ROMM RGB primaries without clamping it, as converting to this type
+    /// clamps every component to the gamut.
+    fn in_gamut<T: Color>(color: &T, tolerance: f64) -> bool {
+        RGBColorSpace::PROPHOTO_RGB.in_gamut(color, tolerance)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,254 @@
+//! This module implements correlated color temperature (CCT), the usual way of describing the color
+//! of a white light: the temperature of the blackbody whose color is closest to it. As most lights
+//! aren't exactly the color of any blackbody, the distance from the blackbody (Planckian) locus,
+//! called *Duv*, is given as well. Both are measured in the CIE 1960 UCS diagram, as the CIE
+//! specifies.
+//!
+//! [`Color::cct`] uses Ohno's 2013 method, which is accurate to well within a kelvin everywhere it's
+//! meaningful. [`Color::approximate_cct`] uses McCamy's cubic formula, which is much faster but is
+//! only accurate to about 10 kelvin near the locus between about 2800 K and 6500 K, and doesn't give
+//! Duv.
+//!
+//! [`Color::cct`]: ../color/trait.Color.html#method.cct
+//! [`Color::approximate_cct`]: ../color/trait.Color.html#method.approximate_cct
+
+use color::XYZColor;
+use illuminants::Illuminant;
+
+/// The lowest temperature in the table of the Planckian locus used to find CCT.
+const MIN_CCT: f64 = 1000.0;
+/// The highest temperature in the table of the Planckian locus used to find CCT.
+const MAX_CCT: f64 = 100_000.0;
+
+/// The correlated color temperature of a color, along with its distance from the Planckian locus.
+/// # Example
+/// D65 is a little greener than a blackbody at the same temperature.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::XYZColor;
+/// let d65 = XYZColor::white_point(Illuminant::D65).cct();
+/// assert!((d65.cct - 6504.).abs() <= 2.);
+/// assert!((d65.duv - 0.0032).abs() <= 1e-4);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ColorTemperature {
+    /// The correlated color temperature, in kelvin.
+    pub cct: f64,
+    /// The signed distance from the Planckian locus in the CIE 1960 UCS diagram: positive above the
+    /// locus, towards green, and negative below it, towards pink. The CIE considers CCT meaningless
+    /// for colors further than 0.05 from the locus, and lights meant to look white are usually within
+    /// 0.006 of it.
+    pub duv: f64,
+}
+
+/// A point on the Planckian locus, in CIE 1960 UCS.
+#[derive(Debug, Copy, Clone)]
+struct LocusPoint {
+    t: f64,
+    u: f64,
+    v: f64,
+}
+
+impl LocusPoint {
+    /// Gets the point on the Planckian locus at the given temperature.
+    fn at(t: f64) -> LocusPoint {
+        let (u, v) = uv(Illuminant::Planckian(t).white_point());
+        LocusPoint { t, u, v }
+    }
+    /// Gets the distance between this point and the given UCS coordinates.
+    fn distance(&self, u: f64, v: f64) -> f64 {
+        (u - self.u).hypot(v - self.v)
+    }
+}
+
+lazy_static! {
+    // the table Ohno's method starts with, with temperatures 1% apart
+    static ref PLANCKIAN_LOCUS: Vec<LocusPoint> = {
+        let mut points = vec![];
+        let mut t = MIN_CCT;
+        while t <= MAX_CCT {
+            points.push(LocusPoint::at(t));
+            t *= 1.01;
+        }
+        points
+    };
+}
+
+/// Gets the CIE 1960 UCS coordinates of the given XYZ values.
+fn uv(xyz: [f64; 3]) -> (f64, f64) {
+    let denom = xyz[0] + 15.0 * xyz[1] + 3.0 * xyz[2];
+    (4.0 * xyz[0] / denom, 6.0 * xyz[1] / denom)
+}
+
+impl ColorTemperature {
+    /// Computes the correlated color temperature and Duv of the given XYZ values with Ohno's
+    /// method, from *Practical Use and Calculation of CCT and Duv* (2013). The illuminant of the
+    /// color is ignored, as CCT describes the color of a light and not how it's seen.
+    /// Temperatures from 1000 K to 100,000 K are found exactly: outside of that, the result is
+    /// extrapolated.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::color::XYZColor;
+    /// # use scarlet::colortemperature::ColorTemperature;
+    /// let warm_white = ColorTemperature::from_xyz(XYZColor::from_blackbody(2700.));
+    /// assert!((warm_white.cct - 2700.).abs() <= 0.01);
+    /// assert!(warm_white.duv.abs() <= 1e-6);
+    /// ```
+    pub fn from_xyz(xyz: XYZColor) -> ColorTemperature {
+        let (u, v) = uv([xyz.x, xyz.y, xyz.z]);
+        let closest = |points: &[LocusPoint]| {
+            let mut min = 0;
+            for (i, point) in points.iter().enumerate() {
+                if point.distance(u, v) < points[min].distance(u, v) {
+                    min = i;
+                }
+            }
+            // we need a point on either side
+            min.clamp(1, points.len() - 2)
+        };
+        // the table is too coarse to be accurate, so we repeatedly make a finer table between the
+        // neighbors of the closest point: Ohno calls this cascading
+        let mut points = PLANCKIAN_LOCUS.clone();
+        let mut m = closest(&points);
+        while points[m + 1].t / points[m - 1].t > 1.0001 {
+            let (lo, hi) = (points[m - 1].t, points[m + 1].t);
+            points = (0..11)
+                .map(|i| LocusPoint::at(lo * (hi / lo).powf(f64::from(i) / 10.0)))
+                .collect();
+            m = closest(&points);
+        }
+        let (p0, p1, p2) = (points[m - 1], points[m], points[m + 1]);
+        let (d0, d1, d2) = (p0.distance(u, v), p1.distance(u, v), p2.distance(u, v));
+
+        // the triangular solution, which is accurate near the locus
+        let l = (p2.u - p0.u).hypot(p2.v - p0.v);
+        let x = (d0 * d0 - d2 * d2 + l * l) / (2.0 * l);
+        let t_x = p0.t + (p2.t - p0.t) * x / l;
+        let v_x = p0.v + (p2.v - p0.v) * x / l;
+        let duv = (d0 * d0 - x * x).max(0.0).sqrt() * (v - v_x).signum();
+        if duv.abs() < 0.002 {
+            return ColorTemperature { cct: t_x, duv };
+        }
+
+        // further away, the parabolic solution fitting the distances is better
+        let denom = (p2.t - p1.t) * (p0.t - p2.t) * (p1.t - p0.t);
+        let a = (p0.t * (d2 - d1) + p1.t * (d0 - d2) + p2.t * (d1 - d0)) / denom;
+        let b = -(p0.t.powi(2) * (d2 - d1) + p1.t.powi(2) * (d0 - d2) + p2.t.powi(2) * (d1 - d0))
+            / denom;
+        let c = -(d0 * (p2.t - p1.t) * p1.t * p2.t
+            + d1 * (p0.t - p2.t) * p0.t * p2.t
+            + d2 * (p1.t - p0.t) * p0.t * p1.t)
+            / denom;
+        let cct = -b / (2.0 * a);
+        let duv = (a * cct * cct + b * cct + c) * (v - LocusPoint::at(cct).v).signum();
+        ColorTemperature { cct, duv }
+    }
+    /// Approximates the correlated color temperature of the given XYZ values with McCamy's cubic
+    /// formula from 1992. This is much faster than [`from_xyz`](#method.from_xyz), but it's only
+    /// accurate to within about 10 kelvin for colors near the Planckian locus from about 2800 K to
+    /// 6500 K, and gets much worse outside of that.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::XYZColor;
+    /// # use scarlet::colortemperature::ColorTemperature;
+    /// let d50 = XYZColor::white_point(Illuminant::D50);
+    /// let exact = ColorTemperature::from_xyz(d50).cct;
+    /// assert!((ColorTemperature::mccamy(d50) - exact).abs() <= 2.);
+    /// ```
+    pub fn mccamy(xyz: XYZColor) -> f64 {
+        let sum = xyz.x + xyz.y + xyz.z;
+        let (x, y) = (xyz.x / sum, xyz.y / sum);
+        let n = (x - 0.3320) / (y - 0.1858);
+        -449.0 * n.powi(3) + 3525.0 * n.powi(2) - 6823.3 * n + 5520.33
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    /// Gets XYZ values a given distance from the Planckian locus, perpendicular to it, at the given
+    /// temperature.
+    fn off_locus(t: f64, duv: f64) -> XYZColor {
+        let (p, next) = (LocusPoint::at(t), LocusPoint::at(t * 1.0001));
+        let (du, dv) = (next.u - p.u, next.v - p.v);
+        let norm = du.hypot(dv);
+        // the normal pointing towards positive v
+        let (nu, nv) = if du < 0.0 {
+            (dv / norm, -du / norm)
+        } else {
+            (-dv / norm, du / norm)
+        };
+        let (u, v) = (p.u + duv * nu, p.v + duv * nv);
+        let denom = 2.0 * u - 8.0 * v + 4.0;
+        let (x, y) = (3.0 * u / denom, 2.0 * v / denom);
+        XYZColor {
+            x: x / y,
+            y: 1.0,
+            z: (1.0 - x - y) / y,
+            illuminant: Illuminant::D65,
+        }
+    }
+
+    #[test]
+    fn test_blackbody() {
+        for &t in [1000.0, 1850.0, 2700.0, 4000.0, 6500.0, 10000.0, 50000.0].iter() {
+            let temp = ColorTemperature::from_xyz(XYZColor::from_blackbody(t));
+            assert!((temp.cct - t).abs() <= 1e-4 * t, "{} != {}", temp.cct, t);
+            assert!(temp.duv.abs() <= 1e-6);
+        }
+    }
+    #[test]
+    #[should_panic(expected = "must be positive")]
+    fn test_blackbody_zero() {
+        XYZColor::from_blackbody(0.0);
+    }
+    #[test]
+    #[should_panic(expected = "must be positive")]
+    fn test_blackbody_negative() {
+        XYZColor::from_blackbody(-1500.0);
+    }
+    #[test]
+    fn test_off_locus() {
+        // both sides of the threshold between the triangular and parabolic solutions
+        for &(t, duv) in [
+            (2000.0, 0.001),
+            (3000.0, -0.015),
+            (4000.0, 0.02),
+            (6500.0, -0.0015),
+            (15000.0, -0.01),
+        ]
+        .iter()
+        {
+            let temp = ColorTemperature::from_xyz(off_locus(t, duv));
+            assert!((temp.cct - t).abs() <= 0.1, "{} != {}", temp.cct, t);
+            assert!((temp.duv - duv).abs() <= 1e-6, "{} != {}", temp.duv, duv);
+        }
+    }
+    #[test]
+    fn test_illuminants() {
+        // illuminant A is a blackbody at 2848 K with an older constant: 2856 K with the current one
+        let a = ColorTemperature::from_xyz(XYZColor::white_point(Illuminant::A));
+        assert!((a.cct - 2855.5).abs() <= 0.1);
+        assert!(a.duv.abs() <= 1e-6);
+        // the CIE gives 4230 K for F2, rounded
+        let f2 = ColorTemperature::from_xyz(XYZColor::white_point(Illuminant::F2));
+        assert!((f2.cct - 4230.0).abs() <= 10.0);
+        // D50 is nominally 5000 K, but 5003 K with the current constant
+        let d50 = ColorTemperature::from_xyz(XYZColor::white_point(Illuminant::D50));
+        assert!((d50.cct - 5003.0).abs() <= 2.0);
+        assert!(d50.duv > 0.0);
+    }
+    #[test]
+    fn test_mccamy() {
+        for &t in [3000.0, 4000.0, 5000.0, 6500.0].iter() {
+            let xyz = XYZColor::from_blackbody(t);
+            assert!((ColorTemperature::mccamy(xyz) - t).abs() <= 10.0);
+        }
+    }
+}
@@ -29,6 +29,58 @@ lazy_static! {
     };
     pub(crate) static ref BRADFORD_TRANSFORM_LU: PartialPivLu<f64> =
         { PartialPivLu::decompose(BRADFORD_TRANSFORM.clone()).expect("Matrix is invertible.") };
+    pub(crate) static ref CAT16_TRANSFORM: Matrix<f64> = {
+        matrix![00.401288, 0.650173, -0.051461;
+                -0.250268, 1.204414, 00.045854;
+                -0.002079, 0.048952, 00.953127]
+    };
+    pub(crate) static ref CAT16_TRANSFORM_LU: PartialPivLu<f64> =
+        PartialPivLu::decompose(CAT16_TRANSFORM.clone()).expect("Matrix is invertible.");
+    pub(crate) static ref XYZ_SCALING_TRANSFORM: Matrix<f64> = Matrix::identity(3);
+    pub(crate) static ref XYZ_SCALING_TRANSFORM_LU: PartialPivLu<f64> =
+        PartialPivLu::decompose(XYZ_SCALING_TRANSFORM.clone()).expect("Matrix is invertible.");
+    pub(crate) static ref VON_KRIES_TRANSFORM: Matrix<f64> = {
+        matrix![00.40024, 0.70760, -0.08081;
+                -0.22630, 1.16532, 00.04570;
+                00.00000, 0.00000, 00.91822]
+    };
+    pub(crate) static ref VON_KRIES_TRANSFORM_LU: PartialPivLu<f64> =
+        PartialPivLu::decompose(VON_KRIES_TRANSFORM.clone()).expect("Matrix is invertible.");
+    pub(crate) static ref CAT02_TRANSFORM: Matrix<f64> = {
+        matrix![00.7328, 0.4296, -0.1624;
+                -0.7036, 1.6975, 00.0061;
+                00.0030, 0.0136, 00.9834]
+    };
+    pub(crate) static ref CAT02_TRANSFORM_LU: PartialPivLu<f64> =
+        PartialPivLu::decompose(CAT02_TRANSFORM.clone()).expect("Matrix is invertible.");
+    pub(crate) static ref SHARP_TRANSFORM: Matrix<f64> = {
+        matrix![01.2694, -0.0988, -0.1706;
+                -0.8364, 01.8006, 00.0357;
+                00.0297, -0.0315, 01.0018]
+    };
+    pub(crate) static ref SHARP_TRANSFORM_LU: PartialPivLu<f64> =
+        PartialPivLu::decompose(SHARP_TRANSFORM.clone()).expect("Matrix is invertible.");
+    pub(crate) static ref CMCCAT2000_TRANSFORM: Matrix<f64> = {
+        matrix![00.7982, 0.3389, -0.1371;
+                -0.5918, 1.5512, 00.0406;
+                00.0008, 0.0239, 00.9753]
+    };
+    pub(crate) static ref CMCCAT2000_TRANSFORM_LU: PartialPivLu<f64> =
+        PartialPivLu::decompose(CMCCAT2000_TRANSFORM.clone()).expect("Matrix is invertible.");
+    pub(crate) static ref OKLAB_LMS_TRANSFORM: Matrix<f64> = {
+        matrix![0.8189330101, 0.3618667424, -0.1288597137;
+                0.0329845436, 0.9293118715, 0.0361456387;
+                0.0482003018, 0.2643662691, 0.6338517070]
+    };
+    pub(crate) static ref OKLAB_LMS_TRANSFORM_LU: PartialPivLu<f64> =
+        PartialPivLu::decompose(OKLAB_LMS_TRANSFORM.clone()).expect("Matrix is invertible.");
+    pub(crate) static ref OKLAB_LAB_TRANSFORM: Matrix<f64> = {
+        matrix![0.2104542553, 00.7936177850, -0.0040720468;
+                1.9779984951, -2.4285922050, 00.4505937099;
+                0.0259040371, 00.7827717662, -0.8086757660]
+    };
+    pub(crate) static ref OKLAB_LAB_TRANSFORM_LU: PartialPivLu<f64> =
+        PartialPivLu::decompose(OKLAB_LAB_TRANSFORM.clone()).expect("Matrix is invertible.");
     pub(crate) static ref ROMM_RGB_TRANSFORM: Matrix<f64> = {
         matrix![0.7976749, 0.1351917, 0.0313534;
                 0.2880402, 0.7118741, 0.0000857;
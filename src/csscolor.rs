@@ -1,17 +1,36 @@
-//! This file uses the CSS numeric parsing in `cssnumeric.rs` to parse CSS functional color notation
-//! according to the W3 specification. The only difference is that arithmetic is not supported to
-//! specify colors. Its end goal is the implementation of FromStr for RGB, HSL, and HSV colors,
-//! although the specific `impl` blocks are in their respective source files. You can see the full
-//! spec here: [https://www.w3.org/TR/css-color-3/](https://www.w3.org/TR/css-color-3/). One quick caveat:
-//! as is relatively standard, percents are only integral: "45.5%" will be treated as invalid.
-
-pub(crate) use cssnumeric::CSSParseError;
+//! This module parses CSS color syntax into Scarlet colors. It handles two generations of the
+//! specification. The functions that implement FromStr for RGB, HSL, and HSV colors follow [CSS
+//! Color Level 3](https://www.w3.org/TR/css-color-3/), using the CSS numeric parsing in
+//! `cssnumeric.rs`: arguments are separated by commas, and as is relatively standard for that level,
+//! percents are only integral: "45.5%" will be treated as invalid. On top of that, [`CSSColor`]
+//! implements the full [CSS Color Level 4](https://www.w3.org/TR/css-color-4/) grammar: space-separated
+//! arguments, alpha after a slash, fractional percentages, `none`, angle units, and the `hwb()`,
+//! `lab()`, `lch()`, `oklab()`, `oklch()`, and `color()` functions. In both cases, arithmetic such
+//! as `calc()` is not supported. Going the other way, [`ToCSSString`] writes any Scarlet color as
+//! CSS Color Level 4 syntax that this module can parse again.
+//!
+//! [`CSSColor`]: enum.CSSColor.html
+//! [`ToCSSString`]: trait.ToCSSString.html
+
+use alpha::Alpha;
+use color::{Color, RGBColor, XYZColor};
+use colors::{
+    AdobeRGBColor, CAM16Color, CAM16LCDColor, CAM16SCDColor, CAM16UCSColor, CIELABColor,
+    CIELCHColor, CIELCHuvColor, CIELUVColor, CustomRGBColor, DisplayP3Color, HSLColor, HSVColor,
+    LinearRGBColor, OklabColor, OklchColor, ROMMRGBColor, Rec2020Color,
+};
+pub use cssnumeric::CSSParseError;
 use cssnumeric::{parse_css_number, CSSNumeric};
+use illuminants::Illuminant;
+use parseerror::{span_of, ColorParseError, ParseErrorKind, ParseGrammar};
+use rgbcolorspace::{ProPhotoRGB, RGBSpace};
+use std::ops::Range;
+use std::str::FromStr;
 
 /// Given a string, attempts to parse as a CSS numeric. If successful, interprets the number given as
 /// a component of an RGB color, clamping accordingly. Returns the appropriate `u8`: e.g., "102%" maps
 /// to 255, and "34.5" maps to 35. Gives an error on invalid input.
-fn parse_rgb_num(num: &str) -> Result<u8, CSSParseError> {
+fn parse_rgb_num(num: &str) -> Result<u8, ColorParseError> {
     let parsed_num = parse_css_number(num)?;
     match parsed_num {
         // integer: clamp to 0-255 and use directly
@@ -52,65 +71,147 @@ fn parse_rgb_num(num: &str) -> Result<u8, CSSParseError> {
     }
 }
 
+/// Makes an error in a CSS color function.
+fn func_error(kind: ParseErrorKind, span: Range<usize>) -> ColorParseError {
+    ColorParseError::new(kind, ParseGrammar::Functional, span)
+}
+
+/// Returns a function that moves an error reported relative to `part`, a slice of `input`, to where
+/// `part` is in `input`.
+fn relative_to(input: &str, part: &str) -> impl Fn(ColorParseError) -> ColorParseError {
+    let start = span_of(input, part).start;
+    move |err| err.offset(start)
+}
+
+/// Checks that a CSS function has one of the given names, returning the rest of it from the opening
+/// parenthesis on. `expected` describes the allowed names, for the error if the name is wrong.
+pub(crate) fn strip_function_name<'a>(
+    input: &'a str,
+    names: &[&str],
+    expected: &'static str,
+) -> Result<&'a str, ColorParseError> {
+    let open = match input.find('(') {
+        Some(open) => open,
+        None => {
+            return Err(
+                func_error(ParseErrorKind::InvalidSyntax, input.len()..input.len())
+                    .expecting("'('"),
+            )
+        }
+    };
+    if !names.contains(&&input[..open]) {
+        return Err(func_error(ParseErrorKind::UnknownName, 0..open).expecting(expected));
+    }
+    Ok(&input[open..])
+}
+
+/// Splits the comma-separated arguments of a legacy CSS function, given as a parenthesized tuple
+/// like "(1, 2, 3)", into between `min` and `max` trimmed arguments. Error spans are relative to
+/// the tuple.
+fn split_legacy_tuple(tup: &str, min: usize, max: usize) -> Result<Vec<&str>, ColorParseError> {
+    let args = match tup.strip_prefix('(') {
+        Some(args) => args,
+        None => return Err(func_error(ParseErrorKind::InvalidSyntax, 0..0).expecting("'('")),
+    };
+    let args = match args.strip_suffix(')') {
+        Some(args) => args,
+        None => {
+            return Err(
+                func_error(ParseErrorKind::InvalidSyntax, tup.len()..tup.len()).expecting("')'"),
+            )
+        }
+    };
+    let parts: Vec<&str> = args.split(',').map(|part| part.trim()).collect();
+    if parts.len() > max {
+        // point at the first extra argument, which should have been the end
+        let extra = span_of(tup, parts[max]);
+        let comma = tup[..extra.start].rfind(',').unwrap_or(extra.start);
+        return Err(func_error(ParseErrorKind::InvalidSyntax, comma..extra.end).expecting("')'"));
+    }
+    if parts.len() < min {
+        let end = tup.len() - 1;
+        return Err(func_error(ParseErrorKind::InvalidSyntax, end..end).expecting("','"));
+    }
+    Ok(parts)
+}
+
 /// Parses a string of the form "rgb(r, g, b)", where r, g, and b are numbers, returning a tuple of
-/// u8s for the three components. Gives a CSSParseError on invalid input.
-pub(crate) fn parse_rgb_str(num: &str) -> Result<(u8, u8, u8), CSSParseError> {
-    // must have at least 10 characters
-    // has to start with "rgb(" or not a valid color
-    if !num.starts_with("rgb(") || num.len() < 10 {
-        return Err(CSSParseError::InvalidColorSyntax);
-    }
-    // remove first four chars, put in Vec
-    let mut chars: Vec<char> = num.chars().skip(4).collect();
-    // check for and remove parenthesis
-    if chars.iter().last().unwrap() != &')' {
-        return Err(CSSParseError::InvalidColorSyntax);
-    }
-    chars.pop();
-
-    // test for disallowed characters
-    if chars.iter().any(|&c| !"0123456789+-,. %".contains(c)) {
-        println!("hi");
-        return Err(CSSParseError::InvalidColorSyntax);
-    }
-    // this now requires a very specific format: three commas, a parenthesis at the end, and spaces
-    // in between
-    // check for commas (the right number of them) and split into numbers, remove whitespace,
-    // parse, and recombine
-    let split_iter = (&chars).split(|c| c == &',');
-    // now remove surrounding whitespace and pass to number parsing, propagating errors
+/// u8s for the three components. Gives a ColorParseError on invalid input.
+pub(crate) fn parse_rgb_str(num: &str) -> Result<(u8, u8, u8), ColorParseError> {
+    let tup = strip_function_name(num, &["rgb"], "'rgb'")?;
+    // this now requires a very specific format: three comma-separated numbers in parentheses,
+    // with optional whitespace in between
+    let parts = split_legacy_tuple(tup, 3, 3).map_err(relative_to(num, tup))?;
+    // now pass to number parsing, propagating errors
     let mut nums: Vec<u8> = vec![];
-    for split in split_iter {
-        nums.push(parse_rgb_num(&(split.iter().collect::<String>().trim()))?);
-    }
-    if nums.len() != 3 {
-        return Err(CSSParseError::InvalidColorSyntax);
+    for part in parts {
+        nums.push(parse_rgb_num(part).map_err(relative_to(num, part))?);
     }
     Ok((nums[0], nums[1], nums[2]))
 }
 
+/// Interprets a CSS numeric as an alpha value, clamping it between 0 and 1. Numbers are used
+/// directly, and percentages are divided by 100.
+fn alpha_from_numeric(num: CSSNumeric) -> f64 {
+    let alpha = match num {
+        CSSNumeric::Integer(val) => val as f64,
+        CSSNumeric::Float(val) => val,
+        CSSNumeric::Percentage(val) => (val as f64) / 100.,
+    };
+    alpha.clamp(0., 1.)
+}
+
+/// Parses a string of the form "rgba(r, g, b, a)" or "rgb(r, g, b)", where r, g, and b are numbers
+/// as in [`parse_rgb_str`] and a is an alpha value between 0 and 1 or a percentage. Either function
+/// name may be used with or without the alpha, as CSS allows, and a missing alpha is 1. Gives a
+/// ColorParseError on invalid input.
+pub(crate) fn parse_rgba_str(num: &str) -> Result<(u8, u8, u8, f64), ColorParseError> {
+    let tup = strip_function_name(num, &["rgb", "rgba"], "'rgb' or 'rgba'")?;
+    let parts = split_legacy_tuple(tup, 3, 4).map_err(relative_to(num, tup))?;
+    let alpha = match parts.get(3) {
+        Some(part) => alpha_from_numeric(parse_css_number(part).map_err(relative_to(num, part))?),
+        None => 1.,
+    };
+    let channel = |part: &str| parse_rgb_num(part).map_err(relative_to(num, part));
+    Ok((
+        channel(parts[0])?,
+        channel(parts[1])?,
+        channel(parts[2])?,
+        alpha,
+    ))
+}
+
 /// Parses an HSL or HSV tuple, given after "hsl" or "hsv" in normal CSS, such as "(250, 50%, 50%)"
 /// into a tuple (f64, f64, f64) such that the first float lies within the range 0-360 and the other
-/// two lie within the range 0-1. Gives a CSSParseError if invalid.
-pub(crate) fn parse_hsl_hsv_tuple(tup: &str) -> Result<(f64, f64, f64), CSSParseError> {
-    // must have '(' at start and ')' at end: remove them, and store in chars vec
-    if !tup.starts_with('(') || !tup.ends_with(')') {
-        return Err(CSSParseError::InvalidColorSyntax);
-    }
-    let mut chars: Vec<char> = tup.chars().skip(1).collect();
-    chars.pop();
+/// two lie within the range 0-1. Gives a ColorParseError if invalid.
+pub(crate) fn parse_hsl_hsv_tuple(tup: &str) -> Result<(f64, f64, f64), ColorParseError> {
+    let (h, s, l_or_v, _alpha) = parse_hsl_args(tup, false)?;
+    Ok((h, s, l_or_v))
+}
 
-    // split with commas: must be 3 distinct things
-    let split_iter = (&chars).split(|c| c == &',');
+/// Parses an HSL or HSV tuple like [`parse_hsl_hsv_tuple`], but also accepts an optional fourth
+/// alpha component as in "(250, 50%, 50%, 0.5)". The alpha is returned as `None` if absent.
+pub(crate) fn parse_hsla_hsva_tuple(
+    tup: &str,
+) -> Result<(f64, f64, f64, Option<f64>), ColorParseError> {
+    parse_hsl_args(tup, true)
+}
+
+/// Parses an HSL or HSV tuple, with an optional alpha if `allow_alpha` is true.
+fn parse_hsl_args(
+    tup: &str,
+    allow_alpha: bool,
+) -> Result<(f64, f64, f64, Option<f64>), ColorParseError> {
+    // split with commas: must be 3 distinct things, or 4 with an alpha
+    let max = if allow_alpha { 4 } else { 3 };
+    let parts = split_legacy_tuple(tup, 3, max)?;
     let mut numerics: Vec<CSSNumeric> = vec![];
-    for split in split_iter {
-        numerics.push(parse_css_number(
-            &(split.iter().collect::<String>().trim()),
-        )?);
-    }
-    if numerics.len() != 3 {
-        return Err(CSSParseError::InvalidColorSyntax);
+    for part in parts.iter() {
+        numerics.push(parse_css_number(part).map_err(relative_to(tup, part))?);
     }
+    let wrong_type = |i: usize, expected| {
+        func_error(ParseErrorKind::WrongValueType, span_of(tup, parts[i])).expecting(expected)
+    };
     // hue is special: require float or integer, normalize to 0-360
     let hue: f64 = match numerics[0] {
         CSSNumeric::Integer(val) => {
@@ -133,7 +234,7 @@ pub(crate) fn parse_hsl_hsv_tuple(tup: &str) -> Result<(f64, f64, f64), CSSParse
             }
             clamped
         }
-        _ => return Err(CSSParseError::InvalidColorSyntax),
+        _ => return Err(wrong_type(0, "a hue")),
     };
     // saturation and lightness/value all work the same way: clamp between 0 and 1 and expect a
     // percentage
@@ -147,7 +248,7 @@ pub(crate) fn parse_hsl_hsv_tuple(tup: &str) -> Result<(f64, f64, f64), CSSParse
                 (val as f64) / 100.
             }
         }
-        _ => return Err(CSSParseError::InvalidColorSyntax),
+        _ => return Err(wrong_type(1, "a percentage")),
     };
     let l_or_v: f64 = match numerics[2] {
         CSSNumeric::Percentage(val) => {
@@ -159,10 +260,763 @@ pub(crate) fn parse_hsl_hsv_tuple(tup: &str) -> Result<(f64, f64, f64), CSSParse
                 (val as f64) / 100.
             }
         }
-        _ => return Err(CSSParseError::InvalidColorSyntax),
+        _ => return Err(wrong_type(2, "a percentage")),
     };
+    let alpha = numerics.get(3).map(|&num| alpha_from_numeric(num));
     // now return
-    Ok((hue, sat, l_or_v))
+    Ok((hue, sat, l_or_v, alpha))
+}
+
+/// A color parsed from CSS, in whichever Scarlet type best matches the syntax it was written in.
+/// Because every variant is a [`Color`](../color/trait.Color.html), so is this type, so it can be
+/// converted to whatever color type is needed.
+#[derive(Debug, Copy, Clone)]
+pub enum CSSColor {
+    /// An sRGB color, from a hex code, a color name, `rgb()`, `hwb()`, or `color(srgb ...)`.
+    RGB(RGBColor),
+    /// A color from `hsl()`.
+    HSL(HSLColor),
+    /// A color from `lab()`.
+    CIELAB(CIELABColor),
+    /// A color from `lch()`.
+    CIELCH(CIELCHColor),
+    /// A color from `oklab()`.
+    Oklab(OklabColor),
+    /// A color from `oklch()`.
+    Oklch(OklchColor),
+    /// A color from `color(srgb-linear ...)`.
+    LinearRGB(LinearRGBColor),
+    /// A color from `color(display-p3 ...)`.
+    DisplayP3(DisplayP3Color),
+    /// A color from `color(a98-rgb ...)`.
+    AdobeRGB(AdobeRGBColor),
+    /// A color from `color(prophoto-rgb ...)`. Unlike
+    /// [`ROMMRGBColor`](../colors/rommrgbcolor/struct.ROMMRGBColor.html), CSS doesn't apply flare
+    /// correction, so this uses the plain ProPhoto space.
+    ProPhotoRGB(CustomRGBColor<ProPhotoRGB>),
+    /// A color from `color(rec2020 ...)`.
+    Rec2020(Rec2020Color),
+    /// A color from `color(xyz ...)`, `color(xyz-d65 ...)`, or `color(xyz-d50 ...)`.
+    XYZ(XYZColor),
+}
+
+impl Color for CSSColor {
+    /// Wraps an XYZ color, as there's no way to know which CSS syntax it should be written in.
+    fn from_xyz(xyz: XYZColor) -> CSSColor {
+        CSSColor::XYZ(xyz)
+    }
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        match *self {
+            CSSColor::RGB(c) => c.to_xyz(illuminant),
+            CSSColor::HSL(c) => c.to_xyz(illuminant),
+            CSSColor::CIELAB(c) => c.to_xyz(illuminant),
+            CSSColor::CIELCH(c) => c.to_xyz(illuminant),
+            CSSColor::Oklab(c) => c.to_xyz(illuminant),
+            CSSColor::Oklch(c) => c.to_xyz(illuminant),
+            CSSColor::LinearRGB(c) => c.to_xyz(illuminant),
+            CSSColor::DisplayP3(c) => c.to_xyz(illuminant),
+            CSSColor::AdobeRGB(c) => c.to_xyz(illuminant),
+            CSSColor::ProPhotoRGB(c) => c.to_xyz(illuminant),
+            CSSColor::Rec2020(c) => c.to_xyz(illuminant),
+            CSSColor::XYZ(c) => c.to_xyz(illuminant),
+        }
+    }
+}
+
+impl FromStr for Alpha<CSSColor> {
+    type Err = ColorParseError;
+
+    /// Parses any color that CSS Color Level 4 allows, except for `currentcolor` and system colors,
+    /// which depend on context a string doesn't have. Function names and keywords are
+    /// case-insensitive, as in CSS.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::alpha::Alpha;
+    /// # use scarlet::csscolor::CSSColor;
+    /// let color: Alpha<CSSColor> = "oklch(62.8% 0.258 29.23deg / 50%)".parse().unwrap();
+    /// assert_eq!(color.alpha, 0.5);
+    /// if let CSSColor::Oklch(lch) = color.color {
+    ///     assert!((lch.l - 0.628).abs() <= 1e-10);
+    /// } else {
+    ///     panic!("oklch() parses as Oklch");
+    /// }
+    /// // this is just sRGB red
+    /// let rgb: RGBColor = color.color.convert();
+    /// assert_eq!(rgb.to_string(), "#FF0000");
+    /// let p3: Alpha<CSSColor> = "color(display-p3 1 0 0)".parse().unwrap();
+    /// let hwb: Alpha<CSSColor> = "hwb(120 20% 30% / 0.8)".parse().unwrap();
+    /// assert_eq!(hwb.color.convert::<RGBColor>().to_string(), "#33B333");
+    /// ```
+    fn from_str(s: &str) -> Result<Alpha<CSSColor>, ColorParseError> {
+        parse_css_color(s)
+    }
+}
+
+/// A single component of a CSS Level 4 color function, before it is interpreted for a particular
+/// color space.
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum CSSComponent {
+    /// A plain number, like "0.5" or "-2e3".
+    Number(f64),
+    /// A percentage, like "45.5%", stored as the number before the '%'.
+    Percentage(f64),
+    /// An angle with units, like "0.25turn", converted to degrees.
+    Angle(f64),
+    /// The keyword "none", for a missing component. This is treated as 0.
+    None,
+}
+
+impl CSSComponent {
+    /// Interprets the component as a number, where 100% is equal to `percent_ref`. Angles are
+    /// invalid.
+    fn number(self, percent_ref: f64) -> Option<f64> {
+        match self {
+            CSSComponent::Number(val) => Some(val),
+            CSSComponent::Percentage(val) => Some(val / 100. * percent_ref),
+            CSSComponent::None => Some(0.),
+            CSSComponent::Angle(_) => None,
+        }
+    }
+    /// Interprets the component as a hue in degrees, normalized to the range 0-360. Plain numbers
+    /// are degrees, and percentages are invalid.
+    fn hue(self) -> Option<f64> {
+        let degrees = match self {
+            CSSComponent::Number(val) | CSSComponent::Angle(val) => val,
+            CSSComponent::None => 0.,
+            CSSComponent::Percentage(_) => return None,
+        };
+        Some(degrees.rem_euclid(360.))
+    }
+}
+
+/// A component of a CSS Level 4 color function, along with where it is in the input so that errors
+/// can point to it.
+#[derive(Debug, PartialEq, Clone)]
+struct CSSArgument {
+    /// The parsed component.
+    component: CSSComponent,
+    /// The range of bytes the component was parsed from.
+    span: Range<usize>,
+}
+
+impl CSSArgument {
+    /// Parses the given token, a slice of `input`, as a component.
+    fn parse(input: &str, token: &str) -> Result<CSSArgument, ColorParseError> {
+        Ok(CSSArgument {
+            component: parse_css_component(token).map_err(relative_to(input, token))?,
+            span: span_of(input, token),
+        })
+    }
+    /// Interprets the component as a number, as in [`CSSComponent::number`], with an error pointing
+    /// to it if it's an angle.
+    fn number(&self, percent_ref: f64) -> Result<f64, ColorParseError> {
+        self.component.number(percent_ref).ok_or_else(|| {
+            func_error(ParseErrorKind::WrongValueType, self.span.clone())
+                .expecting("a number or percentage")
+        })
+    }
+    /// Interprets the component as a hue, as in [`CSSComponent::hue`], with an error pointing to it
+    /// if it's a percentage.
+    fn hue(&self) -> Result<f64, ColorParseError> {
+        self.component.hue().ok_or_else(|| {
+            func_error(ParseErrorKind::WrongValueType, self.span.clone())
+                .expecting("a number or angle")
+        })
+    }
+}
+
+/// Parses a single CSS Level 4 number, percentage, angle, or `none`. Error spans are relative to the
+/// token.
+fn parse_css_component(token: &str) -> Result<CSSComponent, ColorParseError> {
+    if token == "none" {
+        return Ok(CSSComponent::None);
+    }
+    // find where the number ends and the unit begins: the exponent of a number like "1e3" is part
+    // of the number, but "deg" starts with a letter that isn't
+    let split = token
+        .char_indices()
+        .find(|&(i, c)| {
+            !("0123456789+-.".contains(c)
+                || ((c == 'e' || c == 'E')
+                    && token[i + 1..]
+                        .starts_with(|d: char| d.is_ascii_digit() || d == '-' || d == '+')))
+        })
+        .map(|(i, _c)| i)
+        .unwrap_or_else(|| token.len());
+    let (num, unit) = token.split_at(split);
+    let val: f64 = num.parse().map_err(|_e| {
+        func_error(ParseErrorKind::InvalidNumber, 0..num.len()).expecting("a number")
+    })?;
+    match unit {
+        "" => Ok(CSSComponent::Number(val)),
+        "%" => Ok(CSSComponent::Percentage(val)),
+        "deg" => Ok(CSSComponent::Angle(val)),
+        "rad" => Ok(CSSComponent::Angle(val.to_degrees())),
+        "grad" => Ok(CSSComponent::Angle(val * 0.9)),
+        "turn" => Ok(CSSComponent::Angle(val * 360.)),
+        _ => Err(
+            func_error(ParseErrorKind::InvalidCharacters, split..token.len())
+                .expecting("'%' or an angle unit"),
+        ),
+    }
+}
+
+/// Splits the arguments of a CSS color function into its components and its alpha, if any. Handles
+/// both the legacy syntax with commas, allowed if `legacy` is true, and the modern syntax with
+/// spaces and a slash before the alpha. Error spans are relative to the arguments.
+fn split_css_args(args: &str, legacy: bool) -> Result<(Vec<&str>, Option<&str>), ColorParseError> {
+    if let Some(comma) = args.find(',') {
+        if !legacy {
+            return Err(
+                func_error(ParseErrorKind::InvalidSyntax, comma..comma + 1).expecting("a space")
+            );
+        }
+        let mut parts: Vec<&str> = args.split(',').map(|part| part.trim()).collect();
+        // none isn't allowed in the legacy syntax, and neither are spaces between components
+        for part in parts.iter() {
+            if part.is_empty() || *part == "none" {
+                return Err(
+                    func_error(ParseErrorKind::InvalidSyntax, span_of(args, part))
+                        .expecting("a number"),
+                );
+            }
+            if let Some(space) = part.find(char::is_whitespace) {
+                let start = span_of(args, part).start + space;
+                return Err(
+                    func_error(ParseErrorKind::InvalidSyntax, start..start + 1).expecting("','")
+                );
+            }
+        }
+        let alpha = if parts.len() == 4 { parts.pop() } else { None };
+        Ok((parts, alpha))
+    } else {
+        let mut halves = args.split('/');
+        let parts: Vec<&str> = halves.next().unwrap_or("").split_whitespace().collect();
+        let alpha = match halves.next() {
+            Some(alpha) => match alpha.trim() {
+                "" => {
+                    let end = span_of(args, alpha).end;
+                    return Err(
+                        func_error(ParseErrorKind::InvalidSyntax, end..end).expecting("an alpha")
+                    );
+                }
+                trimmed => Some(trimmed),
+            },
+            None => None,
+        };
+        if let Some(extra) = halves.next() {
+            let slash = span_of(args, extra).start - 1;
+            return Err(
+                func_error(ParseErrorKind::InvalidSyntax, slash..slash + 1).expecting("')'")
+            );
+        }
+        Ok((parts, alpha))
+    }
+}
+
+/// Gets the fully saturated sRGB color with a given hue in degrees, as the CSS spec computes it.
+fn hue_to_rgb(hue: f64) -> (f64, f64, f64) {
+    let channel = |n: f64| {
+        let k = (n + hue / 30.) % 12.;
+        0.5 - 0.5 * (k - 3.).min(9. - k).clamp(-1., 1.)
+    };
+    (channel(0.), channel(8.), channel(4.))
+}
+
+/// Parses a CSS Color Level 4 color: a hex code, a named color, `transparent`, or a color function
+/// (`rgb()`, `rgba()`, `hsl()`, `hsla()`, `hwb()`, `lab()`, `lch()`, `oklab()`, `oklch()`, or
+/// `color()`). Returns the color in the closest matching Scarlet type, along with its alpha.
+pub(crate) fn parse_css_color(input: &str) -> Result<Alpha<CSSColor>, ColorParseError> {
+    let trimmed = input.trim();
+    // errors are found in the lowercase copy, which has the same byte offsets as the original
+    let to_input = relative_to(input, trimmed);
+    let lower = trimmed.to_ascii_lowercase();
+    // first try the keyword and hex forms, which Alpha<RGBColor> already handles
+    if !lower.contains('(') && !lower.contains(')') {
+        return match lower.parse::<Alpha<RGBColor>>() {
+            Ok(rgba) => Ok(Alpha::new(CSSColor::RGB(rgba.color), rgba.alpha)),
+            Err(e) => Err(to_input(e)),
+        };
+    }
+    parse_css_function(&lower).map_err(to_input)
+}
+
+/// Parses a lowercase CSS Level 4 color function, as described in [`parse_css_color`].
+fn parse_css_function(input: &str) -> Result<Alpha<CSSColor>, ColorParseError> {
+    let names = [
+        "rgb", "rgba", "hsl", "hsla", "hwb", "lab", "lch", "oklab", "oklch", "color",
+    ];
+    let tup = strip_function_name(input, &names, "a color function")?;
+    let name = &input[..input.len() - tup.len()];
+    let args = match tup[1..].strip_suffix(')') {
+        Some(args) => args,
+        None => {
+            return Err(
+                func_error(ParseErrorKind::InvalidSyntax, input.len()..input.len())
+                    .expecting("')'"),
+            )
+        }
+    };
+    let legacy = ["rgb", "rgba", "hsl", "hsla"].contains(&name);
+    let (mut parts, alpha) = split_css_args(args, legacy).map_err(relative_to(input, args))?;
+    let alpha = match alpha {
+        Some(alpha) => {
+            if let Some(space) = alpha.find(char::is_whitespace) {
+                let start = span_of(input, alpha).start + space;
+                return Err(
+                    func_error(ParseErrorKind::InvalidSyntax, start..start + 1).expecting("')'")
+                );
+            }
+            CSSArgument::parse(input, alpha)?.number(1.)?.clamp(0., 1.)
+        }
+        None => 1.,
+    };
+    // color() names its color space first
+    let space = if name == "color" {
+        if parts.is_empty() {
+            let end = span_of(input, args).end;
+            return Err(
+                func_error(ParseErrorKind::InvalidSyntax, end..end).expecting("a color space")
+            );
+        }
+        Some(parts.remove(0))
+    } else {
+        None
+    };
+    if parts.len() > 3 {
+        return Err(
+            func_error(ParseErrorKind::InvalidSyntax, span_of(input, parts[3]))
+                .expecting("')' or '/'"),
+        );
+    }
+    if parts.len() < 3 {
+        let end = span_of(input, args).end;
+        return Err(func_error(ParseErrorKind::InvalidSyntax, end..end).expecting("a component"));
+    }
+    let mut comps = Vec::with_capacity(3);
+    for part in parts.iter() {
+        comps.push(CSSArgument::parse(input, part)?);
+    }
+    let color = match (name, space) {
+        ("rgb", None) | ("rgba", None) => {
+            let channel = |c: &CSSArgument| Ok(c.number(255.)?.clamp(0., 255.) / 255.);
+            CSSColor::RGB(RGBColor {
+                r: channel(&comps[0])?,
+                g: channel(&comps[1])?,
+                b: channel(&comps[2])?,
+            })
+        }
+        ("hsl", None) | ("hsla", None) => CSSColor::HSL(HSLColor {
+            h: comps[0].hue()?,
+            s: comps[1].number(100.)?.clamp(0., 100.) / 100.,
+            l: comps[2].number(100.)?.clamp(0., 100.) / 100.,
+        }),
+        ("hwb", None) => {
+            let hue = comps[0].hue()?;
+            let white = comps[1].number(100.)?.clamp(0., 100.) / 100.;
+            let black = comps[2].number(100.)?.clamp(0., 100.) / 100.;
+            let (r, g, b) = if white + black >= 1. {
+                let gray = white / (white + black);
+                (gray, gray, gray)
+            } else {
+                let (r, g, b) = hue_to_rgb(hue);
+                let scale = 1. - white - black;
+                (r * scale + white, g * scale + white, b * scale + white)
+            };
+            CSSColor::RGB(RGBColor { r, g, b })
+        }
+        ("lab", None) => CSSColor::CIELAB(CIELABColor {
+            l: comps[0].number(100.)?.clamp(0., 100.),
+            a: comps[1].number(125.)?,
+            b: comps[2].number(125.)?,
+        }),
+        ("lch", None) => CSSColor::CIELCH(CIELCHColor {
+            l: comps[0].number(100.)?.clamp(0., 100.),
+            c: comps[1].number(150.)?.max(0.),
+            h: comps[2].hue()?,
+        }),
+        ("oklab", None) => CSSColor::Oklab(OklabColor {
+            l: comps[0].number(1.)?.clamp(0., 1.),
+            a: comps[1].number(0.4)?,
+            b: comps[2].number(0.4)?,
+        }),
+        ("oklch", None) => CSSColor::Oklch(OklchColor {
+            l: comps[0].number(1.)?.clamp(0., 1.),
+            c: comps[1].number(0.4)?.max(0.),
+            h: comps[2].hue()?,
+        }),
+        ("color", Some(space)) => {
+            let (x, y, z) = (
+                comps[0].number(1.)?,
+                comps[1].number(1.)?,
+                comps[2].number(1.)?,
+            );
+            match space {
+                "srgb" => CSSColor::RGB(RGBColor { r: x, g: y, b: z }),
+                "srgb-linear" => CSSColor::LinearRGB(LinearRGBColor { r: x, g: y, b: z }),
+                "display-p3" => CSSColor::DisplayP3(DisplayP3Color { r: x, g: y, b: z }),
+                "a98-rgb" => CSSColor::AdobeRGB(AdobeRGBColor { r: x, g: y, b: z }),
+                "prophoto-rgb" => CSSColor::ProPhotoRGB(CustomRGBColor::new(x, y, z)),
+                "rec2020" => CSSColor::Rec2020(Rec2020Color { r: x, g: y, b: z }),
+                "xyz" | "xyz-d65" => CSSColor::XYZ(XYZColor {
+                    x,
+                    y,
+                    z,
+                    illuminant: Illuminant::D65,
+                }),
+                "xyz-d50" => CSSColor::XYZ(XYZColor {
+                    x,
+                    y,
+                    z,
+                    illuminant: Illuminant::D50,
+                }),
+                _ => {
+                    return Err(
+                        func_error(ParseErrorKind::UnknownName, span_of(input, space))
+                            .expecting("a predefined color space"),
+                    )
+                }
+            }
+        }
+        // strip_function_name only allows the names handled above
+        _ => unreachable!(),
+    };
+    Ok(Alpha::new(color, alpha))
+}
+
+/// The number of decimal places [`ToCSSString::to_css_string`] writes.
+///
+/// [`ToCSSString::to_css_string`]: trait.ToCSSString.html#method.to_css_string
+pub const DEFAULT_CSS_PRECISION: usize = 4;
+
+/// Writes a number the way CSS serializes it: rounded to the given number of decimal places, without
+/// trailing zeros or a trailing decimal point, and never as "-0".
+fn css_number(num: f64, precision: usize) -> String {
+    let mut s = format!("{:.*}", precision, num);
+    if s.contains('.') {
+        let trimmed_len = s.trim_end_matches('0').trim_end_matches('.').len();
+        s.truncate(trimmed_len);
+    }
+    if s == "-0" {
+        s = String::from("0");
+    }
+    s
+}
+
+/// Writes a CSS Level 4 function with space-separated components, followed by the alpha after a
+/// slash if there is one.
+fn css_function(name: &str, components: &[String], alpha: Option<f64>, precision: usize) -> String {
+    match alpha {
+        Some(a) => format!(
+            "{}({} / {})",
+            name,
+            components.join(" "),
+            css_number(a.clamp(0., 1.), precision)
+        ),
+        None => format!("{}({})", name, components.join(" ")),
+    }
+}
+
+/// Writes a `color()` function in the given predefined color space.
+fn css_color_function(
+    space: &str,
+    components: [f64; 3],
+    alpha: Option<f64>,
+    precision: usize,
+) -> String {
+    let args = vec![
+        String::from(space),
+        css_number(components[0], precision),
+        css_number(components[1], precision),
+        css_number(components[2], precision),
+    ];
+    css_function("color", &args, alpha, precision)
+}
+
+/// A trait for colors that can be written as CSS, in the form CSS Color Level 4 gives as canonical
+/// for their color space. Colors that CSS has a function for use it, like `hsl()` or `lab()`; RGB
+/// spaces CSS predefines use `color()`; and everything else is converted to `color(xyz-d65 ...)`
+/// or `color(xyz-d50 ...)`. Everything written this way can be read back by parsing an
+/// [`Alpha<CSSColor>`](enum.CSSColor.html).
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::{AdobeRGBColor, CIELABColor, HSLColor};
+/// # use scarlet::csscolor::ToCSSString;
+/// let hsl = HSLColor{h: 120., s: 0.5, l: 0.25};
+/// assert_eq!(hsl.to_css_string(), "hsl(120 50% 25%)");
+/// let lab = CIELABColor{l: 53.24079, a: 80.09246, b: 67.2032};
+/// assert_eq!(lab.to_css_string_with_precision(1), "lab(53.2 80.1 67.2)");
+/// let adobe = AdobeRGBColor{r: 0.5, g: 0.25, b: 1.};
+/// assert_eq!(adobe.to_css_string_with_alpha(Some(0.8), 2), "color(a98-rgb 0.5 0.25 1 / 0.8)");
+/// ```
+pub trait ToCSSString {
+    /// Writes this color as CSS, rounding every number to `precision` decimal places. If `alpha`
+    /// is given, it is written after a slash.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String;
+    /// Writes this color as CSS without an alpha, rounding every number to `precision` decimal
+    /// places.
+    fn to_css_string_with_precision(&self, precision: usize) -> String {
+        self.to_css_string_with_alpha(None, precision)
+    }
+    /// Writes this color as CSS without an alpha, using
+    /// [`DEFAULT_CSS_PRECISION`](constant.DEFAULT_CSS_PRECISION.html) decimal places.
+    fn to_css_string(&self) -> String {
+        self.to_css_string_with_alpha(None, DEFAULT_CSS_PRECISION)
+    }
+}
+
+impl ToCSSString for RGBColor {
+    /// Writes the color with `rgb()` or `rgba()`, in the comma-separated form CSS uses to serialize
+    /// sRGB colors. Components range from 0 to 255, and are not clamped.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        let rgb = format!(
+            "{}, {}, {}",
+            css_number(self.r * 255., precision),
+            css_number(self.g * 255., precision),
+            css_number(self.b * 255., precision)
+        );
+        match alpha {
+            Some(a) => format!("rgba({}, {})", rgb, css_number(a.clamp(0., 1.), precision)),
+            None => format!("rgb({})", rgb),
+        }
+    }
+}
+
+impl ToCSSString for HSLColor {
+    /// Writes the color with `hsl()`, with saturation and lightness as percentages.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        let args = [
+            css_number(self.h, precision),
+            css_number(self.s * 100., precision) + "%",
+            css_number(self.l * 100., precision) + "%",
+        ];
+        css_function("hsl", &args, alpha, precision)
+    }
+}
+
+impl ToCSSString for HSVColor {
+    /// CSS has no HSV function, but HWB is the same model under a different name: whiteness is
+    /// (1 - S)V and blackness is 1 - V. This writes the equivalent `hwb()` color.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        let args = [
+            css_number(self.h, precision),
+            css_number((1. - self.s) * self.v * 100., precision) + "%",
+            css_number((1. - self.v) * 100., precision) + "%",
+        ];
+        css_function("hwb", &args, alpha, precision)
+    }
+}
+
+impl ToCSSString for CIELABColor {
+    /// Writes the color with `lab()`. CSS always uses a D50 white point for CIELAB, just like
+    /// Scarlet.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        let args = [
+            css_number(self.l, precision),
+            css_number(self.a, precision),
+            css_number(self.b, precision),
+        ];
+        css_function("lab", &args, alpha, precision)
+    }
+}
+
+impl ToCSSString for CIELCHColor {
+    /// Writes the color with `lch()`.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        let args = [
+            css_number(self.l, precision),
+            css_number(self.c, precision),
+            css_number(self.h, precision),
+        ];
+        css_function("lch", &args, alpha, precision)
+    }
+}
+
+impl ToCSSString for OklabColor {
+    /// Writes the color with `oklab()`, with lightness as a number from 0 to 1.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        let args = [
+            css_number(self.l, precision),
+            css_number(self.a, precision),
+            css_number(self.b, precision),
+        ];
+        css_function("oklab", &args, alpha, precision)
+    }
+}
+
+impl ToCSSString for OklchColor {
+    /// Writes the color with `oklch()`, with lightness as a number from 0 to 1.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        let args = [
+            css_number(self.l, precision),
+            css_number(self.c, precision),
+            css_number(self.h, precision),
+        ];
+        css_function("oklch", &args, alpha, precision)
+    }
+}
+
+impl ToCSSString for LinearRGBColor {
+    /// Writes the color as `color(srgb-linear ...)`.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        css_color_function("srgb-linear", [self.r, self.g, self.b], alpha, precision)
+    }
+}
+
+impl ToCSSString for DisplayP3Color {
+    /// Writes the color as `color(display-p3 ...)`.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        css_color_function("display-p3", [self.r, self.g, self.b], alpha, precision)
+    }
+}
+
+impl ToCSSString for AdobeRGBColor {
+    /// Writes the color as `color(a98-rgb ...)`.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        css_color_function("a98-rgb", [self.r, self.g, self.b], alpha, precision)
+    }
+}
+
+impl ToCSSString for Rec2020Color {
+    /// Writes the color as `color(rec2020 ...)`.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        css_color_function("rec2020", [self.r, self.g, self.b], alpha, precision)
+    }
+}
+
+impl<S: RGBSpace> ToCSSString for CustomRGBColor<S> {
+    /// Writes the color with `color()` if CSS has a name for the space (see
+    /// [`RGBSpace::css_name`](../rgbcolorspace/trait.RGBSpace.html#method.css_name)), and as XYZ
+    /// relative to the space's white point otherwise.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        match S::css_name() {
+            Some(name) => css_color_function(name, [self.r, self.g, self.b], alpha, precision),
+            None => self
+                .to_xyz(S::color_space().white)
+                .to_css_string_with_alpha(alpha, precision),
+        }
+    }
+}
+
+impl ToCSSString for ROMMRGBColor {
+    /// Writes the color as `color(prophoto-rgb ...)`. CSS doesn't use ROMM RGB's flare correction,
+    /// so the components may differ slightly from this color's.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        self.convert::<CustomRGBColor<ProPhotoRGB>>()
+            .to_css_string_with_alpha(alpha, precision)
+    }
+}
+
+impl ToCSSString for XYZColor {
+    /// Writes the color as `color(xyz-d50 ...)` if its illuminant is D50, and as `color(xyz-d65
+    /// ...)` otherwise, chromatically adapting it to D65 if needed: CSS has no other white points.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        let (space, xyz) = match self.illuminant {
+            Illuminant::D50 => ("xyz-d50", *self),
+            _ => ("xyz-d65", self.color_adapt(Illuminant::D65)),
+        };
+        css_color_function(space, [xyz.x, xyz.y, xyz.z], alpha, precision)
+    }
+}
+
+// the remaining color spaces have no CSS syntax at all, so they're written as XYZ in D65
+
+impl ToCSSString for CIELUVColor {
+    /// Writes the color as `color(xyz-d65 ...)`, as CSS has no syntax for CIELUV.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        self.to_xyz(Illuminant::D65)
+            .to_css_string_with_alpha(alpha, precision)
+    }
+}
+
+impl ToCSSString for CIELCHuvColor {
+    /// Writes the color as `color(xyz-d65 ...)`, as CSS has no syntax for CIELCHuv.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        self.to_xyz(Illuminant::D65)
+            .to_css_string_with_alpha(alpha, precision)
+    }
+}
+
+impl ToCSSString for CAM16Color {
+    /// Writes the color as `color(xyz-d65 ...)`, as CSS has no syntax for CAM16.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        self.to_xyz(Illuminant::D65)
+            .to_css_string_with_alpha(alpha, precision)
+    }
+}
+
+impl ToCSSString for CAM16UCSColor {
+    /// Writes the color as `color(xyz-d65 ...)`, as CSS has no syntax for CAM16-UCS.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        self.to_xyz(Illuminant::D65)
+            .to_css_string_with_alpha(alpha, precision)
+    }
+}
+
+impl ToCSSString for CAM16LCDColor {
+    /// Writes the color as `color(xyz-d65 ...)`, as CSS has no syntax for CAM16-LCD.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        self.to_xyz(Illuminant::D65)
+            .to_css_string_with_alpha(alpha, precision)
+    }
+}
+
+impl ToCSSString for CAM16SCDColor {
+    /// Writes the color as `color(xyz-d65 ...)`, as CSS has no syntax for CAM16-SCD.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        self.to_xyz(Illuminant::D65)
+            .to_css_string_with_alpha(alpha, precision)
+    }
+}
+
+impl ToCSSString for CSSColor {
+    /// Writes the color in the same syntax it was parsed from. Colors parsed from `hwb()`, color
+    /// names, and hex codes are written with `rgb()`, as they are stored as sRGB.
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        match *self {
+            CSSColor::RGB(c) => c.to_css_string_with_alpha(alpha, precision),
+            CSSColor::HSL(c) => c.to_css_string_with_alpha(alpha, precision),
+            CSSColor::CIELAB(c) => c.to_css_string_with_alpha(alpha, precision),
+            CSSColor::CIELCH(c) => c.to_css_string_with_alpha(alpha, precision),
+            CSSColor::Oklab(c) => c.to_css_string_with_alpha(alpha, precision),
+            CSSColor::Oklch(c) => c.to_css_string_with_alpha(alpha, precision),
+            CSSColor::LinearRGB(c) => c.to_css_string_with_alpha(alpha, precision),
+            CSSColor::DisplayP3(c) => c.to_css_string_with_alpha(alpha, precision),
+            CSSColor::AdobeRGB(c) => c.to_css_string_with_alpha(alpha, precision),
+            CSSColor::ProPhotoRGB(c) => c.to_css_string_with_alpha(alpha, precision),
+            CSSColor::Rec2020(c) => c.to_css_string_with_alpha(alpha, precision),
+            CSSColor::XYZ(c) => c.to_css_string_with_alpha(alpha, precision),
+        }
+    }
+}
+
+impl<C: Color + ToCSSString> ToCSSString for Alpha<C> {
+    /// Writes the color with its own alpha, unless a different one is given. As in CSS, an alpha
+    /// of 1 is left out.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::alpha::Alpha;
+    /// # use scarlet::colors::OklchColor;
+    /// # use scarlet::csscolor::ToCSSString;
+    /// let color = Alpha::new(OklchColor{l: 0.628, c: 0.2577, h: 29.23}, 0.5);
+    /// assert_eq!(color.to_css_string(), "oklch(0.628 0.2577 29.23 / 0.5)");
+    /// assert_eq!(Alpha::opaque(color.color).to_css_string(), "oklch(0.628 0.2577 29.23)");
+    /// ```
+    fn to_css_string_with_alpha(&self, alpha: Option<f64>, precision: usize) -> String {
+        let own_alpha = if self.alpha >= 1. {
+            None
+        } else {
+            Some(self.alpha)
+        };
+        self.color
+            .to_css_string_with_alpha(alpha.or(own_alpha), precision)
+    }
 }
 
 #[cfg(test)]
@@ -170,6 +1024,15 @@ mod tests {
     #[allow(unused_imports)]
     use super::*;
 
+    // makes the error a color function parser should give
+    fn err<T>(
+        kind: ParseErrorKind,
+        span: Range<usize>,
+        expected: &'static str,
+    ) -> Result<T, ColorParseError> {
+        Err(func_error(kind, span).expecting(expected))
+    }
+
     #[test]
     fn test_rgb_num_parsing() {
         // test integers
@@ -183,11 +1046,11 @@ mod tests {
         assert_eq!(255u8, parse_rgb_num("115%").unwrap());
         // test errors
         assert_eq!(
-            Err(CSSParseError::InvalidNumericCharacters),
+            err(ParseErrorKind::InvalidCharacters, 0..1, "a number"),
             parse_rgb_num("abc")
         );
         assert_eq!(
-            Err(CSSParseError::InvalidNumericSyntax),
+            err(ParseErrorKind::InvalidNumber, 0..5, "a number"),
             parse_rgb_num("123%%")
         );
     }
@@ -202,17 +1065,30 @@ mod tests {
         assert_eq!(rgb, (0, 0, 255));
         // test error on bad syntax
         assert_eq!(
-            Err(CSSParseError::InvalidColorSyntax),
+            err(ParseErrorKind::UnknownName, 0..3, "'rgb'"),
             parse_rgb_str("rgB(123, 33, 2)")
         );
         assert_eq!(
-            Err(CSSParseError::InvalidColorSyntax),
+            err(ParseErrorKind::InvalidSyntax, 16..20, "')'"),
             parse_rgb_str("rgb(123, 123, 41, 22)")
         );
         assert_eq!(
-            Err(CSSParseError::InvalidColorSyntax),
+            err(ParseErrorKind::UnknownName, 0..3, "'rgb'"),
             parse_rgb_str("rgB(())")
         );
+        // errors point to where the problem is
+        assert_eq!(
+            err(ParseErrorKind::InvalidSyntax, 11..11, "')'"),
+            parse_rgb_str("rgb(1, 2, 3")
+        );
+        assert_eq!(
+            err(ParseErrorKind::InvalidCharacters, 11..12, "a number"),
+            parse_rgb_str("rgb(1, 2, 3x)")
+        );
+        assert_eq!(
+            err(ParseErrorKind::InvalidSyntax, 8..8, "','"),
+            parse_rgb_str("rgb(1, 2)")
+        );
     }
 
     #[test]
@@ -239,7 +1115,376 @@ mod tests {
         // test error
         assert_eq!(
             parse_hsl_hsv_tuple("(14%, 140%, 12%)"),
-            Err(CSSParseError::InvalidColorSyntax)
+            err(ParseErrorKind::WrongValueType, 1..4, "a hue")
+        );
+    }
+
+    #[test]
+    fn test_rgba_str_parsing() {
+        assert_eq!(
+            parse_rgba_str("rgba(125, 20%, 0.5, 0.25)").unwrap(),
+            (125, 51, 127, 0.25)
+        );
+        assert_eq!(
+            parse_rgba_str("rgba(0, 0, 0, 40%)").unwrap(),
+            (0, 0, 0, 0.4)
+        );
+        // alpha is optional and clamped
+        assert_eq!(parse_rgba_str("rgb(1, 2, 3)").unwrap(), (1, 2, 3, 1.));
+        assert_eq!(parse_rgba_str("rgb(1, 2, 3, 2)").unwrap(), (1, 2, 3, 1.));
+        assert_eq!(
+            err(ParseErrorKind::InvalidSyntax, 17..22, "')'"),
+            parse_rgba_str("rgba(1, 2, 3, 0.5, 0.5)")
+        );
+        assert_eq!(
+            err(ParseErrorKind::InvalidSyntax, 17..17, "')'"),
+            parse_rgba_str("rgba(1, 2, 3, 0.5")
+        );
+    }
+
+    #[test]
+    fn test_hsla_str_parsing() {
+        let hsla = parse_hsla_hsva_tuple("(123, 40%, 40%, 0.5)").unwrap();
+        assert_eq!(hsla.0.round() as u8, 123u8);
+        assert_eq!(hsla.3, Some(0.5));
+        let hsl = parse_hsla_hsva_tuple("(123, 40%, 40%)").unwrap();
+        assert_eq!(hsl.3, None);
+        // the alpha-less function doesn't allow an alpha
+        assert_eq!(
+            parse_hsl_hsv_tuple("(123, 40%, 40%, 0.5)"),
+            err(ParseErrorKind::InvalidSyntax, 14..19, "')'")
+        );
+    }
+
+    #[test]
+    fn test_css_component_parsing() {
+        assert_eq!(parse_css_component("none"), Ok(CSSComponent::None));
+        assert_eq!(parse_css_component("-.5"), Ok(CSSComponent::Number(-0.5)));
+        assert_eq!(parse_css_component("1e2"), Ok(CSSComponent::Number(100.)));
+        assert_eq!(
+            parse_css_component("45.5%"),
+            Ok(CSSComponent::Percentage(45.5))
+        );
+        assert_eq!(
+            parse_css_component("0.5turn"),
+            Ok(CSSComponent::Angle(180.))
+        );
+        assert_eq!(
+            parse_css_component("200grad"),
+            Ok(CSSComponent::Angle(180.))
+        );
+        assert_eq!(parse_css_component("90deg"), Ok(CSSComponent::Angle(90.)));
+        match parse_css_component("3.14159265358979rad") {
+            Ok(CSSComponent::Angle(val)) => assert!((val - 180.).abs() <= 1e-10),
+            _ => panic!("should parse as an angle"),
+        }
+        assert_eq!(
+            parse_css_component("1.2.3"),
+            err(ParseErrorKind::InvalidNumber, 0..5, "a number")
+        );
+        assert_eq!(
+            parse_css_component("12px"),
+            err(
+                ParseErrorKind::InvalidCharacters,
+                2..4,
+                "'%' or an angle unit"
+            )
+        );
+    }
+
+    #[test]
+    fn test_css4_rgb_hsl_hwb() {
+        let rgb = parse_css_color("rgb(255 127.5 0 / 25%)").unwrap();
+        assert_eq!(rgb.alpha, 0.25);
+        match rgb.color {
+            CSSColor::RGB(c) => assert_eq!(
+                c,
+                RGBColor {
+                    r: 1.,
+                    g: 0.5,
+                    b: 0.
+                }
+            ),
+            _ => panic!("rgb() should parse as RGB"),
+        }
+        // legacy syntax works too, and is the same color
+        let legacy = parse_css_color("rgba(255, 127.5, 0, 0.25)").unwrap();
+        match legacy.color {
+            CSSColor::RGB(c) => assert_eq!(
+                c,
+                RGBColor {
+                    r: 1.,
+                    g: 0.5,
+                    b: 0.
+                }
+            ),
+            _ => panic!("rgba() should parse as RGB"),
+        }
+        let hsl = parse_css_color("HSL(0.25turn 50.5% none)").unwrap();
+        match hsl.color {
+            CSSColor::HSL(c) => {
+                assert!((c.h - 90.).abs() <= 1e-10);
+                assert!((c.s - 0.505).abs() <= 1e-10);
+                assert_eq!(c.l, 0.);
+            }
+            _ => panic!("hsl() should parse as HSL"),
+        }
+        let hwb = parse_css_color("hwb(0 0% 0%)").unwrap();
+        assert_eq!(hwb.color.convert::<RGBColor>().to_string(), "#FF0000");
+        // whiteness and blackness that add to more than 100% give a gray
+        match parse_css_color("hwb(60 60% 60%)").unwrap().color {
+            CSSColor::RGB(c) => assert_eq!(
+                c,
+                RGBColor {
+                    r: 0.5,
+                    g: 0.5,
+                    b: 0.5
+                }
+            ),
+            _ => panic!("hwb() should parse as RGB"),
+        }
+        // the modern syntax can't mix commas and spaces, or use commas with new functions
+        assert!(parse_css_color("rgb(255, 0 0)").is_err());
+        assert!(parse_css_color("hwb(0, 0%, 0%)").is_err());
+        assert!(parse_css_color("rgb(255 0 0 / 0.5 / 0.5)").is_err());
+        assert!(parse_css_color("rgb(none, 0, 0)").is_err());
+        assert!(parse_css_color("rgb(1 2)").is_err());
+    }
+
+    #[test]
+    fn test_css4_lab_lch() {
+        match parse_css_color("lab(50% 40 -100%)").unwrap().color {
+            CSSColor::CIELAB(c) => {
+                assert_eq!(c.l, 50.);
+                assert_eq!(c.a, 40.);
+                assert_eq!(c.b, -125.);
+            }
+            _ => panic!("lab() should parse as CIELAB"),
+        }
+        match parse_css_color("lch(52.2345% 72.2 56.2deg)").unwrap().color {
+            CSSColor::CIELCH(c) => {
+                assert!((c.l - 52.2345).abs() <= 1e-10);
+                assert!((c.c - 72.2).abs() <= 1e-10);
+                assert!((c.h - 56.2).abs() <= 1e-10);
+            }
+            _ => panic!("lch() should parse as CIELCH"),
+        }
+        match parse_css_color("oklab(40.1% 0.1143 0.045)").unwrap().color {
+            CSSColor::Oklab(c) => assert!((c.l - 0.401).abs() <= 1e-10),
+            _ => panic!("oklab() should parse as Oklab"),
+        }
+        match parse_css_color("oklch(0.5 50% -90)").unwrap().color {
+            CSSColor::Oklch(c) => {
+                assert!((c.c - 0.2).abs() <= 1e-10);
+                assert!((c.h - 270.).abs() <= 1e-10);
+            }
+            _ => panic!("oklch() should parse as Oklch"),
+        }
+        // hues can't be percentages
+        assert!(parse_css_color("lch(50% 20 10%)").is_err());
+    }
+
+    #[test]
+    fn test_css4_color_function() {
+        let spaces = [
+            "srgb",
+            "srgb-linear",
+            "display-p3",
+            "a98-rgb",
+            "prophoto-rgb",
+            "rec2020",
+            "xyz",
+            "xyz-d50",
+            "xyz-d65",
+        ];
+        for space in spaces.iter() {
+            let color = parse_css_color(&format!("color({} 0.5 0.25 none / 0.5)", space)).unwrap();
+            assert_eq!(color.alpha, 0.5);
+        }
+        match parse_css_color("color(display-p3 100% 0 0)").unwrap().color {
+            CSSColor::DisplayP3(c) => assert_eq!(c.r, 1.),
+            _ => panic!("display-p3 should parse as DisplayP3"),
+        }
+        match parse_css_color("color(xyz-d50 0.2 0.3 0.4)").unwrap().color {
+            CSSColor::XYZ(c) => assert_eq!(c.illuminant, Illuminant::D50),
+            _ => panic!("xyz-d50 should parse as XYZ"),
+        }
+        // srgb red and rgb() red are the same color
+        let red = parse_css_color("color(srgb 1 0 0)").unwrap().color;
+        assert!(
+            red.distance(&RGBColor {
+                r: 1.,
+                g: 0.,
+                b: 0.
+            }) <= 1e-10
+        );
+        assert!(parse_css_color("color(cmyk 1 0 0)").is_err());
+        assert!(parse_css_color("color(srgb 1 0)").is_err());
+        assert!(parse_css_color("color()").is_err());
+    }
+
+    #[test]
+    fn test_css4_keywords() {
+        let red = parse_css_color("Red").unwrap();
+        assert_eq!(red.alpha, 1.);
+        assert_eq!(red.color.convert::<RGBColor>().to_string(), "#FF0000");
+        let clear = parse_css_color("transparent").unwrap();
+        assert_eq!(clear.alpha, 0.);
+        let hex = parse_css_color("#ff000080").unwrap();
+        assert!((hex.alpha - 128. / 255.).abs() <= 1e-15);
+        assert!(parse_css_color("not-a-color").is_err());
+    }
+
+    #[test]
+    fn test_css_number_formatting() {
+        assert_eq!(css_number(1.0, 4), "1");
+        assert_eq!(css_number(0.25, 4), "0.25");
+        assert_eq!(css_number(1.23456, 2), "1.23");
+        assert_eq!(css_number(-0.00001, 4), "0");
+        assert_eq!(css_number(120.0, 0), "120");
+        assert_eq!(css_number(-12.5, 1), "-12.5");
+    }
+
+    #[test]
+    fn test_css_serialization_forms() {
+        let rgb = RGBColor {
+            r: 1.,
+            g: 0.5,
+            b: 0.,
+        };
+        assert_eq!(rgb.to_css_string(), "rgb(255, 127.5, 0)");
+        assert_eq!(rgb.to_css_string_with_precision(0), "rgb(255, 128, 0)");
+        assert_eq!(
+            rgb.to_css_string_with_alpha(Some(0.25), 2),
+            "rgba(255, 127.5, 0, 0.25)"
+        );
+        let hsv = HSVColor {
+            h: 60.,
+            s: 0.5,
+            v: 0.8,
+        };
+        assert_eq!(hsv.to_css_string(), "hwb(60 40% 20%)");
+        let lch = CIELCHColor {
+            l: 50.,
+            c: 30.,
+            h: 270.,
+        };
+        assert_eq!(lch.to_css_string(), "lch(50 30 270)");
+        let p3 = DisplayP3Color {
+            r: 1.,
+            g: 0.,
+            b: 0.,
+        };
+        assert_eq!(p3.to_css_string(), "color(display-p3 1 0 0)");
+        let xyz = XYZColor {
+            x: 0.5,
+            y: 0.25,
+            z: 0.125,
+            illuminant: Illuminant::D50,
+        };
+        assert_eq!(xyz.to_css_string(), "color(xyz-d50 0.5 0.25 0.125)");
+        assert!(xyz
+            .color_adapt(Illuminant::D55)
+            .to_css_string()
+            .starts_with("color(xyz-d65 "));
+        let prophoto = CustomRGBColor::<ProPhotoRGB>::new(0.5, 0.5, 0.5);
+        assert_eq!(prophoto.to_css_string(), "color(prophoto-rgb 0.5 0.5 0.5)");
+        // an explicit alpha replaces the color's own
+        let translucent = Alpha::new(p3, 0.5);
+        assert_eq!(
+            translucent.to_css_string_with_alpha(Some(0.75), 4),
+            "color(display-p3 1 0 0 / 0.75)"
+        );
+    }
+
+    // parses the CSS for a color and checks that it's the same color
+    fn assert_round_trip<T: Color + ToCSSString + Copy>(color: T, alpha: f64) {
+        let css = Alpha::new(color, alpha).to_css_string_with_alpha(None, 10);
+        let parsed = parse_css_color(&css).unwrap();
+        assert!(
+            parsed.color.distance(&color) <= 1e-6,
+            "{} didn't round trip: {}",
+            css,
+            parsed.color.distance(&color)
+        );
+        assert!((parsed.alpha - alpha).abs() <= 1e-10);
+    }
+
+    #[test]
+    fn test_css_round_trip() {
+        let rgb = RGBColor::from_hex_code("#3a7bd5").unwrap();
+        assert_round_trip(rgb, 1.);
+        assert_round_trip(rgb, 0.3);
+        assert_round_trip::<HSLColor>(rgb.convert(), 0.5);
+        assert_round_trip::<HSVColor>(rgb.convert(), 0.5);
+        assert_round_trip::<CIELABColor>(rgb.convert(), 0.5);
+        assert_round_trip::<CIELCHColor>(rgb.convert(), 0.5);
+        assert_round_trip::<OklabColor>(rgb.convert(), 0.5);
+        assert_round_trip::<OklchColor>(rgb.convert(), 0.5);
+        assert_round_trip::<LinearRGBColor>(rgb.convert(), 0.5);
+        assert_round_trip::<DisplayP3Color>(rgb.convert(), 0.5);
+        assert_round_trip::<AdobeRGBColor>(rgb.convert(), 0.5);
+        assert_round_trip::<Rec2020Color>(rgb.convert(), 0.5);
+        assert_round_trip::<CustomRGBColor<ProPhotoRGB>>(rgb.convert(), 0.5);
+        assert_round_trip::<ROMMRGBColor>(rgb.convert(), 0.5);
+        assert_round_trip::<CIELUVColor>(rgb.convert(), 0.5);
+        assert_round_trip::<CIELCHuvColor>(rgb.convert(), 0.5);
+        assert_round_trip::<CAM16UCSColor>(rgb.convert(), 0.5);
+        assert_round_trip::<XYZColor>(rgb.to_xyz(Illuminant::D50), 0.);
+        assert_round_trip::<XYZColor>(rgb.to_xyz(Illuminant::D65), 0.);
+        // parsed colors are written back in their own syntax
+        for css in [
+            "lab(50 20 -30)",
+            "color(rec2020 0.1 0.2 0.3 / 0.5)",
+            "hsl(30 40% 50%)",
+        ]
+        .iter()
+        {
+            let parsed = parse_css_color(css).unwrap();
+            assert_eq!(&parsed.to_css_string(), css);
+        }
+    }
+
+    #[test]
+    fn test_css4_error_positions() {
+        let wrong_type = parse_css_color("  lab(50% 10 20deg)").map(|c| c.alpha);
+        assert_eq!(
+            wrong_type,
+            err(
+                ParseErrorKind::WrongValueType,
+                13..18,
+                "a number or percentage"
+            )
+        );
+        let unknown_space = parse_css_color("color(CMYK 1 0 0)").map(|c| c.alpha);
+        assert_eq!(
+            unknown_space,
+            err(
+                ParseErrorKind::UnknownName,
+                6..10,
+                "a predefined color space"
+            )
+        );
+        let unknown_function = parse_css_color("rgbx(1 2 3)").map(|c| c.alpha);
+        assert_eq!(
+            unknown_function,
+            err(ParseErrorKind::UnknownName, 0..4, "a color function")
+        );
+        let too_many = parse_css_color("rgb(1 2 3 4)").map(|c| c.alpha);
+        assert_eq!(
+            too_many,
+            err(ParseErrorKind::InvalidSyntax, 10..11, "')' or '/'")
         );
+        let unclosed = parse_css_color("oklch(0.5 0.1 120 / 50%").map(|c| c.alpha);
+        assert_eq!(unclosed, err(ParseErrorKind::InvalidSyntax, 23..23, "')'"));
+        let mixed = parse_css_color("hwb(1, 2% 3%)").map(|c| c.alpha);
+        assert_eq!(mixed, err(ParseErrorKind::InvalidSyntax, 5..6, "a space"));
+        // hex codes and names report their own grammar
+        let hex = parse_css_color(" #12345").unwrap_err();
+        assert_eq!(hex.grammar, ParseGrammar::Hex);
+        assert_eq!(hex.span, 2..7);
+        let name = parse_css_color("reddish").unwrap_err();
+        assert_eq!(name.grammar, ParseGrammar::Name);
+        assert_eq!(name.kind, ParseErrorKind::UnknownName);
     }
 }
@@ -3,6 +3,7 @@
 //! encode arbitrary CSS color descriptions into Scarlet structs. (Source for CSS syntax:
 //! [https://www.w3.org/TR/css-color-3/](https://www.w3.org/TR/css-color-3/).)
 
+use parseerror::{ColorParseError, ParseErrorKind, ParseGrammar};
 use std::error::Error;
 use std::fmt;
 
@@ -18,7 +19,10 @@ pub(crate) enum CSSNumeric {
     Percentage(isize),
 }
 
-/// An error in parsing a CSS string. Covers many different kinds of errors.
+/// An error in parsing a CSS string. Covers many different kinds of errors. The parsers now return
+/// the more detailed [`ColorParseError`], which converts into this.
+///
+/// [`ColorParseError`]: ../parseerror/struct.ColorParseError.html
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
 pub enum CSSParseError {
     /// This indicates that non-numeric characters were used in a string on which a parse into a
@@ -38,6 +42,16 @@ impl fmt::Display for CSSParseError {
     }
 }
 
+impl From<ColorParseError> for CSSParseError {
+    fn from(err: ColorParseError) -> CSSParseError {
+        match err.kind {
+            ParseErrorKind::InvalidCharacters => CSSParseError::InvalidNumericCharacters,
+            ParseErrorKind::InvalidNumber => CSSParseError::InvalidNumericSyntax,
+            _ => CSSParseError::InvalidColorSyntax,
+        }
+    }
+}
+
 impl Error for CSSParseError {
     fn description(&self) -> &str {
         match *self {
@@ -48,92 +62,80 @@ impl Error for CSSParseError {
     }
 }
 
-/// Parses a prechecked integer without a sign, such as "023" or "142". Panics on invalid input.
-fn parse_css_integer(num: &str) -> u64 {
-    num.parse().unwrap()
+/// Makes an error for a CSS number that spans all of it.
+fn number_error(kind: ParseErrorKind, num: &str) -> ColorParseError {
+    ColorParseError::new(kind, ParseGrammar::Functional, 0..num.len()).expecting("a number")
 }
 
-/// Parses a CSS float, such as "123.42" or ".34". Panics on invalid input.
-fn parse_css_float(num: &str) -> f64 {
-    num.parse().unwrap()
+/// Parses a prechecked integer without a sign, such as "023" or "142". Returns `None` if the
+/// integer is empty or too large to represent.
+fn parse_css_integer(num: &str) -> Option<isize> {
+    num.parse().ok()
+}
+
+/// Parses a CSS float, such as "123.42" or ".34". Returns `None` on invalid input, like ".".
+fn parse_css_float(num: &str) -> Option<f64> {
+    num.parse().ok()
 }
 
 /// Parses a given CSS float (two integers separated by '.'), CSS integer (a string of characters
 /// '0'-'9') or a CSS percentage (an integer followed by '%'). Returns a struct that represents these
-/// various possibilities.
-pub(crate) fn parse_css_number(num: &str) -> Result<CSSNumeric, CSSParseError> {
-    let mut chars: Vec<char> = num.chars().collect();
-    // if invalid characters, return appropriate error
-    if !chars.iter().all(|&c| "0123456789-+.%".contains(c)) {
-        return Err(CSSParseError::InvalidNumericCharacters);
+/// various possibilities. Error spans are relative to the given string.
+pub(crate) fn parse_css_number(num: &str) -> Result<CSSNumeric, ColorParseError> {
+    // if invalid characters, return appropriate error pointing at the first one
+    if let Some((i, c)) = num
+        .char_indices()
+        .find(|&(_i, c)| !"0123456789-+.%".contains(c))
+    {
+        return Err(ColorParseError::new(
+            ParseErrorKind::InvalidCharacters,
+            ParseGrammar::Functional,
+            i..i + c.len_utf8(),
+        )
+        .expecting("a number"));
     }
     // test if initial character is '-' or '+'. Remove and set sign flag accordingly.
-    let is_positive = match chars[0] {
-        '-' => false,
-        '+' => true,
-        _ => true,
-    };
-    if "-+".contains(chars[0]) {
-        chars.remove(0);
+    let is_positive = !num.starts_with('-');
+    let unsigned = num.trim_start_matches(['-', '+']);
+    // only one sign is allowed
+    if num.len() - unsigned.len() > 1 {
+        return Err(number_error(ParseErrorKind::InvalidNumber, num));
     }
     // if no longer any characters, throw error
-    if chars.is_empty() {
-        return Err(CSSParseError::InvalidNumericSyntax);
+    if unsigned.is_empty() {
+        return Err(number_error(ParseErrorKind::InvalidNumber, num));
     }
     // if any other pluses or minuses, throw error
-    if chars.iter().any(|&c| "-=".contains(c)) {
-        return Err(CSSParseError::InvalidNumericSyntax);
+    if unsigned.contains(['-', '+']) {
+        return Err(number_error(ParseErrorKind::InvalidNumber, num));
     }
+    let sign = if is_positive { 1 } else { -1 };
     // Test if number contains exactly one period. If more than one, throw error: otherwise, split to
     // cases.
-    match chars.iter().filter(|&c| c == &'.').count() {
+    match unsigned.matches('.').count() {
         0 => {
-            // number or percentage: check, throw error if more than one %
-            match chars.iter().filter(|&c| c == &'%').count() {
-                0 => {
-                    // well-formed integer
-                    let uint = parse_css_integer(&(chars.iter().collect::<String>()));
-                    // adjust for sign
-                    let int = if is_positive {
-                        uint as isize
-                    } else {
-                        -(uint as isize)
-                    };
-                    Ok(CSSNumeric::Integer(int))
-                }
-                1 => {
-                    // check if % is at end
-                    if chars.iter().last().unwrap() == &'%' {
-                        // parse the rest as integer and return
-                        chars.pop();
-                        let uint = parse_css_integer(&(chars.iter().collect::<String>()));
-                        // adjust for sign
-                        let int = if is_positive {
-                            uint as isize
-                        } else {
-                            -(uint as isize)
-                        };
-                        Ok(CSSNumeric::Percentage(int))
-                    } else {
-                        // invalid, throw error
-                        Err(CSSParseError::InvalidNumericSyntax)
-                    }
-                }
-                _ => {
-                    // invalid, throw eerror
-                    Err(CSSParseError::InvalidNumericSyntax)
-                }
+            // number or percentage: check, throw error if % is anywhere but the end
+            match unsigned.strip_suffix('%') {
+                Some(digits) if !digits.contains('%') => parse_css_integer(digits)
+                    .map(|int| CSSNumeric::Percentage(sign * int))
+                    .ok_or_else(|| number_error(ParseErrorKind::InvalidNumber, num)),
+                None => parse_css_integer(unsigned)
+                    .map(|int| CSSNumeric::Integer(sign * int))
+                    .ok_or_else(|| number_error(ParseErrorKind::InvalidNumber, num)),
+                // invalid, throw error
+                _ => Err(number_error(ParseErrorKind::InvalidNumber, num)),
             }
         }
-        1 => {
+        // percentages must be integral at this level of CSS
+        1 if !unsigned.contains('%') => {
             // parse as valid float and account for sign
-            let ufloat = parse_css_float(&(chars.iter().collect::<String>()));
-            let float = if is_positive { ufloat } else { -ufloat };
-            Ok(CSSNumeric::Float(float))
+            parse_css_float(unsigned)
+                .map(|float| CSSNumeric::Float(if is_positive { float } else { -float }))
+                .ok_or_else(|| number_error(ParseErrorKind::InvalidNumber, num))
         }
         _ => {
             // invalid, throw error
-            Err(CSSParseError::InvalidNumericSyntax)
+            Err(number_error(ParseErrorKind::InvalidNumber, num))
         }
     }
 }
@@ -224,24 +226,45 @@ mod tests {
     }
     #[test]
     fn test_errors() {
+        let invalid = |span: ::std::ops::Range<usize>| {
+            Err(ColorParseError::new(
+                ParseErrorKind::InvalidNumber,
+                ParseGrammar::Functional,
+                span,
+            )
+            .expecting("a number"))
+        };
         // test non-numeric characters
         assert_eq!(
             parse_css_number("abc"),
-            Err(CSSParseError::InvalidNumericCharacters)
+            Err(ColorParseError::new(
+                ParseErrorKind::InvalidCharacters,
+                ParseGrammar::Functional,
+                0..1
+            )
+            .expecting("a number"))
         );
         // test multiple periods
-        assert_eq!(
-            parse_css_number("14.23.2"),
-            Err(CSSParseError::InvalidNumericSyntax)
-        );
+        assert_eq!(parse_css_number("14.23.2"), invalid(0..7));
         // test multiple percentages, percentages in wrong place
+        assert_eq!(parse_css_number("-24%%"), invalid(0..5));
+        assert_eq!(parse_css_number("1%2%"), invalid(0..4));
+        // test fractional percentages, lone signs and periods, and empty strings
+        assert_eq!(parse_css_number("4.5%"), invalid(0..4));
+        assert_eq!(parse_css_number("+-4"), invalid(0..3));
+        assert_eq!(parse_css_number("-"), invalid(0..1));
+        assert_eq!(parse_css_number("."), invalid(0..1));
+        assert_eq!(parse_css_number(""), invalid(0..0));
+    }
+    #[test]
+    fn test_css_parse_error_from() {
+        let err = |s: &str| CSSParseError::from(parse_css_number(s).unwrap_err());
+        assert_eq!(err("abc"), CSSParseError::InvalidNumericCharacters);
+        assert_eq!(err("14.23.2"), CSSParseError::InvalidNumericSyntax);
+        let syntax = ColorParseError::new(ParseErrorKind::InvalidSyntax, ParseGrammar::Hex, 0..0);
         assert_eq!(
-            parse_css_number("-24%%"),
-            Err(CSSParseError::InvalidNumericSyntax)
-        );
-        assert_eq!(
-            parse_css_number("1%2%"),
-            Err(CSSParseError::InvalidNumericSyntax)
+            CSSParseError::from(syntax),
+            CSSParseError::InvalidColorSyntax
         );
     }
 }
@@ -0,0 +1,423 @@
+//! This module implements a choice of color difference formulas through [`DeltaE`]. Scarlet's
+//! [`Color::distance`] uses CIEDE2000 with its standard weights, which is the right choice for most
+//! uses, but industries and older specifications often set their tolerances with one of the
+//! earlier formulas instead: CMC l:c is the norm in textiles, CIE94 in printing, and DIN99o in
+//! Germany. Each formula gives a different number for the same pair of colors, so a tolerance only
+//! makes sense with the formula it was set with.
+//!
+//! All of these formulas work on CIELAB colors, which Scarlet keeps in D50: any color is converted
+//! before comparison.
+//!
+//! [`DeltaE`]: enum.DeltaE.html
+//! [`Color::distance`]: ../color/trait.Color.html#method.distance
+
+use color::Color;
+use colors::cielabcolor::CIELABColor;
+
+/// A color difference formula, commonly written ΔE. CIE94 and CMC l:c are *asymmetric*: they weight
+/// the difference by the chroma and hue of one of the colors, the reference (or standard), so
+/// swapping the two colors gives a slightly different result. Every method in Scarlet takes the
+/// reference first.
+/// # Example
+/// A textile tolerance of 1 in CMC(2:1) allows for much bigger lightness differences than CIE76,
+/// the Euclidean distance in CIELAB, because textiles are judged on hue much more than lightness.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::CIELABColor;
+/// # use scarlet::deltae::DeltaE;
+/// let standard = CIELABColor{l: 50., a: 30., b: 10.};
+/// let lighter = CIELABColor{l: 52., a: 30., b: 10.};
+/// assert!((DeltaE::CIE76.between(&standard, &lighter) - 2.).abs() <= 1e-10);
+/// assert!(DeltaE::CMC_2_1.between(&standard, &lighter) < 1.);
+/// // the same as Color::delta_e
+/// assert_eq!(DeltaE::CIE94Textiles.between(&standard, &lighter),
+///            standard.delta_e(&lighter, DeltaE::CIE94Textiles));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DeltaE {
+    /// The original CIE 1976 formula: the Euclidean distance in CIELAB. Simple, but it
+    /// overestimates differences between saturated colors by a large margin.
+    CIE76,
+    /// The CIE 1994 formula with the weights for graphic arts: kL = 1, K1 = 0.045, and K2 = 0.015.
+    CIE94GraphicArts,
+    /// The CIE 1994 formula with the weights for textiles: kL = 2, K1 = 0.048, and K2 = 0.014.
+    CIE94Textiles,
+    /// The formula of the Colour Measurement Committee of the Society of Dyers and Colourists,
+    /// with a lightness weight `l` and chroma weight `c`. 2:1 is used for acceptability, usually in
+    /// textiles, and 1:1 for perceptibility.
+    CMC {
+        /// The lightness weight: larger values tolerate larger lightness differences.
+        l: f64,
+        /// The chroma weight: larger values tolerate larger chroma differences.
+        c: f64,
+    },
+    /// The CIEDE2000 formula, with parametric weights for lightness, chroma, and hue. The standard
+    /// weights are all 1, as in [`DeltaE::CIEDE2000_DEFAULT`](#associatedconstant.CIEDE2000_DEFAULT),
+    /// but textile applications often use kL = 2.
+    CIEDE2000 {
+        /// The lightness weight.
+        k_l: f64,
+        /// The chroma weight.
+        k_c: f64,
+        /// The hue weight.
+        k_h: f64,
+    },
+    /// The Euclidean distance in DIN99o, the 2003 revision of the DIN99 color space of the German
+    /// standard DIN 6176. DIN99o warps CIELAB so that plain distances roughly match CIEDE2000.
+    DIN99o,
+}
+
+impl DeltaE {
+    /// CMC l:c with the weights 2:1, used for acceptability.
+    pub const CMC_2_1: DeltaE = DeltaE::CMC { l: 2.0, c: 1.0 };
+    /// CMC l:c with the weights 1:1, used for perceptibility.
+    pub const CMC_1_1: DeltaE = DeltaE::CMC { l: 1.0, c: 1.0 };
+    /// CIEDE2000 with the standard weights kL = kC = kH = 1, as used by
+    /// [`Color::distance`](../color/trait.Color.html#method.distance).
+    pub const CIEDE2000_DEFAULT: DeltaE = DeltaE::CIEDE2000 {
+        k_l: 1.0,
+        k_c: 1.0,
+        k_h: 1.0,
+    };
+
+    /// Computes the color difference between a reference color and a sample with this formula.
+    /// The result is never negative, and 0 only for identical colors.
+    pub fn between<T: Color, U: Color>(self, reference: &T, sample: &U) -> f64 {
+        let lab1: CIELABColor = reference.convert();
+        let lab2: CIELABColor = sample.convert();
+        match self {
+            DeltaE::CIE76 => cie76(lab1, lab2),
+            DeltaE::CIE94GraphicArts => cie94(lab1, lab2, 1.0, 0.045, 0.015),
+            DeltaE::CIE94Textiles => cie94(lab1, lab2, 2.0, 0.048, 0.014),
+            DeltaE::CMC { l, c } => cmc(lab1, lab2, l, c),
+            DeltaE::CIEDE2000 { k_l, k_c, k_h } => ciede2000(lab1, lab2, k_l, k_c, k_h),
+            DeltaE::DIN99o => cie76(din99o(lab1), din99o(lab2)),
+        }
+    }
+}
+
+/// Gets the chroma and the hue angle in degrees, from 0 to 360, of a CIELAB color.
+fn chroma_hue(lab: CIELABColor) -> (f64, f64) {
+    let hue = if lab.a == 0.0 && lab.b == 0.0 {
+        0.0
+    } else {
+        lab.b.atan2(lab.a).to_degrees().rem_euclid(360.0)
+    };
+    (lab.a.hypot(lab.b), hue)
+}
+
+/// The squared difference in hue between two colors, in the units of CIELAB: this is whatever is
+/// left of the total difference once lightness and chroma are accounted for.
+fn delta_h_squared(lab1: CIELABColor, lab2: CIELABColor, delta_c: f64) -> f64 {
+    // rounding error can make this very slightly negative for colors of the same hue
+    ((lab1.a - lab2.a).powi(2) + (lab1.b - lab2.b).powi(2) - delta_c.powi(2)).max(0.0)
+}
+
+/// The CIE76 color difference: simple Euclidean distance.
+fn cie76(lab1: CIELABColor, lab2: CIELABColor) -> f64 {
+    ((lab1.l - lab2.l).powi(2) + (lab1.a - lab2.a).powi(2) + (lab1.b - lab2.b).powi(2)).sqrt()
+}
+
+/// The CIE94 color difference with lightness weight `k_l` and chroma and hue factors `k_1` and `k_2`.
+fn cie94(lab1: CIELABColor, lab2: CIELABColor, k_l: f64, k_1: f64, k_2: f64) -> f64 {
+    let (c_1, _h_1) = chroma_hue(lab1);
+    let (c_2, _h_2) = chroma_hue(lab2);
+    let delta_l = lab1.l - lab2.l;
+    let delta_c = c_1 - c_2;
+    let delta_h_2 = delta_h_squared(lab1, lab2, delta_c);
+    // the weighting functions only depend on the reference color
+    let s_c = 1.0 + k_1 * c_1;
+    let s_h = 1.0 + k_2 * c_1;
+    ((delta_l / k_l).powi(2) + (delta_c / s_c).powi(2) + delta_h_2 / s_h.powi(2)).sqrt()
+}
+
+/// The CMC l:c color difference with lightness weight `l` and chroma weight `c`.
+fn cmc(lab1: CIELABColor, lab2: CIELABColor, l: f64, c: f64) -> f64 {
+    let (c_1, h_1) = chroma_hue(lab1);
+    let (c_2, _h_2) = chroma_hue(lab2);
+    let delta_l = lab1.l - lab2.l;
+    let delta_c = c_1 - c_2;
+    let delta_h_2 = delta_h_squared(lab1, lab2, delta_c);
+    let deg_cos = |x: f64| x.to_radians().cos();
+    // again, the weighting functions only depend on the reference color
+    let f = (c_1.powi(4) / (c_1.powi(4) + 1900.0)).sqrt();
+    let t = if (164.0..=345.0).contains(&h_1) {
+        0.56 + (0.2 * deg_cos(h_1 + 168.0)).abs()
+    } else {
+        0.36 + (0.4 * deg_cos(h_1 + 35.0)).abs()
+    };
+    let s_l = if lab1.l < 16.0 {
+        0.511
+    } else {
+        0.040975 * lab1.l / (1.0 + 0.01765 * lab1.l)
+    };
+    let s_c = 0.0638 * c_1 / (1.0 + 0.0131 * c_1) + 0.638;
+    let s_h = s_c * (f * t + 1.0 - f);
+    ((delta_l / (l * s_l)).powi(2) + (delta_c / (c * s_c)).powi(2) + delta_h_2 / s_h.powi(2)).sqrt()
+}
+
+/// Converts a CIELAB color to DIN99o, returning it as a CIELAB color so that the distance can be
+/// computed the same way.
+fn din99o(lab: CIELABColor) -> CIELABColor {
+    // the hue rotation of the redness-greenness axis, in degrees
+    let theta = 26.0f64.to_radians();
+    let l_99 = 303.67 * (1.0 + 0.0039 * lab.l).ln();
+    let e = lab.a * theta.cos() + lab.b * theta.sin();
+    let f = 0.83 * (lab.b * theta.cos() - lab.a * theta.sin());
+    let g = e.hypot(f);
+    let c_99 = (1.0 + 0.075 * g).ln() / 0.0435;
+    let h_99 = f.atan2(e) + theta;
+    CIELABColor {
+        l: l_99,
+        a: c_99 * h_99.cos(),
+        b: c_99 * h_99.sin(),
+    }
+}
+
+/// The CIEDE2000 color difference with parametric weights `k_l`, `k_c`, and `k_h`.
+fn ciede2000(lab1: CIELABColor, lab2: CIELABColor, k_l: f64, k_c: f64, k_h: f64) -> f64 {
+    // implementation reference found here:
+    // https://pdfs.semanticscholar.org/969b/c38ea067dd22a47a44bcb59c23807037c8d8.pdf
+
+    // I'm going to match the notation in that text pretty much exactly: it's the only way to
+    // keep this both concise and readable
+
+    // step 1: calculation of C and h
+    // the method hypot returns sqrt(a^2 + b^2)
+    let c_star_1: f64 = lab1.a.hypot(lab1.b);
+    let c_star_2: f64 = lab2.a.hypot(lab2.b);
+
+    let c_bar_ab: f64 = (c_star_1 + c_star_2) / 2.0;
+    let g = 0.5 * (1.0 - ((c_bar_ab.powi(7)) / (c_bar_ab.powi(7) + 25.0f64.powi(7))).sqrt());
+
+    let a_prime_1 = (1.0 + g) * lab1.a;
+    let a_prime_2 = (1.0 + g) * lab2.a;
+
+    let c_prime_1 = a_prime_1.hypot(lab1.b);
+    let c_prime_2 = a_prime_2.hypot(lab2.b);
+
+    // this closure simply does the atan2 like CIELCH, but safely accounts for a == b == 0
+    // we're gonna do this twice, so I just use a closure
+    let h_func = |a: f64, b: f64| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            let val = b.atan2(a).to_degrees();
+            if val < 0.0 {
+                val + 360.0
+            } else {
+                val
+            }
+        }
+    };
+
+    let h_prime_1 = h_func(a_prime_1, lab1.b);
+    let h_prime_2 = h_func(a_prime_2, lab2.b);
+
+    // step 2: computing delta L, delta C, and delta H
+    // take a deep breath, you got this!
+
+    let delta_l = lab2.l - lab1.l;
+    let delta_c = c_prime_2 - c_prime_1;
+    // essentially, compute the difference in hue but keep it in the right range
+    let delta_angle_h = if c_prime_1 * c_prime_2 == 0.0 {
+        0.0
+    } else if (h_prime_2 - h_prime_1).abs() <= 180.0 {
+        h_prime_2 - h_prime_1
+    } else if h_prime_2 - h_prime_1 > 180.0 {
+        h_prime_2 - h_prime_1 - 360.0
+    } else {
+        h_prime_2 - h_prime_1 + 360.0
+    };
+    // now get the Cartesian equivalent of the angle difference in hue
+    // this also corrects for chromaticity mattering less at low luminances
+    let delta_h = 2.0 * (c_prime_1 * c_prime_2).sqrt() * (delta_angle_h / 2.0).to_radians().sin();
+
+    // step 3: the color difference
+    // if you're reading this, it's not too late to back out
+    let l_bar_prime = (lab1.l + lab2.l) / 2.0;
+    let c_bar_prime = (c_prime_1 + c_prime_2) / 2.0;
+    let h_bar_prime = if c_prime_1 * c_prime_2 == 0.0 {
+        h_prime_1 + h_prime_2
+    } else if (h_prime_2 - h_prime_1).abs() <= 180.0 {
+        (h_prime_1 + h_prime_2) / 2.0
+    } else if h_prime_1 + h_prime_2 < 360.0 {
+        (h_prime_1 + h_prime_2 + 360.0) / 2.0
+    } else {
+        (h_prime_1 + h_prime_2 - 360.0) / 2.0
+    };
+
+    // we're gonna use this a lot
+    let deg_cos = |x: f64| x.to_radians().cos();
+
+    let t = 1.0 - 0.17 * deg_cos(h_bar_prime - 30.0)
+        + 0.24 * deg_cos(2.0 * h_bar_prime)
+        + 0.32 * deg_cos(3.0 * h_bar_prime + 6.0)
+        - 0.20 * deg_cos(4.0 * h_bar_prime - 63.0);
+
+    let delta_theta = 30.0 * (-((h_bar_prime - 275.0) / 25.0).powi(2)).exp();
+    let r_c = 2.0 * (c_bar_prime.powi(7) / (c_bar_prime.powi(7) + 25.0f64.powi(7))).sqrt();
+    let s_l = 1.0
+        + ((0.015 * (l_bar_prime - 50.0).powi(2)) / (20.0 + (l_bar_prime - 50.0).powi(2)).sqrt());
+    let s_c = 1.0 + 0.045 * c_bar_prime;
+    let s_h = 1.0 + 0.015 * c_bar_prime * t;
+    let r_t = -r_c * (2.0 * delta_theta).to_radians().sin();
+    // finally, the end result
+    // the three parametric weights scale the differences in lightness, chroma, and hue: larger
+    // weights make differences in that attribute count for less
+    let l_term = delta_l / (k_l * s_l);
+    let c_term = delta_c / (k_c * s_c);
+    let h_term = delta_h / (k_h * s_h);
+    (l_term.powi(2) + c_term.powi(2) + h_term.powi(2) + r_t * c_term * h_term).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn lab(l: f64, a: f64, b: f64) -> CIELABColor {
+        CIELABColor { l, a, b }
+    }
+
+    #[test]
+    fn test_ciede2000_reference_data() {
+        // pairs from the test data of Sharma, Wu, and Dalal (2005)
+        let pairs = [
+            (
+                lab(50.0, 2.6772, -79.7751),
+                lab(50.0, 0.0, -82.7485),
+                2.0425,
+            ),
+            (lab(50.0, 0.0, 0.0), lab(50.0, -1.0, 2.0), 2.3669),
+            (lab(50.0, 2.5, 0.0), lab(73.0, 25.0, -18.0), 27.1492),
+            (
+                lab(60.2574, -34.0099, 36.2677),
+                lab(60.4626, -34.1751, 39.4387),
+                1.2644,
+            ),
+            (
+                lab(2.0776, 0.0795, -1.1350),
+                lab(0.9033, -0.0636, -0.5514),
+                0.9082,
+            ),
+        ];
+        for &(lab1, lab2, expected) in pairs.iter() {
+            let delta_e = DeltaE::CIEDE2000_DEFAULT.between(&lab1, &lab2);
+            assert!((delta_e - expected).abs() <= 1e-4);
+            // this should be exactly the same as Color::distance, both ways round
+            assert_eq!(delta_e, lab1.distance(&lab2));
+            assert!((delta_e - lab2.distance(&lab1)).abs() <= 1e-12);
+        }
+    }
+    #[test]
+    fn test_ciede2000_weights() {
+        // a pure lightness difference is halved by doubling kL and unaffected by the others
+        let lab1 = lab(50.0, 0.0, 0.0);
+        let lab2 = lab(55.0, 0.0, 0.0);
+        let standard = DeltaE::CIEDE2000_DEFAULT.between(&lab1, &lab2);
+        let textile = DeltaE::CIEDE2000 {
+            k_l: 2.0,
+            k_c: 1.0,
+            k_h: 1.0,
+        };
+        let chroma_hue = DeltaE::CIEDE2000 {
+            k_l: 1.0,
+            k_c: 3.0,
+            k_h: 3.0,
+        };
+        assert!((textile.between(&lab1, &lab2) - standard / 2.0).abs() <= 1e-12);
+        assert!((chroma_hue.between(&lab1, &lab2) - standard).abs() <= 1e-12);
+    }
+    #[test]
+    fn test_lightness_only_differences() {
+        // with no chroma, every formula reduces to a weighted lightness difference, which is easy
+        // to check by hand
+        let lab1 = lab(50.0, 0.0, 0.0);
+        let lab2 = lab(60.0, 0.0, 0.0);
+        assert!((DeltaE::CIE76.between(&lab1, &lab2) - 10.0).abs() <= 1e-12);
+        assert!((DeltaE::CIE94GraphicArts.between(&lab1, &lab2) - 10.0).abs() <= 1e-12);
+        assert!((DeltaE::CIE94Textiles.between(&lab1, &lab2) - 5.0).abs() <= 1e-12);
+        // S_L = 0.040975 * 50 / (1 + 0.01765 * 50)
+        let s_l = 2.04875 / 1.8825;
+        assert!((DeltaE::CMC_1_1.between(&lab1, &lab2) - 10.0 / s_l).abs() <= 1e-12);
+        assert!((DeltaE::CMC_2_1.between(&lab1, &lab2) - 5.0 / s_l).abs() <= 1e-12);
+        let l_99 = |l: f64| 303.67 * (1.0 + 0.0039 * l).ln();
+        assert!((DeltaE::DIN99o.between(&lab1, &lab2) - (l_99(60.0) - l_99(50.0))).abs() <= 1e-12);
+        // below L = 16, CMC uses a constant lightness weight
+        let dark1 = lab(5.0, 0.0, 0.0);
+        let dark2 = lab(6.0, 0.0, 0.0);
+        assert!((DeltaE::CMC_1_1.between(&dark1, &dark2) - 1.0 / 0.511).abs() <= 1e-12);
+    }
+    #[test]
+    fn test_chroma_and_hue_differences() {
+        let reference = lab(50.0, 40.0, 30.0);
+        // a pure chroma difference: the same hue angle, 10 units more chroma
+        let more_chroma = lab(50.0, 48.0, 36.0);
+        // C = 50, so S_C = 1 + 0.045 * 50 for graphic arts
+        let cie94 = DeltaE::CIE94GraphicArts.between(&reference, &more_chroma);
+        assert!((cie94 - 10.0 / 3.25).abs() <= 1e-12);
+        let cie94_textiles = DeltaE::CIE94Textiles.between(&reference, &more_chroma);
+        assert!((cie94_textiles - 10.0 / 3.4).abs() <= 1e-12);
+        // CMC weights chroma the same way regardless of l
+        let cmc_s_c = 0.0638 * 50.0 / (1.0 + 0.0131 * 50.0) + 0.638;
+        assert!(
+            (DeltaE::CMC_2_1.between(&reference, &more_chroma) - 10.0 / cmc_s_c).abs() <= 1e-12
+        );
+        assert!(
+            (DeltaE::CMC_2_1.between(&reference, &more_chroma)
+                - DeltaE::CMC_1_1.between(&reference, &more_chroma))
+            .abs()
+                <= 1e-12
+        );
+        // a pure hue difference: the same chroma, rotated
+        let angle = 10.0f64.to_radians();
+        let rotated = lab(
+            50.0,
+            40.0 * angle.cos() - 30.0 * angle.sin(),
+            40.0 * angle.sin() + 30.0 * angle.cos(),
+        );
+        let arc = 2.0 * 50.0 * (angle / 2.0).sin();
+        assert!((DeltaE::CIE76.between(&reference, &rotated) - arc).abs() <= 1e-12);
+        let cie94_hue = DeltaE::CIE94GraphicArts.between(&reference, &rotated);
+        assert!((cie94_hue - arc / 1.75).abs() <= 1e-10);
+    }
+    #[test]
+    fn test_asymmetry() {
+        // CIE94 and CMC weight by the reference, so order matters, but the rest are symmetric
+        let lab1 = lab(50.0, 10.0, 5.0);
+        let lab2 = lab(55.0, 40.0, -20.0);
+        let asymmetric = [
+            DeltaE::CIE94GraphicArts,
+            DeltaE::CIE94Textiles,
+            DeltaE::CMC_2_1,
+        ];
+        for formula in asymmetric.iter() {
+            let there = formula.between(&lab1, &lab2);
+            let back = formula.between(&lab2, &lab1);
+            assert!((there - back).abs() > 1e-3);
+        }
+        let symmetric = [DeltaE::CIE76, DeltaE::CIEDE2000_DEFAULT, DeltaE::DIN99o];
+        for formula in symmetric.iter() {
+            let there = formula.between(&lab1, &lab2);
+            let back = formula.between(&lab2, &lab1);
+            assert!((there - back).abs() <= 1e-12);
+        }
+    }
+    #[test]
+    fn test_identical_colors() {
+        let color = lab(63.0, -20.0, 45.0);
+        let formulas = [
+            DeltaE::CIE76,
+            DeltaE::CIE94GraphicArts,
+            DeltaE::CIE94Textiles,
+            DeltaE::CMC_2_1,
+            DeltaE::CIEDE2000_DEFAULT,
+            DeltaE::DIN99o,
+        ];
+        for formula in formulas.iter() {
+            assert_eq!(formula.between(&color, &color), 0.0);
+        }
+    }
+}
@@ -1,9 +1,17 @@
 //! This module provides an enum of various illuminants supported by Scarlet, as well as their white
-//! point values.The source for this table is from the [ASTM E308
-//! standard](https://www.astm.org/Standards/E308.htm). The only one I could find available freely was
-//! the outdated E308-01 standard, but these values should be the same: they're both copied
-//! photographically from the CIE standard itself. These are normalized so that the Y (luminance)
-//! value is 100.
+//! point values. The D series of daylight illuminants use the white points tabulated in the [ASTM
+//! E308 standard](https://www.astm.org/Standards/E308.htm), as RGB and other standards define their
+//! whites with exactly those values. The only one I could find available freely was the outdated
+//! E308-01 standard, but these values should be the same: they're both copied photographically from
+//! the CIE standard itself. Every other standard illuminant is defined by its spectral power
+//! distribution, and its white point is computed by integrating that against the CIE 1931 standard
+//! observer. All white points are normalized so that the Y (luminance) value is 1. White points for
+//! the CIE 1964 10° observer are available with
+//! [`Illuminant::white_point_with`](enum.Illuminant.html#method.white_point_with).
+
+use observer::{interpolate, StandardObserver};
+use spectral_data::{DAYLIGHT_S0, DAYLIGHT_S1, DAYLIGHT_S2, DAYLIGHT_START, DAYLIGHT_STEP};
+use spectral_data::{ILLUMINANT_B, ILLUMINANT_C, ILLUMINANT_F, SPD_START, SPD_STEP};
 
 /// A listing of the supported CIE standard illuminants, standards that describe a particular set of
 /// lighting conditions. The most common ones for computers are D50 and D65, differing kinds of
@@ -11,6 +19,16 @@
 /// compatibility won't break without warning.
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Illuminant {
+    /// CIE illuminant A, representing a tungsten-filament light bulb: a blackbody at about 2856 K,
+    /// defined by Planck's law. This is the standard for incandescent lighting.
+    A,
+    /// CIE illuminant B, illuminant A filtered to simulate direct noon sunlight at about 4874 K.
+    /// This is deprecated by the CIE in favor of the D series, but still turns up in old data.
+    B,
+    /// CIE illuminant C, illuminant A filtered to simulate average daylight at about 6774 K. Like
+    /// B, this is deprecated in favor of the D series, but was the standard for early NTSC
+    /// television.
+    C,
     /// The CIE D50 standard illuminant. See [this
     /// page](https://en.wikipedia.org/wiki/Standard_illuminant#Illuminant_series_D) for more
     /// information. This has a rough color temperature of 5000 K, so it looks the reddest out of all
@@ -27,6 +45,44 @@ pub enum Illuminant {
     /// The CIE D75 illuminant. Rarer than the others, this is nontheless included for the occasional
     /// place where it might be used.
     D75,
+    /// CIE daylight at any correlated color temperature, in kelvin, as the D series define it: a
+    /// mix of the mean spectrum of daylight and its two main modes of variation. The CIE defines
+    /// this from 4000 K to 25000 K, and temperatures outside that range are treated as the nearest
+    /// end of it. See [`Illuminant::daylight`](#method.daylight) for a constructor.
+    Daylight(f64),
+    /// A blackbody, or Planckian radiator, at the given temperature in kelvin, whose spectrum
+    /// follows Planck's law. Incandescent lights are close to this. See
+    /// [`Illuminant::planckian`](#method.planckian) for a constructor.
+    Planckian(f64),
+    /// The equal-energy illuminant E, with the same power at every wavelength. This isn't a real
+    /// light source, but it's a useful theoretical reference.
+    E,
+    /// CIE illuminant F1, a daylight fluorescent lamp at 6430 K.
+    F1,
+    /// CIE illuminant F2, a cool white fluorescent lamp at 4230 K. This is the most common
+    /// fluorescent light, particularly in offices.
+    F2,
+    /// CIE illuminant F3, a white fluorescent lamp at 3450 K.
+    F3,
+    /// CIE illuminant F4, a warm white fluorescent lamp at 2940 K.
+    F4,
+    /// CIE illuminant F5, a daylight fluorescent lamp at 6350 K.
+    F5,
+    /// CIE illuminant F6, a lite white fluorescent lamp at 4150 K.
+    F6,
+    /// CIE illuminant F7, a broadband fluorescent lamp simulating D65.
+    F7,
+    /// CIE illuminant F8, a broadband fluorescent lamp simulating D50.
+    F8,
+    /// CIE illuminant F9, a broadband cool white deluxe fluorescent lamp at 4150 K.
+    F9,
+    /// CIE illuminant F10, a narrow-band fluorescent lamp at 5000 K.
+    F10,
+    /// CIE illuminant F11, a narrow-band fluorescent lamp at 4000 K, often sold as TL84 and used
+    /// for color matching in stores.
+    F11,
+    /// CIE illuminant F12, a narrow-band fluorescent lamp at 3000 K.
+    F12,
     /// Represents a light of any given hue, as an array `[X, Y, Z]` in CIE 1931 space. This does not
     /// allow one to replicate any illuminant, but it does allow for custom illuminants and the
     /// ability to chromatically adapt to unique lighting conditions, like dark shade or colored
@@ -45,7 +101,156 @@ pub(crate) static ILLUMINANT_WHITE_POINTS: [[f64; 3]; 4] = [
     [0.94972, 1.00000, 1.22638],
 ];
 
+/// The D series, in the order of their white points in `ILLUMINANT_WHITE_POINTS`.
+static D_SERIES: [Illuminant; 4] = [
+    Illuminant::D50,
+    Illuminant::D55,
+    Illuminant::D65,
+    Illuminant::D75,
+];
+
+/// The illuminants defined by their spectra, in the order of their white points in
+/// `SPECTRAL_WHITE_POINTS`.
+static SPECTRAL_ILLUMINANTS: [Illuminant; 16] = [
+    Illuminant::A,
+    Illuminant::B,
+    Illuminant::C,
+    Illuminant::E,
+    Illuminant::F1,
+    Illuminant::F2,
+    Illuminant::F3,
+    Illuminant::F4,
+    Illuminant::F5,
+    Illuminant::F6,
+    Illuminant::F7,
+    Illuminant::F8,
+    Illuminant::F9,
+    Illuminant::F10,
+    Illuminant::F11,
+    Illuminant::F12,
+];
+
+/// Integrates the spectrum of each of the given illuminants against the given observer.
+fn spectral_white_points(illuminants: &[Illuminant], observer: StandardObserver) -> Vec<[f64; 3]> {
+    illuminants
+        .iter()
+        .map(|ill| {
+            observer
+                .cmfs()
+                .white_point(|wl| ill.spectral_power(wl).expect("Illuminant has a spectrum."))
+        })
+        .collect()
+}
+
+lazy_static! {
+    // computed once, as every chromatic adaptation needs the white point
+    static ref SPECTRAL_WHITE_POINTS: Vec<[f64; 3]> =
+        spectral_white_points(&SPECTRAL_ILLUMINANTS, StandardObserver::CIE1931);
+    static ref SPECTRAL_WHITE_POINTS_10: Vec<[f64; 3]> =
+        spectral_white_points(&SPECTRAL_ILLUMINANTS, StandardObserver::CIE1964);
+    static ref D_SERIES_WHITE_POINTS_10: Vec<[f64; 3]> =
+        spectral_white_points(&D_SERIES, StandardObserver::CIE1964);
+}
+
+/// The relative spectral power of a blackbody at the given temperature and wavelength, by Planck's
+/// law, normalized to 100 at 560 nm. `c2` is the second radiation constant in nm⋅K.
+fn planck(temperature: f64, c2: f64, wavelength: f64) -> f64 {
+    if wavelength <= 0.0 {
+        return 0.0;
+    }
+    // (e^a - 1) / (e^b - 1) is written as e^(a - b) (1 - e^-a) / (1 - e^-b) so that neither
+    // exponential overflows at low temperatures
+    let a = c2 / (560.0 * temperature);
+    let b = c2 / (wavelength * temperature);
+    100.0 * (560.0 / wavelength).powi(5) * (a - b).exp() * (-a).exp_m1() / (-b).exp_m1()
+}
+
+/// The relative spectral power of CIE illuminant A, as the CIE defines it: a blackbody at 2848 K
+/// with the old value of the second radiation constant, normalized to 100 at 560 nm.
+fn illuminant_a(wavelength: f64) -> f64 {
+    planck(2848.0, 1.435e7, wavelength)
+}
+
+/// The second radiation constant in Planck's law, in nm⋅K, as CIE 15:2004 gives it.
+const C2: f64 = 1.4388e7;
+
+/// Converts a nominal color temperature, like the 6500 K of D65, to the temperature the D series
+/// actually use. The named illuminants were defined before the second radiation constant in
+/// Planck's law was revised from 1.4380 × 10⁻² m⋅K to 1.4388 × 10⁻² m⋅K, and their temperatures were
+/// scaled to keep the same spectra.
+fn nominal_to_actual_cct(cct: f64) -> f64 {
+    cct * 1.4388 / 1.4380
+}
+
+/// Gets the xy chromaticity of CIE daylight at the given correlated color temperature.
+fn daylight_chromaticity(cct: f64) -> (f64, f64) {
+    let t = cct.clamp(4000.0, 25000.0);
+    let x = if t <= 7000.0 {
+        -4.6070e9 / t.powi(3) + 2.9678e6 / t.powi(2) + 0.09911e3 / t + 0.244063
+    } else {
+        -2.0064e9 / t.powi(3) + 1.9018e6 / t.powi(2) + 0.24748e3 / t + 0.237040
+    };
+    let y = -3.000 * x * x + 2.870 * x - 0.275;
+    (x, y)
+}
+
+/// Gets the relative spectral power of CIE daylight at the given correlated color temperature and
+/// wavelength, normalized so the mean spectrum S0 is 100 at 560 nm.
+fn daylight_spd(cct: f64, wavelength: f64) -> f64 {
+    let (x, y) = daylight_chromaticity(cct);
+    let m = 0.0241 + 0.2562 * x - 0.7341 * y;
+    // the CIE rounds the weights to 3 decimal places, and the standard tables depend on that
+    let m1 = ((-1.3515 - 1.7703 * x + 5.9114 * y) / m * 1000.0).round() / 1000.0;
+    let m2 = ((0.0300 - 31.4424 * x + 30.0717 * y) / m * 1000.0).round() / 1000.0;
+    let basis = |data: &[f64]| interpolate(data, DAYLIGHT_START, DAYLIGHT_STEP, wavelength);
+    basis(&DAYLIGHT_S0) + m1 * basis(&DAYLIGHT_S1) + m2 * basis(&DAYLIGHT_S2)
+}
+
 impl Illuminant {
+    /// Makes CIE daylight with the given correlated color temperature in kelvin, as the D series
+    /// are defined. This lets you use daylight that isn't one of the named illuminants, like D93
+    /// for old monitors or a measured sky. The named illuminants use slightly different
+    /// temperatures than their names suggest, because of a change in physical constants since
+    /// they were defined: D65 is 6504 K. To match one of those, like D93, multiply the nominal
+    /// temperature by 1.4388 / 1.4380.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let d65 = Illuminant::daylight(6500. * 1.4388 / 1.4380);
+    /// let wp = d65.white_point();
+    /// // very close to the tabulated D65 white point
+    /// assert!((wp[0] - 0.95047).abs() <= 1e-4);
+    /// assert!((wp[2] - 1.08884).abs() <= 1e-3);
+    /// // lower temperatures are redder
+    /// let warm = Illuminant::daylight(4500.);
+    /// assert!(warm.white_point()[2] < wp[2]);
+    /// ```
+    pub fn daylight(cct: f64) -> Illuminant {
+        Illuminant::Daylight(cct)
+    }
+    /// Makes a blackbody illuminant with the given temperature in kelvin, which must be positive.
+    /// Blackbodies are defined from Planck's law with the current value of the second radiation
+    /// constant, so illuminant A, which uses an older value, is the same as a blackbody at about
+    /// 2856 K.
+    /// # Panics
+    /// Panics if `kelvin` isn't positive: Planck's law has no meaning there.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let bulb = Illuminant::planckian(2856.);
+    /// let (wp, a) = (bulb.white_point(), Illuminant::A.white_point());
+    /// assert!((wp[0] - a[0]).abs() <= 1e-3 && (wp[2] - a[2]).abs() <= 1e-3);
+    /// ```
+    pub fn planckian(kelvin: f64) -> Illuminant {
+        assert!(
+            kelvin > 0.0,
+            "Blackbody temperature must be positive, not {} K.",
+            kelvin
+        );
+        Illuminant::Planckian(kelvin)
+    }
     /// Gets the XYZ coordinates of the white point value of the illuminant, normalized so Y = 1.
     /// # Example
     ///
@@ -55,14 +260,242 @@ impl Illuminant {
     /// assert!((wp[0] - 0.95047).abs() <= 1e-10);
     /// assert!((wp[1] - 1.00000).abs() <= 1e-10);
     /// assert!((wp[2] - 1.08884).abs() <= 1e-10);
+    /// // computed from the spectrum
+    /// let wp = Illuminant::A.white_point(); // [1.09850, 1.00000, 0.35585]
+    /// assert!((wp[0] - 1.09850).abs() <= 1e-5);
+    /// assert!((wp[2] - 0.35585).abs() <= 1e-5);
     /// ```
     pub fn white_point(&self) -> [f64; 3] {
+        self.white_point_with(StandardObserver::CIE1931)
+    }
+    /// Gets the XYZ coordinates of the white point of the illuminant for the given standard
+    /// observer, normalized so Y = 1. For the 2° observer, the D series use the tabulated ASTM E308
+    /// values that RGB color spaces are defined with. Everything else, including the D series for
+    /// the 10° observer, is integrated from its spectrum. `Custom` illuminants are already given
+    /// in XYZ, so they're the same for either observer.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::Illuminant;
+    /// # use scarlet::observer::StandardObserver;
+    /// let wp = Illuminant::A.white_point_with(StandardObserver::CIE1964); // [1.11144, 1.00000, 0.35200]
+    /// assert!((wp[0] - 1.11144).abs() <= 1e-4);
+    /// assert!((wp[2] - 0.35200).abs() <= 1e-4);
+    /// ```
+    pub fn white_point_with(&self, observer: StandardObserver) -> [f64; 3] {
+        let d_series = match observer {
+            StandardObserver::CIE1931 => &ILLUMINANT_WHITE_POINTS[..],
+            StandardObserver::CIE1964 => &D_SERIES_WHITE_POINTS_10[..],
+        };
         match *self {
-            Illuminant::D50 => ILLUMINANT_WHITE_POINTS[0],
-            Illuminant::D55 => ILLUMINANT_WHITE_POINTS[1],
-            Illuminant::D65 => ILLUMINANT_WHITE_POINTS[2],
-            Illuminant::D75 => ILLUMINANT_WHITE_POINTS[3],
+            Illuminant::D50 => d_series[0],
+            Illuminant::D55 => d_series[1],
+            Illuminant::D65 => d_series[2],
+            Illuminant::D75 => d_series[3],
             Illuminant::Custom(xyz) => [xyz[0] / xyz[1], 1.0, xyz[2] / xyz[1]],
+            Illuminant::Daylight(cct) => observer.cmfs().white_point(|wl| daylight_spd(cct, wl)),
+            Illuminant::Planckian(t) => observer.cmfs().white_point(|wl| planck(t, C2, wl)),
+            ill => {
+                let index = SPECTRAL_ILLUMINANTS
+                    .iter()
+                    .position(|&other| other == ill)
+                    .expect("Illuminant has a spectrum.");
+                match observer {
+                    StandardObserver::CIE1931 => SPECTRAL_WHITE_POINTS[index],
+                    StandardObserver::CIE1964 => SPECTRAL_WHITE_POINTS_10[index],
+                }
+            }
+        }
+    }
+    /// Gets the relative spectral power of the illuminant at the given wavelength in nanometers,
+    /// or `None` if the illuminant isn't defined by its spectrum. Tabulated spectra are linearly
+    /// interpolated between their samples every 5 nm from 380 nm to 780 nm, and are 0 outside of
+    /// that range. Daylight, including the D series, is defined from 300 nm to 830 nm. The values
+    /// are on the scale the CIE uses, which is arbitrary but usually around 100 at 560 nm: only
+    /// their ratios matter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::Illuminant;
+    /// // illuminant A is defined to be 100 at 560 nm
+    /// assert!((Illuminant::A.spectral_power(560.).unwrap() - 100.).abs() <= 1e-10);
+    /// // fluorescent lamps have sharp peaks from mercury emission lines
+    /// let f2 = Illuminant::F2;
+    /// assert!(f2.spectral_power(435.).unwrap() > 5. * f2.spectral_power(450.).unwrap());
+    /// assert_eq!(Illuminant::Custom([1., 1., 1.]).spectral_power(500.), None);
+    /// ```
+    pub fn spectral_power(&self, wavelength: f64) -> Option<f64> {
+        let table = |data: &[f64]| Some(interpolate(data, SPD_START, SPD_STEP, wavelength));
+        match *self {
+            Illuminant::A => Some(illuminant_a(wavelength)),
+            Illuminant::B => table(&ILLUMINANT_B),
+            Illuminant::C => table(&ILLUMINANT_C),
+            Illuminant::E => Some(100.0),
+            Illuminant::F1 => table(&ILLUMINANT_F[0]),
+            Illuminant::F2 => table(&ILLUMINANT_F[1]),
+            Illuminant::F3 => table(&ILLUMINANT_F[2]),
+            Illuminant::F4 => table(&ILLUMINANT_F[3]),
+            Illuminant::F5 => table(&ILLUMINANT_F[4]),
+            Illuminant::F6 => table(&ILLUMINANT_F[5]),
+            Illuminant::F7 => table(&ILLUMINANT_F[6]),
+            Illuminant::F8 => table(&ILLUMINANT_F[7]),
+            Illuminant::F9 => table(&ILLUMINANT_F[8]),
+            Illuminant::F10 => table(&ILLUMINANT_F[9]),
+            Illuminant::F11 => table(&ILLUMINANT_F[10]),
+            Illuminant::F12 => table(&ILLUMINANT_F[11]),
+            Illuminant::D50 => Some(daylight_spd(nominal_to_actual_cct(5000.0), wavelength)),
+            Illuminant::D55 => Some(daylight_spd(nominal_to_actual_cct(5500.0), wavelength)),
+            Illuminant::D65 => Some(daylight_spd(nominal_to_actual_cct(6500.0), wavelength)),
+            Illuminant::D75 => Some(daylight_spd(nominal_to_actual_cct(7500.0), wavelength)),
+            Illuminant::Daylight(cct) => Some(daylight_spd(cct, wavelength)),
+            Illuminant::Planckian(t) => Some(planck(t, C2, wavelength)),
+            Illuminant::Custom(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn chromaticity(ill: Illuminant) -> (f64, f64) {
+        let wp = ill.white_point();
+        let sum = wp[0] + wp[1] + wp[2];
+        (wp[0] / sum, wp[1] / sum)
+    }
+
+    #[test]
+    fn test_spectral_white_points() {
+        // the chromaticities published in CIE 15:2004, table T.3 and T.8.2
+        let published = [
+            (Illuminant::A, 0.44757, 0.40745),
+            (Illuminant::B, 0.34842, 0.35161),
+            (Illuminant::C, 0.31006, 0.31616),
+            (Illuminant::E, 1.0 / 3.0, 1.0 / 3.0),
+            (Illuminant::F1, 0.31310, 0.33727),
+            (Illuminant::F2, 0.37208, 0.37529),
+            (Illuminant::F3, 0.40910, 0.39430),
+            (Illuminant::F4, 0.44018, 0.40329),
+            (Illuminant::F5, 0.31379, 0.34531),
+            (Illuminant::F6, 0.37790, 0.38835),
+            (Illuminant::F7, 0.31292, 0.32933),
+            (Illuminant::F8, 0.34588, 0.35875),
+            (Illuminant::F9, 0.37417, 0.37281),
+            (Illuminant::F11, 0.38052, 0.37713),
+            (Illuminant::F12, 0.43695, 0.40441),
+        ];
+        for &(ill, x, y) in published.iter() {
+            let (x_c, y_c) = chromaticity(ill);
+            assert!((x_c - x).abs() <= 3e-4, "{:?}: {} != {}", ill, x_c, x);
+            assert!((y_c - y).abs() <= 3e-4, "{:?}: {} != {}", ill, y_c, y);
+        }
+        // F10 integrates to about 0.001 below its published y chromaticity
+        let (x_c, y_c) = chromaticity(Illuminant::F10);
+        assert!((x_c - 0.34609).abs() <= 2e-3 && (y_c - 0.35986).abs() <= 2e-3);
+    }
+    #[test]
+    fn test_white_point_normalized() {
+        for ill in SPECTRAL_ILLUMINANTS.iter() {
+            assert_eq!(ill.white_point()[1], 1.0);
+            assert!(ill.spectral_power(550.0).unwrap() > 0.0);
+        }
+        // tables end at 380 nm and 780 nm
+        assert_eq!(Illuminant::C.spectral_power(300.0), Some(0.0));
+        assert_eq!(Illuminant::F11.spectral_power(800.0), Some(0.0));
+        assert!(
+            (Illuminant::F11.spectral_power(542.5).unwrap() - (39.59 + 72.84) / 2.0).abs() <= 1e-10
+        );
+        assert_eq!(Illuminant::Custom([1.0; 3]).spectral_power(550.0), None);
+    }
+    #[test]
+    fn test_daylight() {
+        // the D series spectra integrate to their tabulated 2° white points
+        for ill in D_SERIES.iter() {
+            let spectral = StandardObserver::CIE1931
+                .cmfs()
+                .white_point(|wl| ill.spectral_power(wl).unwrap());
+            let tabulated = ill.white_point();
+            assert!((spectral[0] - tabulated[0]).abs() <= 1e-4);
+            assert!((spectral[2] - tabulated[2]).abs() <= 3e-4);
+        }
+        // the chromaticity of D65 published in CIE 15:2004
+        let (x, y) = daylight_chromaticity(nominal_to_actual_cct(6500.0));
+        assert!((x - 0.31272).abs() <= 1e-5 && (y - 0.32903).abs() <= 1e-4);
+        // S1 and S2 are 0 at 560 nm, so every daylight spectrum is 100 there
+        for &cct in [4000.0, 5003.0, 9300.0, 20000.0].iter() {
+            assert!(
+                (Illuminant::daylight(cct).spectral_power(560.0).unwrap() - 100.0).abs() <= 1e-10
+            );
+        }
+        // temperatures outside the defined range are clamped
+        assert_eq!(
+            Illuminant::daylight(2000.0).white_point(),
+            Illuminant::daylight(4000.0).white_point()
+        );
+    }
+    #[test]
+    fn test_planckian() {
+        // points on the Planckian locus
+        let published = [
+            (1000.0, 0.65275, 0.34446),
+            (2700.0, 0.45986, 0.41060),
+            (5000.0, 0.34510, 0.35161),
+            (10000.0, 0.28063, 0.28829),
+        ];
+        for &(t, x, y) in published.iter() {
+            let (x_c, y_c) = chromaticity(Illuminant::planckian(t));
+            assert!((x_c - x).abs() <= 1e-5 && (y_c - y).abs() <= 1e-5);
+        }
+        // Illuminant A is a blackbody with an older constant
+        for &wl in [380.0, 560.0, 700.0].iter() {
+            let a = Illuminant::A.spectral_power(wl).unwrap();
+            let bb = planck(2848.0 * C2 / 1.435e7, C2, wl);
+            assert!((a - bb).abs() <= 1e-10);
+        }
+        // low temperatures don't overflow
+        assert!(Illuminant::planckian(50.0).white_point()[0].is_finite());
+    }
+    #[test]
+    #[should_panic(expected = "must be positive")]
+    fn test_planckian_zero() {
+        Illuminant::planckian(0.0);
+    }
+    #[test]
+    #[should_panic(expected = "must be positive")]
+    fn test_planckian_negative() {
+        Illuminant::planckian(-300.0);
+    }
+    #[test]
+    fn test_10_degree_white_points() {
+        // the white points for the 10° observer published in ASTM E308
+        let published = [
+            (Illuminant::A, 1.11144, 0.35200),
+            (Illuminant::C, 0.97285, 1.16145),
+            (Illuminant::D50, 0.96720, 0.81427),
+            (Illuminant::D55, 0.95799, 0.90926),
+            (Illuminant::D65, 0.94811, 1.07304),
+            (Illuminant::D75, 0.94416, 1.20641),
+        ];
+        for &(ill, x, z) in published.iter() {
+            let wp = ill.white_point_with(StandardObserver::CIE1964);
+            assert!((wp[0] - x).abs() <= 1e-4, "{:?}: {} != {}", ill, wp[0], x);
+            assert!((wp[2] - z).abs() <= 3e-4, "{:?}: {} != {}", ill, wp[2], z);
         }
+        // E is at the center of the chromaticity diagram for both observers
+        let e = Illuminant::E.white_point_with(StandardObserver::CIE1964);
+        assert!((e[0] - 1.0).abs() <= 1e-3 && (e[2] - 1.0).abs() <= 1e-3);
+        // the observers really are different
+        assert!(
+            (Illuminant::F2.white_point_with(StandardObserver::CIE1964)[0]
+                - Illuminant::F2.white_point()[0])
+                .abs()
+                > 1e-3
+        );
+        assert_eq!(
+            Illuminant::Custom([0.5, 0.5, 0.25]).white_point_with(StandardObserver::CIE1964),
+            [1.0, 1.0, 0.5]
+        );
     }
 }
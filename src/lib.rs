@@ -25,19 +25,30 @@ extern crate serde_derive;
 #[macro_use]
 extern crate lazy_static;
 
+pub mod alpha;
 pub mod bound;
+pub mod chromaticadaptation;
 pub mod color;
 pub mod colormap;
 pub mod colorpoint;
+pub mod colorrendering;
 pub mod colors;
+pub mod colortemperature;
 mod consts;
 pub mod coord;
-mod csscolor;
+pub mod csscolor;
 mod cssnumeric;
+pub mod deltae;
 pub mod illuminants;
 pub mod material_colors;
 mod matplotlib_cmaps;
+pub mod observer;
+pub mod parseerror;
+pub mod pigment;
 pub mod prelude;
+pub mod rgbcolorspace;
+mod spectral_data;
+pub mod spectrum;
 mod visual_gamut;
 // pub mod doc;
 
@@ -0,0 +1,207 @@
+//! This module provides the CIE standard observers through [`StandardObserver`]. A standard
+//! observer is a set of color matching functions, which describe how strongly an average person
+//! responds to each wavelength of light and so turn a spectrum into XYZ coordinates. The CIE defines
+//! two: the 1931 2° observer, for colors that take up a small part of the field of view (about the
+//! size of a thumbnail at arm's length), and the 1964 10° observer, for larger patches. The 1931
+//! observer is by far the most common, and everything in Scarlet uses it unless told otherwise.
+//!
+//! XYZ coordinates from different observers aren't interchangeable: the 10° observer gives a
+//! different XYZ space, with its own white points. Scarlet's conversions between color spaces all
+//! assume the 2° observer, as the standards defining those spaces do.
+//!
+//! The data is compiled into Scarlet, so that computing with spectra doesn't depend on any files
+//! being around at runtime.
+//!
+//! [`StandardObserver`]: enum.StandardObserver.html
+
+use super::csv;
+
+/// The CIE 1931 2° color matching functions, sampled every 5 nm from 360 nm to 830 nm.
+static CIE_1931_DATA: &str = include_str!("cie-1931-standard-matching.csv");
+/// The CIE 1964 10° color matching functions, sampled every 5 nm from 360 nm to 830 nm.
+static CIE_1964_DATA: &str = include_str!("cie-1964-standard-matching.csv");
+
+/// A CIE standard observer, which determines how spectra are turned into XYZ coordinates.
+/// # Example
+/// The same light has a slightly different white point for each observer.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::observer::StandardObserver;
+/// let d65_2 = Illuminant::D65.white_point_with(StandardObserver::CIE1931);
+/// let d65_10 = Illuminant::D65.white_point_with(StandardObserver::CIE1964);
+/// assert_eq!(d65_2, Illuminant::D65.white_point());
+/// assert!((d65_10[0] - 0.94811).abs() <= 1e-4);
+/// assert!((d65_10[2] - 1.07304).abs() <= 3e-4);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum StandardObserver {
+    /// The CIE 1931 2° standard observer, for small fields of view. This is the default.
+    CIE1931,
+    /// The CIE 1964 10° supplementary standard observer, for fields of view larger than about 4°.
+    CIE1964,
+}
+
+impl Default for StandardObserver {
+    /// The CIE 1931 2° observer, which Scarlet uses everywhere else.
+    fn default() -> StandardObserver {
+        StandardObserver::CIE1931
+    }
+}
+
+impl StandardObserver {
+    /// Gets the table of color matching functions of this observer.
+    pub(crate) fn cmfs(self) -> &'static ColorMatchingFunctions {
+        match self {
+            StandardObserver::CIE1931 => &CIE_1931,
+            StandardObserver::CIE1964 => &CIE_1964,
+        }
+    }
+    /// Gets the values of the color matching functions x̄, ȳ, and z̄ at the given wavelength in
+    /// nanometers, linearly interpolated between the tabulated values every 5 nm from 360 nm to 830
+    /// nm. Outside of that range, the functions are 0.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::observer::StandardObserver;
+    /// // ȳ peaks at 555 nm, and is defined to be 1 there
+    /// let cmfs = StandardObserver::CIE1931.color_matching_functions(555.);
+    /// assert_eq!(cmfs[1], 1.0);
+    /// // the long and short wavelength ends are effectively invisible
+    /// assert!(StandardObserver::CIE1964.color_matching_functions(830.)[1] < 1e-6);
+    /// ```
+    pub fn color_matching_functions(self, wavelength: f64) -> [f64; 3] {
+        let cmfs = self.cmfs();
+        let start = f64::from(cmfs.wavelengths[0]);
+        let step = f64::from(cmfs.wavelengths[1] - cmfs.wavelengths[0]);
+        let pos = (wavelength - start) / step;
+        if pos < 0.0 || pos > (cmfs.values.len() - 1) as f64 {
+            return [0.0; 3];
+        }
+        let i = pos.floor() as usize;
+        if i + 1 == cmfs.values.len() {
+            return cmfs.values[i];
+        }
+        let frac = pos - i as f64;
+        let (lo, hi) = (cmfs.values[i], cmfs.values[i + 1]);
+        [
+            lo[0] * (1.0 - frac) + hi[0] * frac,
+            lo[1] * (1.0 - frac) + hi[1] * frac,
+            lo[2] * (1.0 - frac) + hi[2] * frac,
+        ]
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Record {
+    wavelength: u16,
+    xbar: f64,
+    ybar: f64,
+    zbar: f64,
+}
+
+/// A table of color matching functions.
+#[derive(Debug, Clone)]
+pub(crate) struct ColorMatchingFunctions {
+    /// The wavelengths, in nanometers, at which the functions are sampled, evenly spaced.
+    pub(crate) wavelengths: Vec<u16>,
+    /// The values of x̄, ȳ, and z̄ at each wavelength.
+    pub(crate) values: Vec<[f64; 3]>,
+}
+
+impl ColorMatchingFunctions {
+    /// Parses color matching functions from CSV data with a header of `wavelength,xbar,ybar,zbar`.
+    fn from_csv(data: &str) -> ColorMatchingFunctions {
+        let mut reader = csv::Reader::from_reader(data.as_bytes());
+        let mut wavelengths = vec![];
+        let mut values = vec![];
+        for result in reader.deserialize() {
+            // we should panic on bad data: this file is supplied by us!
+            let record: Record = result.expect("Color matching data is valid.");
+            wavelengths.push(record.wavelength);
+            values.push([record.xbar, record.ybar, record.zbar]);
+        }
+        ColorMatchingFunctions {
+            wavelengths,
+            values,
+        }
+    }
+    /// Integrates a spectral power distribution, given as a function of wavelength in nanometers,
+    /// against these color matching functions. The result is scaled so that Y = 1.
+    pub(crate) fn white_point<F: Fn(f64) -> f64>(&self, spd: F) -> [f64; 3] {
+        let mut xyz = [0.0; 3];
+        for (&wavelength, cmf) in self.wavelengths.iter().zip(self.values.iter()) {
+            let power = spd(f64::from(wavelength));
+            for i in 0..3 {
+                xyz[i] += power * cmf[i];
+            }
+        }
+        [xyz[0] / xyz[1], 1.0, xyz[2] / xyz[1]]
+    }
+}
+
+lazy_static! {
+    static ref CIE_1931: ColorMatchingFunctions = ColorMatchingFunctions::from_csv(CIE_1931_DATA);
+    static ref CIE_1964: ColorMatchingFunctions = ColorMatchingFunctions::from_csv(CIE_1964_DATA);
+}
+
+/// Linearly interpolates a table of values sampled every `step` nm starting at `start` nm, giving 0
+/// outside of the table.
+pub(crate) fn interpolate(table: &[f64], start: f64, step: f64, wavelength: f64) -> f64 {
+    let pos = (wavelength - start) / step;
+    if pos < 0.0 || pos > (table.len() - 1) as f64 {
+        return 0.0;
+    }
+    let i = pos.floor() as usize;
+    if i + 1 == table.len() {
+        table[i]
+    } else {
+        let frac = pos - i as f64;
+        table[i] * (1.0 - frac) + table[i + 1] * frac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_embedded_data() {
+        assert_eq!(CIE_1931.wavelengths.len(), 95);
+        assert_eq!(CIE_1931.wavelengths[0], 360);
+        assert_eq!(CIE_1931.wavelengths[94], 830);
+        // 560 nm
+        assert_eq!(CIE_1931.values[40], [0.5945, 0.995, 0.0039]);
+        assert_eq!(CIE_1964.wavelengths, CIE_1931.wavelengths);
+        assert_eq!(CIE_1964.values[40], [0.705224, 0.99734, 0.0]);
+    }
+    #[test]
+    fn test_color_matching_functions() {
+        let observer = StandardObserver::CIE1964;
+        assert_eq!(
+            observer.color_matching_functions(560.0),
+            CIE_1964.values[40]
+        );
+        let halfway = observer.color_matching_functions(562.5);
+        for (i, value) in halfway.iter().enumerate() {
+            let mean = (CIE_1964.values[40][i] + CIE_1964.values[41][i]) / 2.0;
+            assert!((value - mean).abs() <= 1e-12);
+        }
+        assert_eq!(
+            observer.color_matching_functions(830.0),
+            CIE_1964.values[94]
+        );
+        assert_eq!(observer.color_matching_functions(350.0), [0.0; 3]);
+        assert_eq!(StandardObserver::default(), StandardObserver::CIE1931);
+    }
+    #[test]
+    fn test_interpolate() {
+        let table = [1.0, 3.0, 2.0];
+        assert_eq!(interpolate(&table, 400.0, 10.0, 400.0), 1.0);
+        assert_eq!(interpolate(&table, 400.0, 10.0, 405.0), 2.0);
+        assert_eq!(interpolate(&table, 400.0, 10.0, 420.0), 2.0);
+        assert_eq!(interpolate(&table, 400.0, 10.0, 399.0), 0.0);
+        assert_eq!(interpolate(&table, 400.0, 10.0, 421.0), 0.0);
+    }
+}
@@ -0,0 +1,215 @@
+//! This module defines [`ColorParseError`], the error returned by every string parser in Scarlet:
+//! hex codes, color names, and CSS color functions alike. Besides what kind of error happened, it
+//! records where in the input the problem is, what the parser expected to find there, and which
+//! grammar it was trying to use, so that tools built on Scarlet can point users to the exact spot
+//! in a string that needs fixing.
+//!
+//! [`ColorParseError`]: struct.ColorParseError.html
+
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+/// The kind of syntax a parser was attempting to read when it failed.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum ParseGrammar {
+    /// A hex code, like `"#ff8000"` or `"f80"`.
+    Hex,
+    /// A color name or keyword, like `"rebeccapurple"` or `"transparent"`.
+    Name,
+    /// A functional notation, like `"rgb(255, 128, 0)"` or `"oklch(70% 0.1 120 / 50%)"`.
+    Functional,
+}
+
+impl ParseGrammar {
+    /// Guesses which grammar a string was meant to use from its shape: anything with a parenthesis
+    /// is a function, anything starting with `#` or made only of hex digits is a hex code, and
+    /// everything else is a name.
+    pub(crate) fn guess(input: &str) -> ParseGrammar {
+        let input = input.trim();
+        if input.contains('(') || input.contains(')') {
+            ParseGrammar::Functional
+        } else if input.starts_with('#')
+            || (!input.is_empty() && input.chars().all(|c| c.is_ascii_hexdigit()))
+        {
+            ParseGrammar::Hex
+        } else {
+            ParseGrammar::Name
+        }
+    }
+}
+
+impl fmt::Display for ParseGrammar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseGrammar::Hex => write!(f, "hex code"),
+            ParseGrammar::Name => write!(f, "color name"),
+            ParseGrammar::Functional => write!(f, "color function"),
+        }
+    }
+}
+
+/// What went wrong when parsing a color.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// Characters appeared that can't be used where they are, such as `'g'` in a hex code or
+    /// `"px"` after a number.
+    InvalidCharacters,
+    /// Something that should be a number isn't well-formed, such as `"1.2.3"` or `"50%%"`.
+    InvalidNumber,
+    /// A well-formed value of the wrong type, such as a percentage for a hue or an angle for
+    /// lightness.
+    WrongValueType,
+    /// The structure of the color is wrong: a missing parenthesis, the wrong number of
+    /// components or digits, mixing commas and spaces, and so on.
+    InvalidSyntax,
+    /// A color name, function name, or color space that isn't recognized.
+    UnknownName,
+}
+
+impl ParseErrorKind {
+    /// A short description of this kind of error.
+    fn description(self) -> &'static str {
+        match self {
+            ParseErrorKind::InvalidCharacters => "unexpected characters",
+            ParseErrorKind::InvalidNumber => "invalid number",
+            ParseErrorKind::WrongValueType => "wrong type of value",
+            ParseErrorKind::InvalidSyntax => "invalid syntax",
+            ParseErrorKind::UnknownName => "unknown name",
+        }
+    }
+}
+
+/// An error from parsing a string as a color.
+/// # Example
+/// Errors know where they happened, which is useful for reporting them.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::parseerror::{ParseErrorKind, ParseGrammar};
+/// let err = "rgb(255, 128, 0".parse::<RGBColor>().unwrap_err();
+/// assert_eq!(err.kind, ParseErrorKind::InvalidSyntax);
+/// assert_eq!(err.grammar, ParseGrammar::Functional);
+/// assert_eq!(err.span, 15..15);
+/// assert_eq!(err.expected, Some("')'"));
+/// assert_eq!(err.to_string(), "invalid color function at column 16: expected ')'");
+/// ```
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ColorParseError {
+    /// What went wrong.
+    pub kind: ParseErrorKind,
+    /// The syntax the parser was trying to read.
+    pub grammar: ParseGrammar,
+    /// The range of bytes in the input that caused the error. If something is missing, this is
+    /// empty and starts where it should have been.
+    pub span: Range<usize>,
+    /// A description of what the parser expected to find at the start of `span`, such as `"')'"`
+    /// or `"a percentage"`, if there's something specific.
+    pub expected: Option<&'static str>,
+}
+
+impl ColorParseError {
+    /// Makes a new error without an expected token.
+    pub(crate) fn new(
+        kind: ParseErrorKind,
+        grammar: ParseGrammar,
+        span: Range<usize>,
+    ) -> ColorParseError {
+        ColorParseError {
+            kind,
+            grammar,
+            span,
+            expected: None,
+        }
+    }
+    /// Sets what the parser expected to find.
+    pub(crate) fn expecting(mut self, expected: &'static str) -> ColorParseError {
+        self.expected = Some(expected);
+        self
+    }
+    /// Shifts the span by the given number of bytes. Parsers report spans relative to the string
+    /// they were given, so this is used to make them relative to a larger string.
+    pub(crate) fn offset(mut self, by: usize) -> ColorParseError {
+        self.span = self.span.start + by..self.span.end + by;
+        self
+    }
+    /// The column the error starts at, counting from 1. This counts bytes, which is the same as
+    /// counting characters for the ASCII text that colors are written in.
+    pub fn column(&self) -> usize {
+        self.span.start + 1
+    }
+}
+
+/// Gets the range of bytes that `inner`, a slice of `outer`, occupies in `outer`. This lets parsers
+/// that split and trim their input find out where each piece came from.
+pub(crate) fn span_of(outer: &str, inner: &str) -> Range<usize> {
+    let start = inner.as_ptr() as usize - outer.as_ptr() as usize;
+    debug_assert!(start + inner.len() <= outer.len());
+    start..start + inner.len()
+}
+
+impl fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.expected {
+            Some(expected) => write!(
+                f,
+                "invalid {} at column {}: expected {}",
+                self.grammar,
+                self.column(),
+                expected
+            ),
+            None => write!(
+                f,
+                "invalid {} at column {}: {}",
+                self.grammar,
+                self.column(),
+                self.kind.description()
+            ),
+        }
+    }
+}
+
+impl Error for ColorParseError {
+    fn description(&self) -> &str {
+        self.kind.description()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_guess_grammar() {
+        assert_eq!(ParseGrammar::guess("#12345"), ParseGrammar::Hex);
+        assert_eq!(ParseGrammar::guess("abc"), ParseGrammar::Hex);
+        assert_eq!(ParseGrammar::guess("  red "), ParseGrammar::Name);
+        assert_eq!(ParseGrammar::guess(""), ParseGrammar::Name);
+        assert_eq!(ParseGrammar::guess("rgb(1, 2, 3"), ParseGrammar::Functional);
+    }
+    #[test]
+    fn test_span_of() {
+        let outer = "rgb( 1, 2 ,3)";
+        let parts: Vec<&str> = outer[4..12].split(',').map(|part| part.trim()).collect();
+        assert_eq!(span_of(outer, parts[0]), 5..6);
+        assert_eq!(span_of(outer, parts[1]), 8..9);
+        assert_eq!(span_of(outer, parts[2]), 11..12);
+    }
+    #[test]
+    fn test_display() {
+        let err = ColorParseError::new(ParseErrorKind::UnknownName, ParseGrammar::Name, 0..4);
+        assert_eq!(
+            err.to_string(),
+            "invalid color name at column 1: unknown name"
+        );
+        let err = ColorParseError::new(ParseErrorKind::InvalidSyntax, ParseGrammar::Hex, 3..3)
+            .expecting("a hex digit")
+            .offset(2);
+        assert_eq!(err.span, 5..5);
+        assert_eq!(
+            err.to_string(),
+            "invalid hex code at column 6: expected a hex digit"
+        );
+    }
+}
@@ -0,0 +1,361 @@
+//! This module implements subtractive color mixing, the way paint mixes, with Kubelka-Munk theory.
+//! Mixing colors as points, like [`ColorPoint::weighted_average`] does, is closer to mixing light:
+//! blue and yellow average out to a dull olive gray. Paint doesn't work that way. Each pigment
+//! absorbs some wavelengths and scatters the rest back out, and a mixture absorbs what any of its
+//! pigments absorbs, so blue and yellow paint give green: the only light both reflect.
+//!
+//! Kubelka-Munk theory describes a pigment by its absorption coefficient *K* and scattering
+//! coefficient *S* at each wavelength. In a mixture, these add up weighted by concentration, and
+//! the reflectance of a layer too thick to see through only depends on their ratio *K/S*. A
+//! [`Pigment`] can be given both, which is the *two-constant* theory and needs them to be measured,
+//! or only the reflectance of the pure pigment, in which case every pigment is assumed to scatter
+//! the same. That's the *single-constant* theory, which works well for mixing colors without
+//! measurements, and is what [`mix_pigments`] uses.
+//!
+//! [`ColorPoint::weighted_average`]: ../colorpoint/trait.ColorPoint.html#method.weighted_average
+//! [`Pigment`]: struct.Pigment.html
+//! [`mix_pigments`]: fn.mix_pigments.html
+
+use color::{Color, RGBColor, XYZColor};
+use illuminants::Illuminant;
+use spectrum::{Spectrum, SpectrumError};
+
+use std::error::Error;
+use std::fmt;
+
+/// The least reflectance a pigment is taken to have, so that its ratio of absorption to scattering
+/// is finite. Without this, a pure black would turn anything it's mixed with completely black.
+const MIN_REFLECTANCE: f64 = 0.001;
+
+/// The errors that can happen when mixing pigments.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PigmentError {
+    /// Returned when there are no pigments to mix.
+    NoPigments,
+    /// Returned when a concentration is negative or not finite, or when they're all 0.
+    InvalidConcentration,
+    /// Returned when a color has no reflectance spectrum, because it's outside of sRGB.
+    OutOfGamut,
+}
+
+impl PigmentError {
+    /// A short description of this error.
+    fn description(self) -> &'static str {
+        match self {
+            PigmentError::NoPigments => "no pigments to mix",
+            PigmentError::InvalidConcentration => "concentrations are not valid",
+            PigmentError::OutOfGamut => "color is not in sRGB",
+        }
+    }
+}
+
+impl fmt::Display for PigmentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", PigmentError::description(*self))
+    }
+}
+
+impl Error for PigmentError {
+    fn description(&self) -> &str {
+        PigmentError::description(*self)
+    }
+}
+
+/// Gets the wavelengths, every 5 nm from 360 nm to 830 nm, at which pigments are mixed.
+fn wavelengths() -> impl Iterator<Item = f64> {
+    (0..95).map(|i| 360.0 + 5.0 * f64::from(i))
+}
+
+/// Gets the reflectance of an opaque layer with the given ratio of absorption to scattering. This
+/// is `1 + K/S - sqrt((K/S)^2 + 2K/S)`, rearranged so that it doesn't lose precision for dark
+/// colors.
+fn reflectance(k_over_s: f64) -> f64 {
+    1.0 / (1.0 + k_over_s + (k_over_s * k_over_s + 2.0 * k_over_s).sqrt())
+}
+
+/// A pigment, described by how much it absorbs and scatters light at each wavelength, following
+/// Kubelka-Munk theory. Only the ratio of the two matters for the pigment by itself, but their
+/// magnitudes determine how strongly it tints a mixture.
+/// # Example
+/// Two-constant mixing, with made-up measurements: a little of a strongly absorbing blue darkens a
+/// weakly absorbing white a lot.
+///
+/// ```
+/// # use scarlet::pigment::Pigment;
+/// # use scarlet::spectrum::Spectrum;
+/// let white = Pigment::new(
+///     Spectrum::uniform(400., 300., vec![0.01, 0.01]).unwrap(),
+///     Spectrum::uniform(400., 300., vec![1., 1.]).unwrap(),
+/// );
+/// let blue = Pigment::new(
+///     Spectrum::uniform(400., 150., vec![0.1, 0.1, 30.]).unwrap(),
+///     Spectrum::uniform(400., 300., vec![0.2, 0.2]).unwrap(),
+/// );
+/// let tint = Pigment::mix(&[(&white, 0.9), (&blue, 0.1)]).unwrap().reflectance();
+/// assert!(tint.value_at(450.) > 0.5);
+/// assert!(tint.value_at(650.) < 0.2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pigment {
+    absorption: Spectrum,
+    scattering: Spectrum,
+}
+
+impl Pigment {
+    /// Makes a pigment from its absorption and scattering coefficients at each wavelength, as
+    /// measured from samples of it. This is the two-constant theory. The units don't matter, as
+    /// long as every pigment mixed together uses the same ones.
+    pub fn new(absorption: Spectrum, scattering: Spectrum) -> Pigment {
+        Pigment {
+            absorption,
+            scattering,
+        }
+    }
+    /// Makes a pigment from the reflectance of a thick layer of it, with a scattering coefficient
+    /// of 1 at every wavelength. This is the single-constant theory: it assumes every pigment
+    /// scatters the same, so it works best when they're similar, such as paints of the same kind.
+    /// Reflectances less than 0.001 are taken to be 0.001, and more than 1 to be 1.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::pigment::Pigment;
+    /// # use scarlet::spectrum::Spectrum;
+    /// let gray = Spectrum::uniform(400., 300., vec![0.5, 0.5]).unwrap();
+    /// let pigment = Pigment::from_reflectance(&gray);
+    /// assert!((pigment.reflectance().value_at(550.) - 0.5).abs() <= 1e-10);
+    /// ```
+    pub fn from_reflectance(reflectance: &Spectrum) -> Pigment {
+        let k_over_s = wavelengths()
+            .map(|wl| {
+                let r = reflectance.value_at(wl).clamp(MIN_REFLECTANCE, 1.0);
+                (1.0 - r) * (1.0 - r) / (2.0 * r)
+            })
+            .collect();
+        Pigment {
+            absorption: Spectrum::uniform(360.0, 5.0, k_over_s).expect("Wavelengths are valid."),
+            scattering: Spectrum::uniform(360.0, 5.0, vec![1.0; 95])
+                .expect("Wavelengths are valid."),
+        }
+    }
+    /// Makes a single-constant pigment that has the given color under D65, using the reflectance
+    /// [`Spectrum::from_rgb`] gives.
+    ///
+    /// [`Spectrum::from_rgb`]: ../spectrum/struct.Spectrum.html#method.from_rgb
+    /// # Errors
+    /// Returns `PigmentError::OutOfGamut` if the color isn't in sRGB, allowing for rounding error.
+    pub fn from_color<T: Color>(color: &T) -> Result<Pigment, PigmentError> {
+        let rgb: RGBColor = color.convert();
+        // conversions between white points can land a hair outside of sRGB, which isn't worth
+        // failing over
+        let nudge = |c: f64| {
+            if c > -1e-3 && c < 1.0 + 1e-3 {
+                c.clamp(0.0, 1.0)
+            } else {
+                c
+            }
+        };
+        let rgb = RGBColor {
+            r: nudge(rgb.r),
+            g: nudge(rgb.g),
+            b: nudge(rgb.b),
+        };
+        match Spectrum::from_rgb(rgb) {
+            Ok(spectrum) => Ok(Pigment::from_reflectance(&spectrum)),
+            Err(SpectrumError::OutOfGamut) => Err(PigmentError::OutOfGamut),
+            Err(_) => unreachable!("Only out-of-gamut colors have no spectrum."),
+        }
+    }
+    /// Gets the absorption coefficient of the pigment at each wavelength.
+    pub fn absorption(&self) -> &Spectrum {
+        &self.absorption
+    }
+    /// Gets the scattering coefficient of the pigment at each wavelength.
+    pub fn scattering(&self) -> &Spectrum {
+        &self.scattering
+    }
+    /// Gets the reflectance of a layer of the pigment thick enough to hide what's underneath it,
+    /// every 5 nm from 360 nm to 830 nm.
+    pub fn reflectance(&self) -> Spectrum {
+        let values = wavelengths()
+            .map(|wl| {
+                let (k, s) = (self.absorption.value_at(wl), self.scattering.value_at(wl));
+                if k <= 0.0 {
+                    1.0
+                } else if s <= 0.0 {
+                    0.0
+                } else {
+                    reflectance(k / s)
+                }
+            })
+            .collect();
+        Spectrum::uniform(360.0, 5.0, values).expect("Wavelengths are valid.")
+    }
+    /// Mixes pigments in the given concentrations, which are normalized to sum to 1. The absorption
+    /// and scattering of the mixture are the averages of those of the pigments, weighted by
+    /// concentration, every 5 nm from 360 nm to 830 nm.
+    /// # Errors
+    /// Returns `PigmentError::NoPigments` if there's nothing to mix, and
+    /// `PigmentError::InvalidConcentration` if a concentration is negative or not finite or they're
+    /// all 0.
+    pub fn mix(pigments: &[(&Pigment, f64)]) -> Result<Pigment, PigmentError> {
+        if pigments.is_empty() {
+            return Err(PigmentError::NoPigments);
+        }
+        let total: f64 = pigments.iter().map(|&(_, c)| c).sum();
+        if pigments.iter().any(|&(_, c)| !c.is_finite() || c < 0.0) || total <= 0.0 {
+            return Err(PigmentError::InvalidConcentration);
+        }
+        let average = |coefficient: fn(&Pigment) -> &Spectrum| {
+            let values = wavelengths()
+                .map(|wl| {
+                    pigments
+                        .iter()
+                        .map(|&(pigment, c)| c * coefficient(pigment).value_at(wl))
+                        .sum::<f64>()
+                        / total
+                })
+                .collect();
+            Spectrum::uniform(360.0, 5.0, values).expect("Wavelengths are valid.")
+        };
+        Ok(Pigment {
+            absorption: average(Pigment::absorption),
+            scattering: average(Pigment::scattering),
+        })
+    }
+}
+
+/// Mixes colors as if they were paints, with the single-constant Kubelka-Munk theory. Each color
+/// is made into a [`Pigment`] with the reflectance [`Spectrum::from_rgb`] gives, the pigments are
+/// mixed in the given concentrations, which are normalized to sum to 1, and the result is the
+/// color of the mixture under D65.
+///
+/// Because it's based on spectra, this gets the mixtures painters expect: blue and yellow make
+/// green, and a little black goes a long way. How the colors were made affects the result in real
+/// life, but not here: two paints of the same color always mix the same.
+///
+/// [`Pigment`]: struct.Pigment.html
+/// [`Spectrum::from_rgb`]: ../spectrum/struct.Spectrum.html#method.from_rgb
+/// # Errors
+/// Returns `PigmentError::NoPigments` if there's nothing to mix,
+/// `PigmentError::InvalidConcentration` if a concentration is negative or not finite or they're all
+/// 0, and `PigmentError::OutOfGamut` if a color isn't in sRGB.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::pigment::mix_pigments;
+/// let blue = RGBColor::from_hex_code("#0040c0").unwrap();
+/// let yellow = RGBColor::from_hex_code("#f0e000").unwrap();
+/// let green = mix_pigments(&[(blue, 1.), (yellow, 1.)]).unwrap();
+/// assert!(green.g > green.r && green.g > green.b);
+/// // averaging them as points gives a duller olive, with much more red
+/// let olive = blue.weighted_average(vec![yellow], vec![0.5, 0.5]).unwrap();
+/// assert!(olive.r > green.r + 0.3);
+/// ```
+pub fn mix_pigments<T: Color>(pigments: &[(T, f64)]) -> Result<T, PigmentError> {
+    let made = pigments
+        .iter()
+        .map(|&(ref color, c)| Pigment::from_color(color).map(|p| (p, c)))
+        .collect::<Result<Vec<(Pigment, f64)>, PigmentError>>()?;
+    let refs: Vec<(&Pigment, f64)> = made.iter().map(|&(ref p, c)| (p, c)).collect();
+    let xyz: XYZColor = Pigment::mix(&refs)?
+        .reflectance()
+        .to_xyz(Illuminant::D65)
+        .expect("D65 has a spectrum.");
+    Ok(T::from_xyz(xyz))
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use colors::cielabcolor::CIELABColor;
+    use colors::cielchcolor::CIELCHColor;
+
+    #[test]
+    fn test_single_pigment() {
+        // a pigment by itself keeps its color
+        for hex in ["#e08030", "#2050a0", "#808080", "#ffffff"].iter() {
+            let color = RGBColor::from_hex_code(hex).unwrap();
+            let mixed = mix_pigments(&[(color, 3.0)]).unwrap();
+            assert!(color.distance(&mixed) <= 0.02, "{}", hex);
+            assert_eq!(mix_pigments(&[(color, 1.0), (color, 2.0)]).unwrap(), mixed);
+        }
+    }
+    #[test]
+    fn test_mix_pigments() {
+        let blue = RGBColor::from_hex_code("#0040c0").unwrap();
+        let yellow = RGBColor::from_hex_code("#f0e000").unwrap();
+        let green = mix_pigments(&[(blue, 1.0), (yellow, 1.0)]).unwrap();
+        let hue = green.convert::<CIELCHColor>().h;
+        assert!(hue > 120.0 && hue < 200.0, "{}", hue);
+        // mixing with white lightens without changing the hue much
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let red = RGBColor::from_hex_code("#c02020").unwrap();
+        let pink = mix_pigments(&[(red, 1.0), (white, 1.0)]).unwrap();
+        assert!(pink.r > red.r && pink.g > red.g && pink.r > pink.g);
+        // a little black darkens a lot
+        let black = RGBColor::from_hex_code("#000000").unwrap();
+        let shade = mix_pigments(&[(white, 0.9), (black, 0.1)]).unwrap();
+        assert!(shade.r < 0.5);
+        // other color types work too
+        let lab_white = CIELABColor {
+            l: 100.0,
+            a: 0.0,
+            b: 0.0,
+        };
+        assert!(mix_pigments(&[(lab_white, 1.0)]).is_ok());
+    }
+    #[test]
+    fn test_errors() {
+        let red = RGBColor::from_hex_code("#c02020").unwrap();
+        let empty: [(RGBColor, f64); 0] = [];
+        assert_eq!(mix_pigments(&empty), Err(PigmentError::NoPigments));
+        assert_eq!(
+            mix_pigments(&[(red, 0.0), (red, 0.0)]),
+            Err(PigmentError::InvalidConcentration)
+        );
+        assert_eq!(
+            mix_pigments(&[(red, 1.0), (red, -0.5)]),
+            Err(PigmentError::InvalidConcentration)
+        );
+        assert_eq!(
+            mix_pigments(&[(red, f64::NAN)]),
+            Err(PigmentError::InvalidConcentration)
+        );
+        let bright = RGBColor {
+            r: 1.5,
+            g: 0.0,
+            b: 0.0,
+        };
+        assert_eq!(
+            mix_pigments(&[(bright, 1.0)]),
+            Err(PigmentError::OutOfGamut)
+        );
+    }
+    #[test]
+    fn test_two_constant() {
+        // with no scattering, only the absorption matters
+        let clear = Pigment::new(
+            Spectrum::new(vec![500.0], vec![0.0]).unwrap(),
+            Spectrum::new(vec![500.0], vec![0.0]).unwrap(),
+        );
+        assert!(clear.reflectance().values().iter().all(|&r| r == 1.0));
+        let dark = Pigment::new(
+            Spectrum::new(vec![500.0], vec![1.0]).unwrap(),
+            Spectrum::new(vec![500.0], vec![0.0]).unwrap(),
+        );
+        assert!(dark.reflectance().values().iter().all(|&r| r == 0.0));
+        // K/S of 0.5 gives a reflectance of 1 + 0.5 - sqrt(0.25 + 1)
+        let gray = Pigment::new(
+            Spectrum::new(vec![500.0], vec![1.0]).unwrap(),
+            Spectrum::new(vec![500.0], vec![2.0]).unwrap(),
+        );
+        let expected = 1.5 - 1.25_f64.sqrt();
+        assert!((gray.reflectance().value_at(600.0) - expected).abs() <= 1e-12);
+        // absorption and scattering are averaged separately
+        let mixed = Pigment::mix(&[(&gray, 1.0), (&dark, 3.0)]).unwrap();
+        assert!((mixed.absorption().value_at(500.0) - 1.0).abs() <= 1e-12);
+        assert!((mixed.scattering().value_at(500.0) - 0.5).abs() <= 1e-12);
+    }
+}
@@ -0,0 +1,474 @@
+//! This module provides a way of describing any RGB color space from the handful of numbers that
+//! its specification gives: the chromaticities of its three primaries, its white point, and the
+//! transfer function (often loosely called "gamma") that maps linear light to encoded values. From
+//! these, Scarlet derives the matrices that convert to and from CIE XYZ, so new RGB spaces don't need
+//! any hardcoded constants. To use a color space as a [`Color`], implement [`RGBSpace`] for a marker
+//! type and use it as the parameter of
+//! [`CustomRGBColor`](../colors/customrgbcolor/struct.CustomRGBColor.html).
+//!
+//! [`Color`]: ../color/trait.Color.html
+//! [`RGBSpace`]: trait.RGBSpace.html
+
+use color::{Color, XYZColor};
+use illuminants::Illuminant;
+use rulinalg::matrix::decomposition::PartialPivLu;
+use std::fmt;
+
+/// A transfer function, mapping linear light intensities to the encoded values an RGB color space
+/// actually stores and back again. Each one is defined on the range 0 to 1 and extended to negative
+/// values by symmetry, so that out-of-gamut colors survive a round trip instead of being clipped.
+#[derive(Copy, Clone)]
+pub enum TransferFunction {
+    /// No transfer function at all: encoded values are linear light.
+    Linear,
+    /// A pure power law, encoding `x` as `x^(1/γ)`. Adobe RGB uses a gamma of 563/256, or about 2.2,
+    /// and DCI-P3 uses 2.6.
+    Gamma(f64),
+    /// The sRGB transfer function: a linear segment near black and a power law of 1/2.4 above it.
+    /// Display P3 uses this too.
+    SRGB,
+    /// The transfer function from ITU-R BT.2020, which is the same as BT.709's but with more precise
+    /// constants: a linear segment near black and a power law of 0.45 above it.
+    Rec2020,
+    /// The transfer function of ROMM (ProPhoto) RGB: a linear segment near black and a power law of
+    /// 1/1.8 above it.
+    ProPhoto,
+    /// Any other transfer function, given as a pair of functions. `encode` maps linear values to
+    /// encoded values, and `decode` must be its inverse. Both only need to handle values from 0 to 1:
+    /// negative values are handled by symmetry like every other transfer function.
+    Custom {
+        /// The function from linear light to encoded values.
+        encode: fn(f64) -> f64,
+        /// The function from encoded values to linear light.
+        decode: fn(f64) -> f64,
+    },
+}
+
+impl fmt::Debug for TransferFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TransferFunction::Linear => write!(f, "Linear"),
+            TransferFunction::Gamma(gamma) => write!(f, "Gamma({:?})", gamma),
+            TransferFunction::SRGB => write!(f, "SRGB"),
+            TransferFunction::Rec2020 => write!(f, "Rec2020"),
+            TransferFunction::ProPhoto => write!(f, "ProPhoto"),
+            TransferFunction::Custom { .. } => write!(f, "Custom"),
+        }
+    }
+}
+
+// the exact constants from BT.2020, chosen so that both parts of the curve meet smoothly
+const REC2020_ALPHA: f64 = 1.09929682680944;
+const REC2020_BETA: f64 = 0.018053968510807;
+
+impl TransferFunction {
+    /// Encodes a linear light value, as a color space's components are stored.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::rgbcolorspace::TransferFunction;
+    /// assert_eq!(TransferFunction::Linear.encode(0.5), 0.5);
+    /// assert!((TransferFunction::Gamma(2.0).encode(0.25) - 0.5).abs() <= 1e-15);
+    /// // middle gray in sRGB is much less than half as bright as white
+    /// assert!((TransferFunction::SRGB.encode(0.214041) - 0.5).abs() <= 1e-6);
+    /// ```
+    pub fn encode(&self, linear: f64) -> f64 {
+        let x = linear.abs();
+        let encoded = match *self {
+            TransferFunction::Linear => x,
+            TransferFunction::Gamma(gamma) => x.powf(1.0 / gamma),
+            TransferFunction::SRGB => {
+                if x <= 0.0031308 {
+                    12.92 * x
+                } else {
+                    1.055 * x.powf(1.0 / 2.4) - 0.055
+                }
+            }
+            TransferFunction::Rec2020 => {
+                if x < REC2020_BETA {
+                    4.5 * x
+                } else {
+                    REC2020_ALPHA * x.powf(0.45) - (REC2020_ALPHA - 1.0)
+                }
+            }
+            TransferFunction::ProPhoto => {
+                if x < 1.0 / 512.0 {
+                    16.0 * x
+                } else {
+                    x.powf(1.0 / 1.8)
+                }
+            }
+            TransferFunction::Custom { encode, .. } => encode(x),
+        };
+        encoded.copysign(linear)
+    }
+    /// Decodes a stored component value into linear light. This is the inverse of
+    /// [`encode`](#method.encode).
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::rgbcolorspace::TransferFunction;
+    /// let tf = TransferFunction::Rec2020;
+    /// for &x in [-0.5, 0.0, 0.01, 0.3, 1.0].iter() {
+    ///     assert!((tf.decode(tf.encode(x)) - x).abs() <= 1e-15);
+    /// }
+    /// ```
+    pub fn decode(&self, encoded: f64) -> f64 {
+        let x = encoded.abs();
+        let linear = match *self {
+            TransferFunction::Linear => x,
+            TransferFunction::Gamma(gamma) => x.powf(gamma),
+            TransferFunction::SRGB => {
+                if x <= 0.04045 {
+                    x / 12.92
+                } else {
+                    ((x + 0.055) / 1.055).powf(2.4)
+                }
+            }
+            TransferFunction::Rec2020 => {
+                if x < 4.5 * REC2020_BETA {
+                    x / 4.5
+                } else {
+                    ((x + REC2020_ALPHA - 1.0) / REC2020_ALPHA).powf(1.0 / 0.45)
+                }
+            }
+            TransferFunction::ProPhoto => {
+                if x < 16.0 / 512.0 {
+                    x / 16.0
+                } else {
+                    x.powf(1.8)
+                }
+            }
+            TransferFunction::Custom { decode, .. } => decode(x),
+        };
+        linear.copysign(encoded)
+    }
+}
+
+/// A description of an RGB color space: the CIE 1931 xy chromaticities of its red, green, and blue
+/// primaries, the illuminant that `(1, 1, 1)` maps to, and the transfer function between linear
+/// light and the encoded components. This is all that's needed to convert to and from XYZ.
+/// # Example
+/// DCI-P3, the color space used in digital cinema, has a greenish white point that isn't one of the
+/// CIE illuminants, so it's given by its XYZ coordinates.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::rgbcolorspace::{RGBColorSpace, TransferFunction};
+/// let dci_p3 = RGBColorSpace {
+///     red: [0.680, 0.320],
+///     green: [0.265, 0.690],
+///     blue: [0.150, 0.060],
+///     white: Illuminant::Custom([0.314 / 0.351, 1.0, (1.0 - 0.314 - 0.351) / 0.351]),
+///     transfer: TransferFunction::Gamma(2.6),
+/// };
+/// // the second row of the matrix holds how much light each primary contributes: together, they
+/// // give white
+/// let m = dci_p3.rgb_to_xyz_matrix();
+/// assert!((m[1][0] + m[1][1] + m[1][2] - 1.0).abs() <= 1e-12);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct RGBColorSpace {
+    /// The xy chromaticity of the red primary.
+    pub red: [f64; 2],
+    /// The xy chromaticity of the green primary.
+    pub green: [f64; 2],
+    /// The xy chromaticity of the blue primary.
+    pub blue: [f64; 2],
+    /// The white point of the space, the color with all three components at their maximum.
+    pub white: Illuminant,
+    /// The transfer function that maps linear light to encoded components.
+    pub transfer: TransferFunction,
+}
+
+impl RGBColorSpace {
+    /// The sRGB color space, as used by [`RGBColor`](../color/struct.RGBColor.html). The matrix this
+    /// derives differs very slightly from `RGBColor`'s, which uses the rounded values in the sRGB
+    /// specification.
+    pub const SRGB: RGBColorSpace = RGBColorSpace {
+        red: [0.64, 0.33],
+        green: [0.30, 0.60],
+        blue: [0.15, 0.06],
+        white: Illuminant::D65,
+        transfer: TransferFunction::SRGB,
+    };
+    /// The Adobe RGB (1998) color space, as used by
+    /// [`AdobeRGBColor`](../colors/adobergbcolor/struct.AdobeRGBColor.html).
+    pub const ADOBE_RGB: RGBColorSpace = RGBColorSpace {
+        red: [0.64, 0.33],
+        green: [0.21, 0.71],
+        blue: [0.15, 0.06],
+        white: Illuminant::D65,
+        transfer: TransferFunction::Gamma(563.0 / 256.0),
+    };
+    /// The ROMM RGB (ProPhoto) color space, as used by
+    /// [`ROMMRGBColor`](../colors/rommrgbcolor/struct.ROMMRGBColor.html), but without the flare
+    /// correction that type applies.
+    pub const PROPHOTO_RGB: RGBColorSpace = RGBColorSpace {
+        red: [0.7347, 0.2653],
+        green: [0.1596, 0.8404],
+        blue: [0.0366, 0.0001],
+        white: Illuminant::D50,
+        transfer: TransferFunction::ProPhoto,
+    };
+
+    /// The Display P3 color space, as used by
+    /// [`DisplayP3Color`](../colors/displayp3color/struct.DisplayP3Color.html): the primaries of
+    /// DCI-P3 with the white point and transfer function of sRGB.
+    pub const DISPLAY_P3: RGBColorSpace = RGBColorSpace {
+        red: [0.680, 0.320],
+        green: [0.265, 0.690],
+        blue: [0.150, 0.060],
+        white: Illuminant::D65,
+        transfer: TransferFunction::SRGB,
+    };
+    /// The ITU-R BT.2020 color space, as used by
+    /// [`Rec2020Color`](../colors/rec2020color/struct.Rec2020Color.html).
+    pub const REC2020: RGBColorSpace = RGBColorSpace {
+        red: [0.708, 0.292],
+        green: [0.170, 0.797],
+        blue: [0.131, 0.046],
+        white: Illuminant::D65,
+        transfer: TransferFunction::Rec2020,
+    };
+
+    /// Gets the matrix that converts linear RGB components in this space to XYZ coordinates relative
+    /// to its white point, as an array of rows. Each column is the XYZ value of a primary, scaled so
+    /// that the three of them add up to the white point.
+    pub fn rgb_to_xyz_matrix(&self) -> [[f64; 3]; 3] {
+        self.matrices().to_xyz
+    }
+    /// Gets the matrix that converts XYZ coordinates relative to this space's white point to linear
+    /// RGB components, as an array of rows. This is the inverse of
+    /// [`rgb_to_xyz_matrix`](#method.rgb_to_xyz_matrix).
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::rgbcolorspace::RGBColorSpace;
+    /// let m = RGBColorSpace::SRGB.xyz_to_rgb_matrix();
+    /// // matches the matrix in the sRGB specification to its precision
+    /// assert!((m[0][0] - 3.2406).abs() <= 1e-3);
+    /// assert!((m[1][1] - 1.8758).abs() <= 1e-3);
+    /// assert!((m[2][2] - 1.0570).abs() <= 1e-3);
+    /// ```
+    pub fn xyz_to_rgb_matrix(&self) -> [[f64; 3]; 3] {
+        self.matrices().from_xyz
+    }
+    /// Gets the conversion matrices of this space. The built-in spaces have theirs computed once,
+    /// and any other space has them derived from its primaries each time.
+    fn matrices(&self) -> SpaceMatrices {
+        let key = (self.red, self.green, self.blue, self.white);
+        BUILT_IN_MATRICES
+            .iter()
+            .find(|&&(built_in, _)| built_in == key)
+            .map(|&(_, matrices)| matrices)
+            .unwrap_or_else(|| SpaceMatrices::derive(self))
+    }
+    /// Converts an XYZ color to the encoded RGB components in this space, returned as `[r, g, b]`.
+    /// The color is chromatically adapted to the space's white point first. Components are not
+    /// clipped, so colors outside the gamut have components outside the range 0 to 1.
+    pub fn xyz_to_rgb(&self, xyz: XYZColor) -> [f64; 3] {
+        let xyz_c = xyz.color_adapt(self.white);
+        let m = self.matrices().from_xyz;
+        let linear = |row: [f64; 3]| row[0] * xyz_c.x + row[1] * xyz_c.y + row[2] * xyz_c.z;
+        [
+            self.transfer.encode(linear(m[0])),
+            self.transfer.encode(linear(m[1])),
+            self.transfer.encode(linear(m[2])),
+        ]
+    }
+    /// Returns whether the given color is in the gamut of this space: whether its components here
+    /// are all between 0 and 1, or outside of that by at most `tolerance`. This is like
+    /// [`Bound::in_gamut`], but for spaces only known at runtime.
+    ///
+    /// [`Bound::in_gamut`]: ../bound/trait.Bound.html#method.in_gamut
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::CIELABColor;
+    /// # use scarlet::rgbcolorspace::RGBColorSpace;
+    /// let teal = CIELABColor{l: 60., a: -60., b: -10.};
+    /// assert!(!RGBColorSpace::SRGB.in_gamut(&teal, 1e-6));
+    /// assert!(RGBColorSpace::REC2020.in_gamut(&teal, 1e-6));
+    /// ```
+    pub fn in_gamut<T: Color>(&self, color: &T, tolerance: f64) -> bool {
+        self.xyz_to_rgb(color.to_xyz(self.white))
+            .iter()
+            .all(|&c| c >= -tolerance && c <= 1.0 + tolerance)
+    }
+    /// Converts encoded RGB components `[r, g, b]` in this space to an XYZ color, chromatically
+    /// adapted from the space's white point to the given illuminant.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::XYZColor;
+    /// # use scarlet::rgbcolorspace::RGBColorSpace;
+    /// let space = RGBColorSpace::REC2020;
+    /// let xyz = space.rgb_to_xyz([0.2, 0.5, 0.9], Illuminant::D50);
+    /// let rgb = space.xyz_to_rgb(xyz);
+    /// assert!((rgb[0] - 0.2).abs() <= 1e-10);
+    /// assert!((rgb[1] - 0.5).abs() <= 1e-10);
+    /// assert!((rgb[2] - 0.9).abs() <= 1e-10);
+    /// ```
+    pub fn rgb_to_xyz(&self, rgb: [f64; 3], illuminant: Illuminant) -> XYZColor {
+        let m = self.matrices().to_xyz;
+        let r = self.transfer.decode(rgb[0]);
+        let g = self.transfer.decode(rgb[1]);
+        let b = self.transfer.decode(rgb[2]);
+        let xyz = |row: [f64; 3]| row[0] * r + row[1] * g + row[2] * b;
+        XYZColor {
+            x: xyz(m[0]),
+            y: xyz(m[1]),
+            z: xyz(m[2]),
+            illuminant: self.white,
+        }
+        .color_adapt(illuminant)
+    }
+}
+
+/// The matrices that convert between linear RGB components in a space and XYZ coordinates relative
+/// to its white point, as arrays of rows.
+#[derive(Debug, Copy, Clone)]
+struct SpaceMatrices {
+    to_xyz: [[f64; 3]; 3],
+    from_xyz: [[f64; 3]; 3],
+}
+
+impl SpaceMatrices {
+    /// Derives the matrices of a space from its primaries and white point.
+    fn derive(space: &RGBColorSpace) -> SpaceMatrices {
+        // the XYZ value with Y = 1 that has a given chromaticity
+        let to_xyz = |xy: [f64; 2]| [xy[0] / xy[1], 1.0, (1.0 - xy[0] - xy[1]) / xy[1]];
+        let (r, g, b) = (to_xyz(space.red), to_xyz(space.green), to_xyz(space.blue));
+        let primaries = matrix![r[0], g[0], b[0];
+                                r[1], g[1], b[1];
+                                r[2], g[2], b[2]];
+        // now find how much of each primary adds up to white, and scale the primaries by that
+        let white = space.white.white_point();
+        let scale = PartialPivLu::decompose(primaries)
+            .expect("Primaries are linearly independent.")
+            .solve(vector![white[0], white[1], white[2]])
+            .expect("Primaries are linearly independent.");
+        let m = [
+            [r[0] * scale[0], g[0] * scale[1], b[0] * scale[2]],
+            [r[1] * scale[0], g[1] * scale[1], b[1] * scale[2]],
+            [r[2] * scale[0], g[2] * scale[1], b[2] * scale[2]],
+        ];
+        let inv = matrix![m[0][0], m[0][1], m[0][2];
+                          m[1][0], m[1][1], m[1][2];
+                          m[2][0], m[2][1], m[2][2]]
+        .inverse()
+        .expect("Primaries are linearly independent.");
+        SpaceMatrices {
+            to_xyz: m,
+            from_xyz: [
+                [inv[[0, 0]], inv[[0, 1]], inv[[0, 2]]],
+                [inv[[1, 0]], inv[[1, 1]], inv[[1, 2]]],
+                [inv[[2, 0]], inv[[2, 1]], inv[[2, 2]]],
+            ],
+        }
+    }
+}
+
+/// The primaries and white point that determine a space's matrices.
+type SpaceKey = ([f64; 2], [f64; 2], [f64; 2], Illuminant);
+
+lazy_static! {
+    // every conversion to or from one of the built-in spaces uses these, so only derive them once
+    static ref BUILT_IN_MATRICES: Vec<(SpaceKey, SpaceMatrices)> = [
+        RGBColorSpace::SRGB,
+        RGBColorSpace::ADOBE_RGB,
+        RGBColorSpace::PROPHOTO_RGB,
+        RGBColorSpace::DISPLAY_P3,
+        RGBColorSpace::REC2020,
+    ]
+    .iter()
+    .map(|space| {
+        (
+            (space.red, space.green, space.blue, space.white),
+            SpaceMatrices::derive(space),
+        )
+    })
+    .collect();
+}
+
+/// A trait for types that name an RGB color space, so that the space can be a type parameter of
+/// [`CustomRGBColor`](../colors/customrgbcolor/struct.CustomRGBColor.html). These types are
+/// typically empty structs that are never constructed.
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::CustomRGBColor;
+/// # use scarlet::rgbcolorspace::{RGBColorSpace, RGBSpace, TransferFunction};
+/// // the measured primaries of a particular display
+/// struct MyDisplay;
+/// impl RGBSpace for MyDisplay {
+///     fn color_space() -> RGBColorSpace {
+///         RGBColorSpace {
+///             red: [0.6512, 0.3297],
+///             green: [0.2914, 0.6269],
+///             blue: [0.1505, 0.0587],
+///             white: Illuminant::D65,
+///             transfer: TransferFunction::Gamma(2.2),
+///         }
+///     }
+/// }
+/// let color: CustomRGBColor<MyDisplay> = RGBColor::from_hex_code("#3399cc").unwrap().convert();
+/// // the color is the same, whichever space it's written in
+/// let back: RGBColor = color.convert();
+/// assert_eq!(back.to_string(), "#3399CC");
+/// ```
+pub trait RGBSpace {
+    /// The description of this color space.
+    fn color_space() -> RGBColorSpace;
+    /// The name CSS uses for this space in `color()`, if it has one. Colors in spaces without a CSS
+    /// name are serialized as XYZ instead.
+    fn css_name() -> Option<&'static str> {
+        None
+    }
+}
+
+/// The ProPhoto (ROMM) RGB color space, described by
+/// [`RGBColorSpace::PROPHOTO_RGB`](struct.RGBColorSpace.html#associatedconstant.PROPHOTO_RGB), for use
+/// as the parameter of [`CustomRGBColor`](../colors/customrgbcolor/struct.CustomRGBColor.html).
+/// This is how CSS defines `prophoto-rgb`, without the flare correction of
+/// [`ROMMRGBColor`](../colors/rommrgbcolor/struct.ROMMRGBColor.html).
+#[derive(Debug, Copy, Clone)]
+pub struct ProPhotoRGB;
+
+impl RGBSpace for ProPhotoRGB {
+    fn color_space() -> RGBColorSpace {
+        RGBColorSpace::PROPHOTO_RGB
+    }
+    fn css_name() -> Option<&'static str> {
+        Some("prophoto-rgb")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_cached_matrices() {
+        // the cached matrices are the same as derived ones
+        let cached = RGBColorSpace::DISPLAY_P3.matrices();
+        let derived = SpaceMatrices::derive(&RGBColorSpace::DISPLAY_P3);
+        assert_eq!(cached.to_xyz, derived.to_xyz);
+        assert_eq!(cached.from_xyz, derived.from_xyz);
+        // a space with the primaries of a built-in one but a different white doesn't use its cache
+        let d50_srgb = RGBColorSpace {
+            white: Illuminant::D50,
+            ..RGBColorSpace::SRGB
+        };
+        let m = d50_srgb.rgb_to_xyz_matrix();
+        let d50 = Illuminant::D50.white_point();
+        for i in 0..3 {
+            assert!((m[i][0] + m[i][1] + m[i][2] - d50[i]).abs() <= 1e-12);
+        }
+    }
+}
@@ -0,0 +1,383 @@
+//! This file provides the spectral data behind Scarlet's illuminants: tables of relative spectral
+//! power, sampled every 5 nm from 380 nm to 780 nm, and the basis functions of the CIE daylight
+//! model. The values are those published by the CIE in CIE 15:2004, *Colorimetry*, tables T.1, T.2,
+//! and T.6, and are normalized as the CIE gives them. It also has the reflectances of the test
+//! color samples from CIE 13.3, *Method of Measuring and Specifying Colour Rendering Properties of
+//! Light Sources*.
+
+// measured values like 3.14 aren't attempts at pi
+#![allow(clippy::approx_constant)]
+
+/// The first wavelength, in nanometers, of each table in this file other than the daylight basis
+/// functions.
+pub(crate) const SPD_START: f64 = 380.0;
+/// The spacing, in nanometers, between entries of each table in this file.
+pub(crate) const SPD_STEP: f64 = 5.0;
+
+/// CIE illuminant B, a simulation of direct noon sunlight.
+#[rustfmt::skip]
+pub(crate) static ILLUMINANT_B: [f64; 81] = [
+    22.40, 26.85, 31.30, 36.18, 41.30, 46.62, 52.10, 57.70, 63.20,
+    68.37, 73.10, 77.31, 80.80, 83.44, 85.40, 86.88, 88.30, 90.08,
+    92.00, 93.75, 95.20, 96.23, 96.50, 95.71, 94.20, 92.37, 90.70,
+    89.65, 89.50, 90.43, 92.20, 94.46, 96.90, 99.16, 101.00, 102.20,
+    102.80, 102.92, 102.60, 101.90, 101.00, 100.07, 99.20, 98.44, 98.00,
+    98.08, 98.50, 99.06, 99.70, 100.36, 101.00, 101.56, 102.20, 103.05,
+    103.90, 104.59, 105.00, 105.08, 104.90, 104.55, 103.90, 102.84, 101.60,
+    100.38, 99.10, 97.70, 96.20, 94.60, 92.90, 91.10, 89.40, 88.00,
+    86.90, 85.90, 85.20, 84.80, 84.70, 84.90, 85.40, 86.10, 87.00,
+];
+
+/// CIE illuminant C, a simulation of average daylight.
+#[rustfmt::skip]
+pub(crate) static ILLUMINANT_C: [f64; 81] = [
+    33.00, 39.92, 47.40, 55.17, 63.30, 71.81, 80.60, 89.53, 98.10,
+    105.80, 112.40, 117.75, 121.50, 123.45, 124.00, 123.60, 123.10, 123.30,
+    123.80, 124.09, 123.90, 122.92, 120.70, 116.90, 112.10, 106.98, 102.30,
+    98.81, 96.90, 96.78, 98.00, 99.94, 102.10, 103.95, 105.20, 105.67,
+    105.30, 104.11, 102.30, 100.15, 97.80, 95.43, 93.20, 91.22, 89.70,
+    88.83, 88.40, 88.19, 88.10, 88.06, 88.00, 87.86, 87.80, 87.99,
+    88.20, 88.20, 87.90, 87.22, 86.30, 85.30, 84.00, 82.21, 80.20,
+    78.24, 76.30, 74.36, 72.40, 70.40, 68.30, 66.30, 64.40, 62.80,
+    61.50, 60.20, 59.20, 58.50, 58.10, 58.00, 58.20, 58.50, 59.10,
+];
+
+/// The CIE F series of fluorescent illuminants, F1 through F12 in order.
+#[rustfmt::skip]
+pub(crate) static ILLUMINANT_F: [[f64; 81]; 12] = [
+    [
+        1.87, 2.36, 2.94, 3.47, 5.17, 19.49, 6.13, 6.24, 7.01,
+        7.79, 8.56, 43.67, 16.94, 10.72, 11.35, 11.89, 12.37, 12.75,
+        13.00, 13.15, 13.23, 13.17, 13.13, 12.85, 12.52, 12.20, 11.83,
+        11.50, 11.22, 11.05, 11.03, 11.18, 11.53, 27.74, 17.05, 13.55,
+        14.33, 15.01, 15.52, 18.29, 19.55, 15.48, 14.91, 14.15, 13.22,
+        12.19, 11.12, 10.03, 8.95, 7.96, 7.02, 6.20, 5.42, 4.73,
+        4.15, 3.64, 3.20, 2.81, 2.47, 2.18, 1.93, 1.72, 1.67,
+        1.43, 1.29, 1.19, 1.08, 0.96, 0.88, 0.81, 0.77, 0.75,
+        0.73, 0.68, 0.69, 0.64, 0.68, 0.69, 0.61, 0.52, 0.43,
+    ],
+    [
+        1.18, 1.48, 1.84, 2.15, 3.44, 15.69, 3.85, 3.74, 4.19,
+        4.62, 5.06, 34.98, 11.81, 6.27, 6.63, 6.93, 7.19, 7.40,
+        7.54, 7.62, 7.65, 7.62, 7.62, 7.45, 7.28, 7.15, 7.05,
+        7.04, 7.16, 7.47, 8.04, 8.88, 10.01, 24.88, 16.64, 14.59,
+        16.16, 17.56, 18.62, 21.47, 22.79, 19.29, 18.66, 17.73, 16.54,
+        15.21, 13.80, 12.36, 10.95, 9.65, 8.40, 7.32, 6.31, 5.43,
+        4.68, 4.02, 3.45, 2.96, 2.55, 2.19, 1.89, 1.64, 1.53,
+        1.27, 1.10, 0.99, 0.88, 0.76, 0.68, 0.61, 0.56, 0.54,
+        0.51, 0.47, 0.47, 0.43, 0.46, 0.47, 0.40, 0.33, 0.27,
+    ],
+    [
+        0.82, 1.02, 1.26, 1.44, 2.57, 14.36, 2.70, 2.45, 2.73,
+        3.00, 3.28, 31.85, 9.47, 4.02, 4.25, 4.44, 4.59, 4.72,
+        4.80, 4.86, 4.87, 4.85, 4.88, 4.77, 4.67, 4.62, 4.62,
+        4.73, 4.99, 5.48, 6.25, 7.34, 8.78, 23.82, 16.14, 14.59,
+        16.63, 18.49, 19.95, 23.11, 24.69, 21.41, 20.85, 19.93, 18.67,
+        17.22, 15.65, 14.04, 12.45, 10.95, 9.51, 8.27, 7.11, 6.09,
+        5.22, 4.45, 3.80, 3.23, 2.75, 2.33, 1.99, 1.70, 1.55,
+        1.27, 1.09, 0.96, 0.83, 0.71, 0.62, 0.54, 0.49, 0.46,
+        0.43, 0.39, 0.39, 0.35, 0.38, 0.39, 0.33, 0.28, 0.21,
+    ],
+    [
+        0.57, 0.70, 0.87, 0.98, 2.01, 13.75, 1.95, 1.59, 1.76,
+        1.93, 2.10, 30.28, 8.03, 2.55, 2.70, 2.82, 2.91, 2.99,
+        3.04, 3.08, 3.09, 3.09, 3.14, 3.06, 3.00, 2.98, 3.01,
+        3.14, 3.41, 3.90, 4.69, 5.81, 7.32, 22.59, 15.11, 13.88,
+        16.33, 18.68, 20.64, 24.28, 26.26, 23.28, 22.94, 22.14, 20.91,
+        19.43, 17.74, 16.00, 14.42, 12.56, 10.93, 9.52, 8.18, 7.01,
+        6.00, 5.11, 4.36, 3.69, 3.13, 2.64, 2.24, 1.91, 1.70,
+        1.39, 1.18, 1.03, 0.88, 0.74, 0.64, 0.54, 0.49, 0.46,
+        0.42, 0.37, 0.37, 0.33, 0.35, 0.36, 0.31, 0.26, 0.19,
+    ],
+    [
+        1.87, 2.35, 2.92, 3.45, 5.10, 18.91, 6.00, 6.11, 6.85,
+        7.58, 8.31, 40.76, 16.06, 10.32, 10.91, 11.40, 11.83, 12.17,
+        12.40, 12.54, 12.58, 12.52, 12.47, 12.20, 11.89, 11.61, 11.33,
+        11.10, 10.96, 10.97, 11.16, 11.54, 12.12, 27.78, 17.73, 14.47,
+        15.20, 15.77, 16.10, 18.54, 19.50, 15.39, 14.64, 13.72, 12.69,
+        11.57, 10.45, 9.35, 8.29, 7.32, 6.41, 5.63, 4.90, 4.26,
+        3.72, 3.25, 2.83, 2.49, 2.19, 1.93, 1.71, 1.52, 1.43,
+        1.26, 1.13, 1.05, 0.96, 0.85, 0.78, 0.72, 0.68, 0.67,
+        0.65, 0.61, 0.62, 0.59, 0.62, 0.64, 0.55, 0.47, 0.40,
+    ],
+    [
+        1.05, 1.31, 1.63, 1.90, 3.11, 14.80, 3.43, 3.30, 3.68,
+        4.07, 4.45, 32.61, 10.74, 5.48, 5.78, 6.03, 6.25, 6.41,
+        6.52, 6.58, 6.59, 6.56, 6.56, 6.42, 6.28, 6.20, 6.19,
+        6.30, 6.60, 7.12, 7.94, 9.07, 10.49, 25.22, 17.46, 15.63,
+        17.22, 18.53, 19.43, 21.97, 23.01, 19.41, 18.56, 17.42, 16.09,
+        14.64, 13.15, 11.68, 10.25, 8.95, 7.74, 6.69, 5.71, 4.87,
+        4.16, 3.55, 3.02, 2.57, 2.20, 1.87, 1.60, 1.37, 1.29,
+        1.05, 0.91, 0.81, 0.71, 0.61, 0.54, 0.48, 0.44, 0.43,
+        0.40, 0.37, 0.38, 0.35, 0.39, 0.41, 0.33, 0.26, 0.21,
+    ],
+    [
+        2.56, 3.18, 3.84, 4.53, 6.15, 19.37, 7.37, 7.05, 7.71,
+        8.41, 9.15, 44.14, 17.52, 11.35, 12.00, 12.58, 13.08, 13.45,
+        13.71, 13.88, 13.95, 13.93, 13.82, 13.64, 13.43, 13.25, 13.08,
+        12.93, 12.78, 12.60, 12.44, 12.33, 12.26, 29.52, 17.05, 12.44,
+        12.58, 12.72, 12.83, 15.46, 16.75, 12.83, 12.67, 12.45, 12.19,
+        11.89, 11.60, 11.35, 11.12, 10.95, 10.76, 10.42, 10.11, 10.04,
+        10.02, 10.11, 9.87, 8.65, 7.27, 6.44, 5.83, 5.41, 5.04,
+        4.57, 4.12, 3.77, 3.46, 3.08, 2.73, 2.47, 2.25, 2.06,
+        1.90, 1.75, 1.62, 1.54, 1.45, 1.32, 1.17, 0.99, 0.81,
+    ],
+    [
+        1.21, 1.50, 1.81, 2.13, 3.17, 13.08, 3.83, 3.45, 3.86,
+        4.42, 5.09, 34.10, 12.42, 7.68, 8.60, 9.46, 10.24, 10.84,
+        11.33, 11.71, 11.98, 12.17, 12.28, 12.32, 12.35, 12.44, 12.55,
+        12.68, 12.77, 12.72, 12.60, 12.43, 12.22, 28.96, 16.51, 11.79,
+        11.76, 11.77, 11.84, 14.61, 16.11, 12.34, 12.53, 12.72, 12.92,
+        13.12, 13.34, 13.61, 13.87, 14.07, 14.20, 14.16, 14.13, 14.34,
+        14.50, 14.46, 14.00, 12.58, 10.99, 9.98, 9.22, 8.62, 8.07,
+        7.39, 6.71, 6.16, 5.63, 5.03, 4.46, 4.02, 3.66, 3.36,
+        3.09, 2.85, 2.65, 2.51, 2.37, 2.15, 1.89, 1.61, 1.32,
+    ],
+    [
+        0.90, 1.12, 1.36, 1.60, 2.59, 12.80, 3.05, 2.56, 2.86,
+        3.30, 3.82, 32.62, 10.77, 5.84, 6.57, 7.25, 7.86, 8.35,
+        8.75, 9.06, 9.31, 9.48, 9.61, 9.68, 9.74, 9.88, 10.04,
+        10.26, 10.48, 10.63, 10.78, 10.96, 11.18, 27.71, 16.29, 12.28,
+        12.74, 13.21, 13.65, 16.57, 18.14, 14.55, 14.65, 14.66, 14.61,
+        14.50, 14.39, 14.40, 14.47, 14.62, 14.72, 14.55, 14.40, 14.58,
+        14.88, 15.51, 15.47, 13.20, 10.57, 9.18, 8.25, 7.57, 7.03,
+        6.35, 5.72, 5.25, 4.80, 4.29, 3.80, 3.43, 3.12, 2.86,
+        2.64, 2.43, 2.26, 2.14, 2.02, 1.83, 1.61, 1.35, 1.10,
+    ],
+    [
+        1.11, 0.63, 0.62, 0.57, 1.48, 12.16, 2.12, 2.70, 3.74,
+        5.14, 6.75, 34.39, 14.86, 10.40, 10.76, 10.67, 10.11, 9.27,
+        8.29, 7.29, 7.91, 16.64, 16.73, 10.44, 5.94, 3.34, 2.35,
+        1.88, 1.59, 1.47, 1.80, 5.71, 40.98, 73.69, 33.61, 8.24,
+        3.38, 2.47, 2.14, 4.86, 11.45, 14.79, 12.16, 8.97, 6.52,
+        8.31, 44.12, 34.55, 12.09, 12.15, 10.52, 4.43, 1.95, 2.19,
+        3.19, 2.77, 2.29, 2.00, 1.52, 1.35, 1.47, 1.79, 1.74,
+        1.02, 1.14, 3.32, 4.49, 2.05, 0.49, 0.24, 0.21, 0.21,
+        0.24, 0.24, 0.21, 0.17, 0.21, 0.22, 0.17, 0.12, 0.09,
+    ],
+    [
+        0.91, 0.63, 0.46, 0.37, 1.29, 12.68, 1.59, 1.79, 2.46,
+        3.33, 4.49, 33.94, 12.13, 6.95, 7.19, 7.12, 6.72, 6.13,
+        5.46, 4.79, 5.66, 14.29, 14.96, 8.97, 4.72, 2.33, 1.47,
+        1.10, 0.89, 0.83, 1.18, 4.90, 39.59, 72.84, 32.61, 7.52,
+        2.83, 1.96, 1.67, 4.43, 11.28, 14.76, 12.73, 9.74, 7.33,
+        9.72, 55.27, 42.58, 13.18, 13.16, 12.26, 5.11, 2.07, 2.34,
+        3.58, 3.01, 2.48, 2.14, 1.54, 1.33, 1.46, 1.94, 2.00,
+        1.20, 1.35, 4.10, 5.58, 2.51, 0.57, 0.27, 0.23, 0.21,
+        0.24, 0.24, 0.20, 0.24, 0.32, 0.26, 0.16, 0.12, 0.09,
+    ],
+    [
+        0.96, 0.64, 0.40, 0.33, 1.19, 12.48, 1.12, 0.94, 1.08,
+        1.37, 1.78, 29.05, 7.90, 2.65, 2.71, 2.65, 2.49, 2.33,
+        2.10, 1.91, 3.01, 10.83, 11.88, 6.88, 3.43, 1.49, 0.92,
+        0.71, 0.60, 0.63, 1.10, 4.56, 34.40, 65.40, 29.48, 7.16,
+        3.08, 2.47, 2.27, 5.09, 11.96, 15.32, 14.27, 11.86, 9.28,
+        12.31, 68.53, 53.02, 14.67, 14.38, 14.71, 6.46, 2.57, 2.75,
+        4.18, 3.44, 2.81, 2.42, 1.64, 1.36, 1.49, 2.14, 2.34,
+        1.42, 1.61, 5.04, 6.98, 3.19, 0.71, 0.30, 0.26, 0.23,
+        0.28, 0.28, 0.21, 0.17, 0.21, 0.19, 0.15, 0.10, 0.05,
+    ],
+];
+
+/// The first wavelength, in nanometers, of the daylight basis functions.
+pub(crate) const DAYLIGHT_START: f64 = 300.0;
+/// The spacing, in nanometers, between entries of the daylight basis functions.
+pub(crate) const DAYLIGHT_STEP: f64 = 10.0;
+
+/// The mean spectral power of daylight, S0, from 300 nm to 830 nm.
+#[rustfmt::skip]
+pub(crate) static DAYLIGHT_S0: [f64; 54] = [
+    0.04, 6.0, 29.6, 55.3, 57.3, 61.8, 61.5, 68.8, 63.4,
+    65.8, 94.8, 104.8, 105.9, 96.8, 113.9, 125.6, 125.5, 121.3,
+    121.3, 113.5, 113.1, 110.8, 106.5, 108.8, 105.3, 104.4, 100.0,
+    96.0, 95.1, 89.1, 90.5, 90.3, 88.4, 84.0, 85.1, 81.9,
+    82.6, 84.9, 81.3, 71.9, 74.3, 76.4, 63.3, 71.7, 77.0,
+    65.2, 47.7, 68.6, 65.0, 66.0, 61.0, 53.3, 58.9, 61.9,
+];
+
+/// The first characteristic vector of daylight, S1, which accounts for yellow-blue variation due to
+/// cloud cover and the sun's position.
+#[rustfmt::skip]
+pub(crate) static DAYLIGHT_S1: [f64; 54] = [
+    0.02, 4.5, 22.4, 42.0, 40.6, 41.6, 38.0, 42.4, 38.5,
+    35.0, 43.4, 46.3, 43.9, 37.1, 36.7, 35.9, 32.6, 27.9,
+    24.3, 20.1, 16.2, 13.2, 8.6, 6.1, 4.2, 1.9, 0.0,
+    -1.6, -3.5, -3.5, -5.8, -7.2, -8.6, -9.5, -10.9, -10.7,
+    -12.0, -14.0, -13.6, -12.0, -13.3, -12.9, -10.6, -11.6, -12.2,
+    -10.2, -7.8, -11.2, -10.4, -10.6, -9.7, -8.3, -9.3, -9.8,
+];
+
+/// The second characteristic vector of daylight, S2, which accounts for pink-green variation due to
+/// water vapor.
+#[rustfmt::skip]
+pub(crate) static DAYLIGHT_S2: [f64; 54] = [
+    0.0, 2.0, 4.0, 8.5, 7.8, 6.7, 5.3, 6.1, 3.0,
+    1.2, -1.1, -0.5, -0.7, -1.2, -2.6, -2.9, -2.8, -2.6,
+    -2.6, -1.8, -1.5, -1.3, -1.2, -1.0, -0.5, -0.3, 0.0,
+    0.2, 0.5, 2.1, 3.2, 4.1, 4.7, 5.1, 6.7, 7.3,
+    8.6, 9.8, 10.2, 8.3, 9.6, 8.5, 7.0, 7.6, 8.0,
+    6.7, 5.2, 7.4, 6.8, 7.0, 6.4, 5.5, 6.1, 6.5,
+];
+
+/// The CIE 13.3 test color samples used to compute the color rendering index, TCS01 through TCS14
+/// in order, as spectral reflectance factors from 380 nm to 760 nm. The first eight are moderately
+/// saturated colors around the hue circle, and the rest are a strong red, yellow, green, and blue,
+/// a light skin tone, and a leaf green. The eye is barely sensitive beyond 760 nm, where each
+/// reflectance is nearly flat.
+#[rustfmt::skip]
+pub(crate) static TEST_COLOR_SAMPLES: [[f64; 77]; 14] = [
+    [
+        0.219, 0.239, 0.252, 0.256, 0.256, 0.254, 0.252, 0.248, 0.244,
+        0.240, 0.237, 0.232, 0.230, 0.226, 0.225, 0.222, 0.220, 0.218,
+        0.216, 0.214, 0.214, 0.214, 0.216, 0.218, 0.223, 0.225, 0.226,
+        0.226, 0.225, 0.225, 0.227, 0.230, 0.236, 0.245, 0.253, 0.262,
+        0.272, 0.283, 0.298, 0.318, 0.341, 0.367, 0.390, 0.409, 0.424,
+        0.435, 0.442, 0.448, 0.450, 0.451, 0.451, 0.451, 0.451, 0.451,
+        0.450, 0.450, 0.451, 0.451, 0.453, 0.454, 0.455, 0.457, 0.458,
+        0.460, 0.462, 0.463, 0.464, 0.465, 0.466, 0.466, 0.466, 0.466,
+        0.467, 0.467, 0.467, 0.467, 0.467,
+    ],
+    [
+        0.070, 0.079, 0.089, 0.101, 0.111, 0.116, 0.118, 0.120, 0.121,
+        0.122, 0.122, 0.122, 0.123, 0.124, 0.127, 0.128, 0.131, 0.134,
+        0.138, 0.143, 0.150, 0.159, 0.174, 0.190, 0.207, 0.225, 0.242,
+        0.253, 0.260, 0.264, 0.267, 0.269, 0.272, 0.276, 0.282, 0.289,
+        0.299, 0.309, 0.322, 0.329, 0.335, 0.339, 0.341, 0.341, 0.342,
+        0.342, 0.342, 0.341, 0.341, 0.339, 0.339, 0.338, 0.338, 0.337,
+        0.336, 0.335, 0.334, 0.332, 0.332, 0.331, 0.331, 0.330, 0.329,
+        0.328, 0.328, 0.327, 0.326, 0.325, 0.324, 0.324, 0.324, 0.323,
+        0.322, 0.321, 0.320, 0.318, 0.316,
+    ],
+    [
+        0.065, 0.068, 0.070, 0.072, 0.073, 0.073, 0.074, 0.074, 0.074,
+        0.073, 0.073, 0.073, 0.073, 0.073, 0.074, 0.075, 0.077, 0.080,
+        0.085, 0.094, 0.109, 0.126, 0.148, 0.172, 0.198, 0.221, 0.241,
+        0.260, 0.278, 0.302, 0.339, 0.370, 0.392, 0.399, 0.400, 0.393,
+        0.380, 0.365, 0.349, 0.332, 0.315, 0.299, 0.285, 0.272, 0.264,
+        0.257, 0.252, 0.247, 0.241, 0.235, 0.229, 0.224, 0.220, 0.217,
+        0.216, 0.216, 0.219, 0.224, 0.230, 0.238, 0.251, 0.269, 0.288,
+        0.312, 0.340, 0.366, 0.390, 0.412, 0.431, 0.447, 0.460, 0.472,
+        0.481, 0.488, 0.493, 0.497, 0.500,
+    ],
+    [
+        0.074, 0.083, 0.093, 0.105, 0.116, 0.121, 0.124, 0.126, 0.128,
+        0.131, 0.135, 0.139, 0.144, 0.151, 0.161, 0.172, 0.186, 0.205,
+        0.229, 0.254, 0.281, 0.308, 0.332, 0.352, 0.370, 0.383, 0.390,
+        0.394, 0.395, 0.392, 0.385, 0.377, 0.367, 0.354, 0.341, 0.327,
+        0.312, 0.296, 0.280, 0.263, 0.247, 0.229, 0.214, 0.198, 0.185,
+        0.175, 0.169, 0.164, 0.160, 0.156, 0.154, 0.152, 0.151, 0.149,
+        0.148, 0.148, 0.148, 0.149, 0.151, 0.154, 0.158, 0.162, 0.165,
+        0.168, 0.170, 0.171, 0.170, 0.168, 0.166, 0.164, 0.164, 0.165,
+        0.168, 0.172, 0.177, 0.181, 0.185,
+    ],
+    [
+        0.295, 0.306, 0.310, 0.312, 0.313, 0.315, 0.319, 0.322, 0.326,
+        0.330, 0.334, 0.339, 0.346, 0.352, 0.360, 0.369, 0.381, 0.394,
+        0.403, 0.410, 0.415, 0.418, 0.419, 0.417, 0.413, 0.409, 0.403,
+        0.396, 0.389, 0.381, 0.372, 0.363, 0.353, 0.342, 0.331, 0.320,
+        0.308, 0.296, 0.284, 0.271, 0.260, 0.247, 0.232, 0.220, 0.210,
+        0.200, 0.194, 0.189, 0.185, 0.183, 0.180, 0.177, 0.176, 0.175,
+        0.175, 0.175, 0.175, 0.177, 0.180, 0.183, 0.186, 0.189, 0.192,
+        0.195, 0.199, 0.200, 0.199, 0.198, 0.196, 0.195, 0.195, 0.196,
+        0.197, 0.200, 0.203, 0.205, 0.208,
+    ],
+    [
+        0.151, 0.203, 0.265, 0.339, 0.410, 0.464, 0.492, 0.508, 0.517,
+        0.524, 0.531, 0.538, 0.544, 0.551, 0.556, 0.556, 0.554, 0.549,
+        0.541, 0.531, 0.519, 0.504, 0.488, 0.469, 0.450, 0.431, 0.414,
+        0.395, 0.377, 0.358, 0.341, 0.325, 0.309, 0.293, 0.279, 0.265,
+        0.253, 0.241, 0.234, 0.227, 0.225, 0.222, 0.221, 0.220, 0.220,
+        0.220, 0.220, 0.220, 0.223, 0.227, 0.233, 0.239, 0.244, 0.251,
+        0.258, 0.263, 0.268, 0.273, 0.278, 0.281, 0.283, 0.286, 0.291,
+        0.296, 0.302, 0.313, 0.325, 0.338, 0.351, 0.364, 0.376, 0.389,
+        0.401, 0.413, 0.425, 0.436, 0.447,
+    ],
+    [
+        0.378, 0.459, 0.524, 0.546, 0.551, 0.555, 0.559, 0.560, 0.561,
+        0.558, 0.556, 0.551, 0.544, 0.535, 0.522, 0.506, 0.488, 0.469,
+        0.448, 0.429, 0.408, 0.385, 0.363, 0.341, 0.324, 0.311, 0.301,
+        0.291, 0.283, 0.273, 0.265, 0.260, 0.257, 0.257, 0.259, 0.260,
+        0.260, 0.258, 0.256, 0.254, 0.254, 0.259, 0.270, 0.284, 0.302,
+        0.324, 0.344, 0.362, 0.377, 0.389, 0.400, 0.410, 0.420, 0.429,
+        0.438, 0.445, 0.452, 0.457, 0.462, 0.466, 0.468, 0.470, 0.473,
+        0.477, 0.483, 0.489, 0.496, 0.503, 0.511, 0.518, 0.525, 0.532,
+        0.539, 0.546, 0.553, 0.559, 0.565,
+    ],
+    [
+        0.104, 0.129, 0.170, 0.240, 0.319, 0.416, 0.462, 0.482, 0.490,
+        0.488, 0.482, 0.473, 0.462, 0.450, 0.439, 0.426, 0.413, 0.397,
+        0.382, 0.366, 0.352, 0.337, 0.325, 0.310, 0.299, 0.289, 0.283,
+        0.276, 0.270, 0.262, 0.256, 0.251, 0.250, 0.251, 0.254, 0.258,
+        0.264, 0.269, 0.272, 0.274, 0.278, 0.284, 0.295, 0.316, 0.348,
+        0.384, 0.434, 0.482, 0.528, 0.568, 0.604, 0.629, 0.648, 0.663,
+        0.676, 0.685, 0.693, 0.700, 0.705, 0.709, 0.712, 0.715, 0.717,
+        0.719, 0.721, 0.720, 0.719, 0.722, 0.725, 0.727, 0.729, 0.730,
+        0.730, 0.730, 0.730, 0.730, 0.730,
+    ],
+    [
+        0.066, 0.062, 0.058, 0.055, 0.052, 0.052, 0.051, 0.050, 0.050,
+        0.049, 0.048, 0.047, 0.046, 0.044, 0.042, 0.041, 0.038, 0.035,
+        0.033, 0.031, 0.030, 0.029, 0.028, 0.028, 0.028, 0.029, 0.030,
+        0.030, 0.031, 0.031, 0.032, 0.032, 0.033, 0.034, 0.035, 0.037,
+        0.041, 0.044, 0.048, 0.052, 0.060, 0.076, 0.102, 0.136, 0.190,
+        0.256, 0.336, 0.418, 0.505, 0.581, 0.641, 0.682, 0.717, 0.740,
+        0.758, 0.770, 0.781, 0.790, 0.797, 0.803, 0.809, 0.814, 0.819,
+        0.824, 0.828, 0.830, 0.831, 0.833, 0.835, 0.836, 0.836, 0.837,
+        0.838, 0.839, 0.839, 0.839, 0.839,
+    ],
+    [
+        0.050, 0.054, 0.059, 0.063, 0.066, 0.067, 0.068, 0.069, 0.069,
+        0.070, 0.072, 0.073, 0.076, 0.078, 0.083, 0.088, 0.095, 0.103,
+        0.113, 0.125, 0.142, 0.162, 0.189, 0.219, 0.262, 0.305, 0.365,
+        0.416, 0.465, 0.509, 0.546, 0.581, 0.610, 0.634, 0.653, 0.666,
+        0.678, 0.687, 0.693, 0.698, 0.701, 0.704, 0.705, 0.705, 0.706,
+        0.707, 0.707, 0.707, 0.708, 0.708, 0.710, 0.711, 0.712, 0.714,
+        0.716, 0.718, 0.720, 0.722, 0.725, 0.729, 0.731, 0.735, 0.739,
+        0.742, 0.746, 0.748, 0.749, 0.751, 0.753, 0.754, 0.755, 0.755,
+        0.755, 0.755, 0.756, 0.757, 0.758,
+    ],
+    [
+        0.111, 0.121, 0.127, 0.129, 0.127, 0.121, 0.116, 0.112, 0.108,
+        0.105, 0.104, 0.104, 0.105, 0.106, 0.110, 0.115, 0.123, 0.134,
+        0.148, 0.167, 0.192, 0.219, 0.252, 0.291, 0.325, 0.347, 0.356,
+        0.353, 0.346, 0.333, 0.314, 0.294, 0.271, 0.248, 0.227, 0.206,
+        0.188, 0.170, 0.153, 0.138, 0.125, 0.114, 0.106, 0.100, 0.096,
+        0.092, 0.090, 0.087, 0.085, 0.082, 0.080, 0.079, 0.078, 0.078,
+        0.078, 0.078, 0.081, 0.083, 0.088, 0.093, 0.102, 0.112, 0.125,
+        0.141, 0.161, 0.182, 0.203, 0.223, 0.242, 0.257, 0.270, 0.282,
+        0.292, 0.302, 0.310, 0.314, 0.317,
+    ],
+    [
+        0.120, 0.103, 0.090, 0.082, 0.076, 0.068, 0.064, 0.065, 0.075,
+        0.093, 0.123, 0.160, 0.207, 0.256, 0.300, 0.331, 0.346, 0.347,
+        0.341, 0.328, 0.307, 0.282, 0.257, 0.230, 0.204, 0.178, 0.154,
+        0.129, 0.109, 0.090, 0.075, 0.062, 0.051, 0.041, 0.035, 0.029,
+        0.025, 0.022, 0.019, 0.017, 0.017, 0.017, 0.016, 0.016, 0.016,
+        0.016, 0.016, 0.016, 0.016, 0.016, 0.018, 0.018, 0.018, 0.018,
+        0.019, 0.020, 0.023, 0.024, 0.026, 0.030, 0.035, 0.043, 0.056,
+        0.074, 0.097, 0.128, 0.166, 0.210, 0.257, 0.305, 0.354, 0.401,
+        0.446, 0.485, 0.520, 0.551, 0.577,
+    ],
+    [
+        0.104, 0.127, 0.161, 0.211, 0.264, 0.313, 0.341, 0.352, 0.359,
+        0.361, 0.364, 0.365, 0.367, 0.369, 0.372, 0.374, 0.376, 0.379,
+        0.384, 0.389, 0.397, 0.405, 0.416, 0.429, 0.443, 0.454, 0.461,
+        0.466, 0.469, 0.471, 0.474, 0.476, 0.483, 0.490, 0.506, 0.526,
+        0.553, 0.582, 0.618, 0.651, 0.680, 0.701, 0.717, 0.729, 0.736,
+        0.742, 0.745, 0.747, 0.748, 0.748, 0.748, 0.748, 0.748, 0.748,
+        0.748, 0.748, 0.747, 0.747, 0.747, 0.747, 0.747, 0.747, 0.747,
+        0.746, 0.746, 0.746, 0.745, 0.744, 0.743, 0.744, 0.745, 0.748,
+        0.750, 0.750, 0.749, 0.748, 0.748,
+    ],
+    [
+        0.036, 0.036, 0.037, 0.038, 0.039, 0.039, 0.040, 0.041, 0.042,
+        0.042, 0.043, 0.044, 0.044, 0.045, 0.045, 0.046, 0.047, 0.048,
+        0.050, 0.052, 0.055, 0.057, 0.062, 0.067, 0.075, 0.083, 0.092,
+        0.100, 0.108, 0.121, 0.133, 0.142, 0.150, 0.154, 0.155, 0.152,
+        0.147, 0.140, 0.133, 0.125, 0.118, 0.112, 0.106, 0.101, 0.098,
+        0.095, 0.093, 0.090, 0.089, 0.087, 0.086, 0.085, 0.084, 0.084,
+        0.084, 0.084, 0.085, 0.087, 0.092, 0.096, 0.102, 0.110, 0.123,
+        0.137, 0.152, 0.169, 0.188, 0.207, 0.226, 0.243, 0.260, 0.277,
+        0.294, 0.310, 0.325, 0.339, 0.353,
+    ],
+];
@@ -0,0 +1,740 @@
+//! This module provides [`Spectrum`], a function of wavelength given by samples, such as the
+//! reflectance curve a spectrophotometer measures. A reflectance spectrum describes an object
+//! independently of the light falling on it, so it can be turned into the color that object has
+//! under any illuminant with a spectrum, as seen by either standard observer.
+//!
+//! The integration follows the usual method of CIE 15 and ASTM E308: the product of the
+//! reflectance, the spectral power of the illuminant, and the color matching functions is summed
+//! every 5 nm from 360 nm to 830 nm, and scaled so that a perfect white reflector gets the white
+//! point of the illuminant, with Y = 1. Reflectances measured over a shorter range are extended by
+//! repeating their first and last values, as ASTM E308 recommends.
+//!
+//! Two objects with different spectra can match under one light and not another: this is called
+//! *metamerism*, and it's why a paint that matches in the store can look wrong at home.
+//! [`Spectrum::metamerism_index`] measures how badly a match breaks down under a change of
+//! illuminant.
+//!
+//! Going the other way, from a color to a spectrum, has no single answer, but spectral renderers
+//! need one anyway: [`Spectrum::from_rgb`] gives a smooth and physically plausible reflectance for
+//! any sRGB color.
+//!
+//! [`Spectrum`]: struct.Spectrum.html
+//! [`Spectrum::metamerism_index`]: struct.Spectrum.html#method.metamerism_index
+//! [`Spectrum::from_rgb`]: struct.Spectrum.html#method.from_rgb
+
+use color::{Color, RGBColor, XYZColor};
+use colors::cielabcolor::CIELABColor;
+use illuminants::Illuminant;
+use observer::StandardObserver;
+use rulinalg::matrix::Matrix;
+use rulinalg::vector::Vector;
+
+use std::error::Error;
+use std::fmt;
+
+/// The errors that can happen when making or using a [`Spectrum`](struct.Spectrum.html).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SpectrumError {
+    /// Returned when there are no samples at all.
+    Empty,
+    /// Returned when the number of wavelengths and the number of values differ.
+    MismatchedLengths,
+    /// Returned when the wavelengths aren't finite and strictly increasing, or a step between them
+    /// isn't positive.
+    InvalidWavelengths,
+    /// Returned when integrating under an illuminant that isn't defined by its spectrum, such as
+    /// `Illuminant::Custom`.
+    NoSpectralPower,
+    /// Returned when asking for the reflectance of a color that no object can have, such as an RGB
+    /// color with components outside of 0 to 1.
+    OutOfGamut,
+}
+
+impl SpectrumError {
+    /// A short description of this error.
+    fn description(self) -> &'static str {
+        match self {
+            SpectrumError::Empty => "spectrum has no samples",
+            SpectrumError::MismatchedLengths => "number of wavelengths and values differ",
+            SpectrumError::InvalidWavelengths => "wavelengths are not strictly increasing",
+            SpectrumError::NoSpectralPower => "illuminant has no spectral power distribution",
+            SpectrumError::OutOfGamut => "no reflectance spectrum has this color",
+        }
+    }
+}
+
+impl fmt::Display for SpectrumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", SpectrumError::description(*self))
+    }
+}
+
+impl Error for SpectrumError {
+    fn description(&self) -> &str {
+        SpectrumError::description(*self)
+    }
+}
+
+/// How many steps [`Spectrum::from_rgb`] takes from gray to the color it's fitting, so that each
+/// fit starts close to the answer.
+///
+/// [`Spectrum::from_rgb`]: struct.Spectrum.html#method.from_rgb
+const FIT_STEPS: u32 = 8;
+/// The most Gauss-Newton iterations [`Spectrum::from_rgb`] takes at each step.
+///
+/// [`Spectrum::from_rgb`]: struct.Spectrum.html#method.from_rgb
+const FIT_ITERATIONS: u32 = 30;
+
+/// The sigmoid Jakob and Hanika use to squeeze a polynomial into a reflectance between 0 and 1,
+/// which needs no exponentials.
+fn sigmoid(z: f64) -> f64 {
+    0.5 + z / (2.0 * (1.0 + z * z).sqrt())
+}
+
+/// Makes the spectrum of the sigmoid of a quadratic with the given coefficients, from the highest
+/// power down, in wavelength scaled from 0 at 360 nm to 1 at 830 nm.
+fn sigmoid_polynomial(coefs: [f64; 3]) -> Spectrum {
+    let values = (0..95)
+        .map(|i| {
+            let x = f64::from(i) / 94.0;
+            sigmoid((coefs[0] * x + coefs[1]) * x + coefs[2])
+        })
+        .collect();
+    Spectrum::uniform(360.0, 5.0, values).expect("Wavelengths are valid.")
+}
+
+/// Gets the CIELAB coordinates of an object with the given spectrum under D65.
+fn lab_under_d65(spectrum: &Spectrum) -> [f64; 3] {
+    let xyz = spectrum.integrate(
+        |wl| {
+            Illuminant::D65
+                .spectral_power(wl)
+                .expect("D65 has a spectrum.")
+        },
+        StandardObserver::CIE1931,
+    );
+    let lab: CIELABColor = XYZColor {
+        x: xyz[0],
+        y: xyz[1],
+        z: xyz[2],
+        illuminant: Illuminant::D65,
+    }
+    .convert();
+    [lab.l, lab.a, lab.b]
+}
+
+/// A function of wavelength, given by its values at a set of wavelengths in nanometers and linearly
+/// interpolated between them. Outside of the sampled range, the first or last value is used. This
+/// is usually a reflectance or transmittance, from 0 to 1, but anything sampled by wavelength works.
+/// # Example
+/// A spectrophotometer reading from 400 nm to 700 nm every 10 nm, of a dull orange.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::spectrum::Spectrum;
+/// let values: Vec<f64> = (0..31)
+///     .map(|i| if i < 14 { 0.05 } else if i < 18 { 0.05 + 0.15 * f64::from(i - 13) } else { 0.65 })
+///     .collect();
+/// let reading = Spectrum::uniform(400., 10., values).unwrap();
+/// let color: RGBColor = reading.to_xyz(Illuminant::D65).unwrap().convert();
+/// assert!(color.r > color.g && color.g > color.b);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spectrum {
+    wavelengths: Vec<f64>,
+    values: Vec<f64>,
+}
+
+impl Spectrum {
+    /// Makes a spectrum from values at the given wavelengths in nanometers, which need not be evenly
+    /// spaced.
+    /// # Errors
+    /// Returns `SpectrumError::MismatchedLengths` if the two vectors have different lengths,
+    /// `SpectrumError::Empty` if they're empty, and `SpectrumError::InvalidWavelengths` if the
+    /// wavelengths aren't finite and strictly increasing.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::spectrum::{Spectrum, SpectrumError};
+    /// let spectrum = Spectrum::new(vec![400., 500., 520.], vec![0.1, 0.3, 0.5]).unwrap();
+    /// assert_eq!(spectrum.value_at(450.), 0.2);
+    /// assert_eq!(
+    ///     Spectrum::new(vec![400., 500.], vec![0.1]),
+    ///     Err(SpectrumError::MismatchedLengths)
+    /// );
+    /// ```
+    pub fn new(wavelengths: Vec<f64>, values: Vec<f64>) -> Result<Spectrum, SpectrumError> {
+        if wavelengths.len() != values.len() {
+            Err(SpectrumError::MismatchedLengths)
+        } else if wavelengths.is_empty() {
+            Err(SpectrumError::Empty)
+        } else if wavelengths.iter().any(|wl| !wl.is_finite())
+            || wavelengths.windows(2).any(|pair| pair[0] >= pair[1])
+        {
+            Err(SpectrumError::InvalidWavelengths)
+        } else {
+            Ok(Spectrum {
+                wavelengths,
+                values,
+            })
+        }
+    }
+    /// Makes a spectrum from values evenly spaced `step` nanometers apart, starting at `start`
+    /// nanometers. This is how most instruments and standards tabulate spectra.
+    /// # Errors
+    /// Returns `SpectrumError::Empty` if there are no values, and
+    /// `SpectrumError::InvalidWavelengths` if `step` isn't positive or the wavelengths aren't finite.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::spectrum::Spectrum;
+    /// let spectrum = Spectrum::uniform(380., 5., vec![0.2; 81]).unwrap();
+    /// assert_eq!(spectrum.wavelengths()[80], 780.);
+    /// ```
+    pub fn uniform(start: f64, step: f64, values: Vec<f64>) -> Result<Spectrum, SpectrumError> {
+        let wavelengths = (0..values.len()).map(|i| start + step * i as f64).collect();
+        Spectrum::new(wavelengths, values)
+    }
+    /// Samples the spectral power distribution of an illuminant every 5 nm from 360 nm to 830 nm,
+    /// the range of the standard observers. This is useful for treating a standard illuminant as a
+    /// light source, such as to find its color rendering index.
+    /// # Errors
+    /// Returns `SpectrumError::NoSpectralPower` if the illuminant isn't defined by its spectrum.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::spectrum::Spectrum;
+    /// let a = Spectrum::from_illuminant(Illuminant::A).unwrap();
+    /// assert!((a.value_at(560.) - 100.).abs() <= 1e-10);
+    /// assert!(Spectrum::from_illuminant(Illuminant::Custom([1., 1., 1.])).is_err());
+    /// ```
+    pub fn from_illuminant(illuminant: Illuminant) -> Result<Spectrum, SpectrumError> {
+        let values = (0..95)
+            .map(|i| illuminant.spectral_power(360.0 + 5.0 * f64::from(i)))
+            .collect::<Option<Vec<f64>>>()
+            .ok_or(SpectrumError::NoSpectralPower)?;
+        Spectrum::uniform(360.0, 5.0, values)
+    }
+    /// Makes a smooth reflectance spectrum for an sRGB color, so that an object with that
+    /// reflectance has that color under D65, the white point of sRGB. Many spectra have any given
+    /// color, so this picks the kind a real surface is likely to have, which is also what spectral
+    /// renderers need: smooth, and between 0 and 1 everywhere. It uses the method of Jakob and
+    /// Hanika, from *A Low-Dimensional Function Space for Efficient Spectral Upsampling* (2019),
+    /// which fits the sigmoid of a quadratic in wavelength to the color.
+    ///
+    /// The spectrum is sampled every 5 nm from 360 nm to 830 nm. Turning it back into a color under
+    /// D65 reproduces the original to within a CIEDE2000 [`distance`] of 0.02, and to within 0.001
+    /// for everything except white. White gets a flat spectrum of 1, the most any object can reflect,
+    /// and that's off by about 0.012 because the white point of sRGB is rounded from the spectrum of
+    /// D65. Black gets a flat spectrum of 0.
+    ///
+    /// [`distance`]: ../color/trait.Color.html#method.distance
+    /// # Errors
+    /// Returns `SpectrumError::OutOfGamut` if any component of the color isn't between 0 and 1,
+    /// as such colors aren't in sRGB.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::spectrum::{Spectrum, SpectrumError};
+    /// let orange = RGBColor::from_hex_code("#e08030").unwrap();
+    /// let spectrum = Spectrum::from_rgb(orange).unwrap();
+    /// // mostly reflects long wavelengths
+    /// assert!(spectrum.value_at(650.) > 0.5 && spectrum.value_at(450.) < 0.1);
+    /// let round_trip: RGBColor = spectrum.to_xyz(Illuminant::D65).unwrap().convert();
+    /// assert!(orange.distance(&round_trip) <= 0.001);
+    /// assert_eq!(orange.to_string(), round_trip.to_string());
+    /// assert_eq!(
+    ///     Spectrum::from_rgb(RGBColor { r: 1.2, g: 0., b: 0. }),
+    ///     Err(SpectrumError::OutOfGamut)
+    /// );
+    /// ```
+    pub fn from_rgb(rgb: RGBColor) -> Result<Spectrum, SpectrumError> {
+        if [rgb.r, rgb.g, rgb.b]
+            .iter()
+            .any(|c| !(0.0..=1.0).contains(c))
+        {
+            return Err(SpectrumError::OutOfGamut);
+        }
+        // the luminance of sRGB is the same as the reflectance of a gray with that luminance
+        let y = rgb.to_xyz(Illuminant::D65).y;
+        if [rgb.r, rgb.g, rgb.b] == [0.0; 3] || [rgb.r, rgb.g, rgb.b] == [1.0; 3] {
+            // the sigmoid only gets to 0 and 1 in the limit
+            return Spectrum::uniform(360.0, 5.0, vec![rgb.r; 95]);
+        }
+        let target = {
+            let lab: CIELABColor = rgb.convert();
+            [lab.l, lab.a, lab.b]
+        };
+        // start from the gray with the same luminance, the inverse of the sigmoid of its
+        // reflectance, and walk towards the color a step at a time
+        let gray = 2.0 * y.clamp(1e-6, 1.0 - 1e-6) - 1.0;
+        let mut coefs = [0.0, 0.0, gray / (1.0 - gray * gray).sqrt()];
+        let start = lab_under_d65(&sigmoid_polynomial(coefs));
+        let residual = |coefs: [f64; 3], goal: [f64; 3]| {
+            let lab = lab_under_d65(&sigmoid_polynomial(coefs));
+            [lab[0] - goal[0], lab[1] - goal[1], lab[2] - goal[2]]
+        };
+        let norm = |r: [f64; 3]| r.iter().map(|x| x * x).sum::<f64>().sqrt();
+        for step in 1..=FIT_STEPS {
+            let t = f64::from(step) / f64::from(FIT_STEPS);
+            let goal = [
+                start[0] + (target[0] - start[0]) * t,
+                start[1] + (target[1] - start[1]) * t,
+                start[2] + (target[2] - start[2]) * t,
+            ];
+            let mut r = residual(coefs, goal);
+            for _ in 0..FIT_ITERATIONS {
+                if norm(r) <= 1e-6 {
+                    break;
+                }
+                // the Jacobian, by forward differences
+                let mut jacobian = vec![0.0; 9];
+                for j in 0..3 {
+                    let h = 1e-6 * coefs[j].abs().max(1.0);
+                    let mut nudged = coefs;
+                    nudged[j] += h;
+                    let r_h = residual(nudged, goal);
+                    for i in 0..3 {
+                        jacobian[3 * i + j] = (r_h[i] - r[i]) / h;
+                    }
+                }
+                let delta = match Matrix::new(3, 3, jacobian).solve(Vector::new(r.to_vec())) {
+                    Ok(delta) => delta,
+                    Err(_) => break,
+                };
+                // halve the step until it helps, so that the fit can't run away
+                let mut scale = 1.0;
+                let mut improved = false;
+                while scale >= 1.0 / 64.0 {
+                    let next = [
+                        coefs[0] - scale * delta[0],
+                        coefs[1] - scale * delta[1],
+                        coefs[2] - scale * delta[2],
+                    ];
+                    let r_next = residual(next, goal);
+                    if norm(r_next) < norm(r) {
+                        coefs = next;
+                        r = r_next;
+                        improved = true;
+                        break;
+                    }
+                    scale /= 2.0;
+                }
+                if !improved {
+                    break;
+                }
+            }
+        }
+        Ok(sigmoid_polynomial(coefs))
+    }
+    /// Gets the wavelengths, in nanometers, at which the spectrum is sampled.
+    pub fn wavelengths(&self) -> &[f64] {
+        &self.wavelengths
+    }
+    /// Gets the sampled values of the spectrum, in the same order as its wavelengths.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+    /// Gets the value of the spectrum at the given wavelength in nanometers, linearly interpolating
+    /// between samples. Before the first sample and after the last, the nearest sample is used.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::spectrum::Spectrum;
+    /// let spectrum = Spectrum::uniform(400., 100., vec![0.2, 0.6, 0.4]).unwrap();
+    /// assert!((spectrum.value_at(425.) - 0.3).abs() <= 1e-10);
+    /// assert_eq!(spectrum.value_at(300.), 0.2);
+    /// assert_eq!(spectrum.value_at(800.), 0.4);
+    /// ```
+    pub fn value_at(&self, wavelength: f64) -> f64 {
+        let last = self.wavelengths.len() - 1;
+        if wavelength.is_nan() {
+            return wavelength;
+        } else if wavelength <= self.wavelengths[0] {
+            return self.values[0];
+        } else if wavelength >= self.wavelengths[last] {
+            return self.values[last];
+        }
+        // the index of the first sample after the wavelength, which isn't the first sample
+        let hi = match self.wavelengths.binary_search_by(|wl| {
+            wl.partial_cmp(&wavelength)
+                .expect("Wavelengths are finite.")
+        }) {
+            Ok(i) => return self.values[i],
+            Err(i) => i,
+        };
+        let (wl0, wl1) = (self.wavelengths[hi - 1], self.wavelengths[hi]);
+        let frac = (wavelength - wl0) / (wl1 - wl0);
+        self.values[hi - 1] * (1.0 - frac) + self.values[hi] * frac
+    }
+    /// Resamples the spectrum every `step` nanometers from `start` to `end` inclusive, interpolating
+    /// as [`value_at`](#method.value_at) does. If `end - start` isn't a multiple of `step`, the last
+    /// sample is the last one before `end`.
+    /// # Errors
+    /// Returns `SpectrumError::InvalidWavelengths` if `step` isn't positive, `end` is before `start`,
+    /// or either isn't finite.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::spectrum::Spectrum;
+    /// let coarse = Spectrum::uniform(400., 20., vec![0.1, 0.3, 0.2]).unwrap();
+    /// let fine = coarse.resample(400., 440., 10.).unwrap();
+    /// assert_eq!(fine.wavelengths(), &[400., 410., 420., 430., 440.]);
+    /// assert!((fine.values()[1] - 0.2).abs() <= 1e-10);
+    /// ```
+    pub fn resample(&self, start: f64, end: f64, step: f64) -> Result<Spectrum, SpectrumError> {
+        if !(start.is_finite() && end.is_finite() && step > 0.0 && end >= start) {
+            return Err(SpectrumError::InvalidWavelengths);
+        }
+        // allow a little rounding error so the end is included when it should be
+        let n = ((end - start) / step + 1e-9).floor() as usize + 1;
+        let wavelengths: Vec<f64> = (0..n).map(|i| start + step * i as f64).collect();
+        let values = wavelengths.iter().map(|&wl| self.value_at(wl)).collect();
+        Spectrum::new(wavelengths, values)
+    }
+    /// Gets the color of an object with this reflectance spectrum under the given illuminant, as the
+    /// CIE 1931 2° standard observer sees it. A perfect white reflector, with a reflectance of 1
+    /// everywhere, gets the white point of the illuminant.
+    /// # Errors
+    /// Returns `SpectrumError::NoSpectralPower` if the illuminant isn't defined by its spectrum.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::spectrum::Spectrum;
+    /// let gray = Spectrum::uniform(360., 470., vec![0.2, 0.2]).unwrap();
+    /// let xyz = gray.to_xyz(Illuminant::F2).unwrap();
+    /// assert!((xyz.y - 0.2).abs() <= 1e-10);
+    /// assert!((xyz.x - 0.2 * Illuminant::F2.white_point()[0]).abs() <= 1e-10);
+    /// ```
+    pub fn to_xyz(&self, illuminant: Illuminant) -> Result<XYZColor, SpectrumError> {
+        self.to_xyz_with(illuminant, StandardObserver::CIE1931)
+    }
+    /// Gets the color of an object with this reflectance spectrum under the given illuminant, as the
+    /// given standard observer sees it. Note that for the CIE 1964 observer, the resulting XYZ
+    /// values are in the 10° XYZ space, and so converting them to other color spaces, which all
+    /// assume the 2° observer, won't give meaningful results.
+    /// # Errors
+    /// Returns `SpectrumError::NoSpectralPower` if the illuminant isn't defined by its spectrum.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::observer::StandardObserver;
+    /// # use scarlet::spectrum::Spectrum;
+    /// let white = Spectrum::uniform(400., 10., vec![1.; 31]).unwrap();
+    /// let xyz = white.to_xyz_with(Illuminant::A, StandardObserver::CIE1964).unwrap();
+    /// let wp = Illuminant::A.white_point_with(StandardObserver::CIE1964);
+    /// assert!((xyz.x - wp[0]).abs() <= 1e-10 && (xyz.z - wp[2]).abs() <= 1e-10);
+    /// ```
+    pub fn to_xyz_with(
+        &self,
+        illuminant: Illuminant,
+        observer: StandardObserver,
+    ) -> Result<XYZColor, SpectrumError> {
+        // check the illuminant first, so the integration can assume it has a spectrum
+        illuminant
+            .spectral_power(560.0)
+            .ok_or(SpectrumError::NoSpectralPower)?;
+        let xyz = self.integrate(
+            |wl| {
+                illuminant
+                    .spectral_power(wl)
+                    .expect("Illuminant has a spectrum.")
+            },
+            observer,
+        );
+        Ok(XYZColor {
+            x: xyz[0],
+            y: xyz[1],
+            z: xyz[2],
+            illuminant,
+        })
+    }
+    /// Checks whether an object with this reflectance spectrum and one with another look the same
+    /// under the given illuminant: that is, whether their [`distance`], with CIEDE2000, is at most
+    /// `tolerance`. A tolerance of 1 is a just noticeable difference, and industries that match
+    /// colors usually set their own.
+    ///
+    /// [`distance`]: ../color/trait.Color.html#method.distance
+    /// # Errors
+    /// Returns `SpectrumError::NoSpectralPower` if the illuminant isn't defined by its spectrum.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::spectrum::Spectrum;
+    /// let gray = Spectrum::uniform(400., 300., vec![0.5, 0.5]).unwrap();
+    /// let bluish_gray = Spectrum::uniform(400., 300., vec![0.52, 0.5]).unwrap();
+    /// assert_eq!(gray.matches_under(&bluish_gray, Illuminant::D65, 1.), Ok(true));
+    /// assert_eq!(gray.matches_under(&bluish_gray, Illuminant::D65, 0.1), Ok(false));
+    /// ```
+    pub fn matches_under(
+        &self,
+        other: &Spectrum,
+        illuminant: Illuminant,
+        tolerance: f64,
+    ) -> Result<bool, SpectrumError> {
+        Ok(self
+            .to_xyz(illuminant)?
+            .distance(&other.to_xyz(illuminant)?)
+            <= tolerance)
+    }
+    /// Computes the CIE special metamerism index for a change in illuminant, as defined in CIE
+    /// 15:2004, of an object with this reflectance spectrum, the standard, and one with another,
+    /// the trial. This is how different the two look under the `test` illuminant, given that they
+    /// match under the `reference` illuminant, usually D65. A pair that matches under every
+    /// illuminant, like two identical samples, has an index of 0, and the more the spectra differ
+    /// in shape, the larger it gets. The CIE recommends testing under A and the fluorescent
+    /// illuminants, such as F2, F7, and F11.
+    ///
+    /// Real samples rarely match exactly under the reference, so the trial's XYZ values under the
+    /// test illuminant are first corrected by the ratio of the standard's to the trial's under the
+    /// reference, which is the multiplicative correction CIE 15 gives. As CIE 15 specifies, both
+    /// are then put in CIELAB with the test illuminant's white point as the reference white, with
+    /// no chromatic adaptation, and the index is the [`distance`] between them, which uses
+    /// CIEDE2000.
+    ///
+    /// [`distance`]: ../color/trait.Color.html#method.distance
+    /// # Errors
+    /// Returns `SpectrumError::NoSpectralPower` if either illuminant isn't defined by its spectrum.
+    /// # Example
+    /// Two grays made with different pigments: one is flat, and the other reflects more at both
+    /// ends of the spectrum and less in the middle. They match in daylight, but not under
+    /// incandescent light.
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::spectrum::Spectrum;
+    /// let flat = Spectrum::uniform(400., 150., vec![0.4, 0.4, 0.4]).unwrap();
+    /// let dipped = Spectrum::uniform(400., 150., vec![0.5, 0.33, 0.5]).unwrap();
+    /// let index = flat.metamerism_index(&dipped, Illuminant::D65, Illuminant::A).unwrap();
+    /// assert!(index > 1.);
+    /// // but not if they're the same
+    /// let same = flat.metamerism_index(&flat, Illuminant::D65, Illuminant::A).unwrap();
+    /// assert!(same <= 1e-10);
+    /// ```
+    pub fn metamerism_index(
+        &self,
+        other: &Spectrum,
+        reference: Illuminant,
+        test: Illuminant,
+    ) -> Result<f64, SpectrumError> {
+        let (standard_ref, trial_ref) = (self.to_xyz(reference)?, other.to_xyz(reference)?);
+        let standard = self.to_xyz(test)?;
+        let trial = other.to_xyz(test)?;
+        // a component that's 0 under the reference can't be corrected, so it's left as it is
+        let correct = |value: f64, standard: f64, trial: f64| {
+            if trial == 0.0 {
+                value
+            } else {
+                value * standard / trial
+            }
+        };
+        let corrected = [
+            correct(trial.x, standard_ref.x, trial_ref.x),
+            correct(trial.y, standard_ref.y, trial_ref.y),
+            correct(trial.z, standard_ref.z, trial_ref.z),
+        ];
+        let white_point = test.white_point();
+        let standard =
+            CIELABColor::from_xyz_relative_to([standard.x, standard.y, standard.z], white_point);
+        let corrected = CIELABColor::from_xyz_relative_to(corrected, white_point);
+        Ok(standard.distance(&corrected))
+    }
+    /// Integrates this spectrum, as a reflectance, under the light with the given spectral power
+    /// distribution. The result is scaled so that a perfect white reflector has Y = 1.
+    pub(crate) fn integrate<F: Fn(f64) -> f64>(
+        &self,
+        power: F,
+        observer: StandardObserver,
+    ) -> [f64; 3] {
+        let cmfs = observer.cmfs();
+        let mut xyz = [0.0; 3];
+        let mut white_y = 0.0;
+        for (&wavelength, cmf) in cmfs.wavelengths.iter().zip(cmfs.values.iter()) {
+            let wavelength = f64::from(wavelength);
+            let power = power(wavelength);
+            let stimulus = power * self.value_at(wavelength);
+            for i in 0..3 {
+                xyz[i] += stimulus * cmf[i];
+            }
+            white_y += power * cmf[1];
+        }
+        [xyz[0] / white_y, xyz[1] / white_y, xyz[2] / white_y]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert_eq!(Spectrum::new(vec![], vec![]), Err(SpectrumError::Empty));
+        assert_eq!(
+            Spectrum::new(vec![500.0, 500.0], vec![0.1, 0.2]),
+            Err(SpectrumError::InvalidWavelengths)
+        );
+        assert_eq!(
+            Spectrum::new(vec![400.0, f64::NAN], vec![0.1, 0.2]),
+            Err(SpectrumError::InvalidWavelengths)
+        );
+        assert_eq!(
+            Spectrum::uniform(400.0, -10.0, vec![0.1, 0.2]),
+            Err(SpectrumError::InvalidWavelengths)
+        );
+        // a single sample is a flat spectrum
+        let flat = Spectrum::new(vec![550.0], vec![0.4]).unwrap();
+        assert_eq!(flat.value_at(380.0), 0.4);
+        assert_eq!(flat.value_at(780.0), 0.4);
+    }
+    #[test]
+    fn test_value_at() {
+        let spectrum = Spectrum::new(vec![400.0, 410.0, 450.0], vec![0.0, 1.0, 0.2]).unwrap();
+        assert_eq!(spectrum.value_at(410.0), 1.0);
+        assert!((spectrum.value_at(404.0) - 0.4).abs() <= 1e-10);
+        assert!((spectrum.value_at(440.0) - 0.4).abs() <= 1e-10);
+    }
+    #[test]
+    fn test_resample() {
+        let spectrum = Spectrum::uniform(380.0, 5.0, (0..81).map(f64::from).collect()).unwrap();
+        let resampled = spectrum.resample(400.0, 700.0, 10.0).unwrap();
+        assert_eq!(resampled.wavelengths().len(), 31);
+        assert_eq!(resampled.values()[0], 4.0);
+        assert_eq!(resampled.values()[30], 64.0);
+        // the end is dropped if it isn't on the grid
+        assert_eq!(
+            spectrum.resample(400.0, 405.0, 2.0).unwrap().wavelengths(),
+            &[400.0, 402.0, 404.0]
+        );
+        assert_eq!(
+            spectrum.resample(400.0, 300.0, 10.0),
+            Err(SpectrumError::InvalidWavelengths)
+        );
+    }
+    #[test]
+    fn test_to_xyz() {
+        // a perfect reflector gets the integrated white point of every illuminant
+        let white = Spectrum::new(vec![500.0], vec![1.0]).unwrap();
+        for &ill in [Illuminant::A, Illuminant::F11, Illuminant::D65].iter() {
+            let xyz = white.to_xyz(ill).unwrap();
+            let wp = ill.white_point();
+            assert_eq!(xyz.illuminant, ill);
+            assert!((xyz.y - 1.0).abs() <= 1e-10);
+            assert!((xyz.x - wp[0]).abs() <= 1e-4 && (xyz.z - wp[2]).abs() <= 3e-4);
+        }
+        assert_eq!(
+            white.to_xyz(Illuminant::Custom([1.0; 3])),
+            Err(SpectrumError::NoSpectralPower)
+        );
+        // light only at long wavelengths is red
+        let red = Spectrum::new(vec![590.0, 610.0], vec![0.0, 0.9]).unwrap();
+        let xyz = red.to_xyz(Illuminant::D65).unwrap();
+        let sum = xyz.x + xyz.y + xyz.z;
+        assert!(xyz.x / sum > 0.6);
+    }
+    #[test]
+    fn test_metamerism_index() {
+        // grays of different lightness are corrected to match, under any light
+        let light = Spectrum::new(vec![500.0], vec![0.8]).unwrap();
+        let dark = Spectrum::new(vec![500.0], vec![0.2]).unwrap();
+        for &test in [Illuminant::A, Illuminant::F2, Illuminant::F11].iter() {
+            let index = light
+                .metamerism_index(&dark, Illuminant::D65, test)
+                .unwrap();
+            assert!(index <= 1e-8, "{}", index);
+        }
+        // a bumpy spectrum looks different from a flat one once the light changes
+        let flat = Spectrum::uniform(380.0, 400.0, vec![0.3, 0.3]).unwrap();
+        let bumpy = Spectrum::uniform(
+            400.0,
+            25.0,
+            vec![
+                0.45, 0.2, 0.4, 0.25, 0.2, 0.35, 0.4, 0.2, 0.3, 0.4, 0.2, 0.3, 0.35,
+            ],
+        )
+        .unwrap();
+        let under_a = flat
+            .metamerism_index(&bumpy, Illuminant::D65, Illuminant::A)
+            .unwrap();
+        let under_f11 = flat
+            .metamerism_index(&bumpy, Illuminant::D65, Illuminant::F11)
+            .unwrap();
+        assert!(
+            under_a > 0.1 && under_f11 > under_a,
+            "{} {}",
+            under_a,
+            under_f11
+        );
+        // the same illuminant undoes the correction
+        let same = flat
+            .metamerism_index(&bumpy, Illuminant::D65, Illuminant::D65)
+            .unwrap();
+        assert!(same <= 1e-8);
+        assert_eq!(
+            flat.metamerism_index(&bumpy, Illuminant::Custom([1.0; 3]), Illuminant::A),
+            Err(SpectrumError::NoSpectralPower)
+        );
+    }
+    #[test]
+    fn test_matches_under() {
+        let gray = Spectrum::new(vec![500.0], vec![0.5]).unwrap();
+        let darker = Spectrum::new(vec![500.0], vec![0.49]).unwrap();
+        assert_eq!(gray.matches_under(&darker, Illuminant::D50, 1.0), Ok(true));
+        assert_eq!(gray.matches_under(&darker, Illuminant::D50, 0.1), Ok(false));
+        assert_eq!(gray.matches_under(&gray, Illuminant::F7, 0.0), Ok(true));
+    }
+    #[test]
+    fn test_from_rgb() {
+        let levels = [0.0, 0.3, 0.7, 1.0];
+        for &r in levels.iter() {
+            for &g in levels.iter() {
+                for &b in levels.iter() {
+                    let rgb = RGBColor { r, g, b };
+                    let spectrum = Spectrum::from_rgb(rgb).unwrap();
+                    assert!(spectrum.values().iter().all(|v| (0.0..=1.0).contains(v)));
+                    let round_trip: RGBColor = spectrum.to_xyz(Illuminant::D65).unwrap().convert();
+                    let max_distance = if [r, g, b] == [1.0; 3] { 0.02 } else { 0.001 };
+                    assert!(rgb.distance(&round_trip) <= max_distance, "{:?}", rgb);
+                }
+            }
+        }
+        // a smooth curve, not a jagged one: green peaks in the middle
+        let green = Spectrum::from_rgb(RGBColor {
+            r: 0.2,
+            g: 0.8,
+            b: 0.3,
+        })
+        .unwrap();
+        let peak = green.values().iter().enumerate().fold(0, |max, (i, &v)| {
+            if v > green.values()[max] {
+                i
+            } else {
+                max
+            }
+        });
+        assert!(green.values()[..peak].windows(2).all(|w| w[0] <= w[1]));
+        assert!(green.values()[peak..].windows(2).all(|w| w[0] >= w[1]));
+        assert!((500.0..=580.0).contains(&green.wavelengths()[peak]));
+        assert_eq!(
+            Spectrum::from_rgb(RGBColor {
+                r: 0.5,
+                g: -0.1,
+                b: 0.5
+            }),
+            Err(SpectrumError::OutOfGamut)
+        );
+        assert_eq!(
+            Spectrum::from_rgb(RGBColor {
+                r: f64::NAN,
+                g: 0.5,
+                b: 0.5
+            }),
+            Err(SpectrumError::OutOfGamut)
+        );
+    }
+}
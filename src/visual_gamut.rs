@@ -1,40 +1,174 @@
 //! This file implements a rather complex and involved function: one that finds the closest color
-//! visible by the human eye to a given color.
+//! visible by the human eye to a given color. The colors people can see are exactly the mixtures of
+//! monochromatic lights, so in a chromaticity diagram they fill the convex hull of the spectral
+//! locus, the curve traced out by the color matching functions. That hull is computed once for each
+//! observer, in CIE 1976 u'v' coordinates, from the data compiled into Scarlet.
 use color::XYZColor;
+use colorpoint::ColorCalcError;
 use illuminants::Illuminant;
+use observer::StandardObserver;
 
-use super::csv;
+use super::geo::prelude::*;
+use super::geo::{Closest, LineString, Point, Polygon};
 
-use std::error::Error;
-use std::path::Path;
+/// How far outside the spectral locus, in u'v', a chromaticity can be and still count as real. This
+/// allows for rounding error in conversions, so that spectral colors themselves are real.
+const LOCUS_TOLERANCE: f64 = 1e-9;
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Record {
-    wavelength: u16,
-    xbar: f64,
-    ybar: f64,
-    zbar: f64,
+// first, read in spectral color data for the given observer
+pub fn read_cie_spectral_data(observer: StandardObserver) -> (Vec<u16>, Vec<XYZColor>) {
+    let cmfs = observer.cmfs();
+    let xyz_data = cmfs
+        .values
+        .iter()
+        .map(|cmf| XYZColor {
+            x: cmf[0],
+            y: cmf[1],
+            z: cmf[2],
+            illuminant: Illuminant::D50,
+        })
+        .collect();
+    (cmfs.wavelengths.clone(), xyz_data)
+}
+
+/// Gets the CIE 1976 u'v' chromaticity of the given XYZ values, or `None` if it's undefined
+/// because `X + 15Y + 3Z` is 0. The explicit formulae are used instead of CIELUVColor to reduce
+/// rounding errors.
+fn uv_prime(xyz: [f64; 3]) -> Option<(f64, f64)> {
+    let denom = xyz[0] + 15.0 * xyz[1] + 3.0 * xyz[2];
+    if denom == 0.0 {
+        None
+    } else {
+        Some((4.0 * xyz[0] / denom, 9.0 * xyz[1] / denom))
+    }
+}
+
+/// Computes the region of real chromaticities for the given observer.
+fn spectral_locus(observer: StandardObserver) -> Polygon<f64> {
+    let (_wavelengths, xyz_data) = read_cie_spectral_data(observer);
+    let uv_data: Vec<(f64, f64)> = xyz_data
+        .into_iter()
+        .filter_map(|xyz| uv_prime([xyz.x, xyz.y, xyz.z]))
+        .collect();
+    // the ends of the locus are noisy, so the hull is cleaner than the locus itself, and closing it
+    // gives the line of purples
+    let line: LineString<f64> = uv_data.into();
+    line.convex_hull()
+}
+
+lazy_static! {
+    static ref SPECTRAL_LOCUS_1931: Polygon<f64> = spectral_locus(StandardObserver::CIE1931);
+    static ref SPECTRAL_LOCUS_1964: Polygon<f64> = spectral_locus(StandardObserver::CIE1964);
+}
+
+/// Gets the precomputed region of real chromaticities for the given observer.
+fn locus(observer: StandardObserver) -> &'static Polygon<f64> {
+    match observer {
+        StandardObserver::CIE1931 => &SPECTRAL_LOCUS_1931,
+        StandardObserver::CIE1964 => &SPECTRAL_LOCUS_1964,
+    }
+}
+
+/// Checks that the XYZ values are finite, so that their chromaticity can be found if they aren't
+/// black.
+fn check_finite(xyz: [f64; 3]) -> Result<(), ColorCalcError> {
+    if xyz.iter().all(|c| c.is_finite()) {
+        Ok(())
+    } else {
+        Err(ColorCalcError::UndefinedChromaticity)
+    }
+}
+
+/// Returns whether the XYZ values are outside the range of human vision for the given observer.
+/// Black is real, and anything with negative luminance is imaginary.
+pub(crate) fn is_imaginary(
+    xyz: [f64; 3],
+    observer: StandardObserver,
+) -> Result<bool, ColorCalcError> {
+    check_finite(xyz)?;
+    if xyz == [0.0; 3] {
+        return Ok(false);
+    } else if xyz[1] < 0.0 {
+        return Ok(true);
+    }
+    let (u, v) = uv_prime(xyz).ok_or(ColorCalcError::UndefinedChromaticity)?;
+    Ok(Point::new(u, v).euclidean_distance(locus(observer)) > LOCUS_TOLERANCE)
 }
 
-// first, read in spectral color data
-pub fn read_cie_spectral_data() -> (Vec<u16>, Vec<XYZColor>) {
-    let mut wavelengths = vec![];
-    let mut xyz_data = vec![];
-    let path = Path::new("cie-1931-standard-matching.csv");
-    let mut reader = match csv::Reader::from_path(path) {
-        Err(e) => panic!("CIE spectral data could not be read: {}", e.description()),
-        Ok(rdr) => rdr,
+/// Gets the XYZ values of the closest real color to the given ones for the given observer: the
+/// color with the same luminance and the closest chromaticity in u'v'. Colors with negative
+/// luminance become black, and real colors are returned unchanged.
+pub(crate) fn closest_real_color(
+    xyz: [f64; 3],
+    observer: StandardObserver,
+) -> Result<[f64; 3], ColorCalcError> {
+    if !is_imaginary(xyz, observer)? {
+        return Ok(xyz);
+    } else if xyz[1] <= 0.0 {
+        return Ok([0.0; 3]);
+    }
+    let (u, v) = uv_prime(xyz).ok_or(ColorCalcError::UndefinedChromaticity)?;
+    let closest = match locus(observer).closest_point(&Point::new(u, v)) {
+        Closest::Intersection(p) | Closest::SinglePoint(p) => p,
+        Closest::Indeterminate => return Err(ColorCalcError::UndefinedChromaticity),
     };
-    for result in reader.deserialize() {
-        // we should panic on bad data: this file is supplied by us!
-        let record: Record = result.unwrap();
-        wavelengths.push(record.wavelength);
-        xyz_data.push(XYZColor {
-            x: record.xbar,
-            y: record.ybar,
-            z: record.zbar,
-            illuminant: Illuminant::D50,
-        });
+    // keep the luminance, and convert back from u'v'
+    let (u, v, y) = (closest.x(), closest.y(), xyz[1]);
+    Ok([
+        y * 9.0 * u / (4.0 * v),
+        y,
+        y * (12.0 - 3.0 * u - 20.0 * v) / (4.0 * v),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_spectral_colors_are_real() {
+        for &observer in [StandardObserver::CIE1931, StandardObserver::CIE1964].iter() {
+            let (wavelengths, xyz_data) = read_cie_spectral_data(observer);
+            assert_eq!(wavelengths.len(), 95);
+            for xyz in xyz_data.into_iter().skip(8).take(70) {
+                assert!(!is_imaginary([xyz.x, xyz.y, xyz.z], observer).unwrap());
+                // nudged off the locus, the closest real color is back on it
+                let outside = [xyz.x * 1.1, xyz.y, xyz.z * 0.9 - 0.01 * xyz.y];
+                let closest = closest_real_color(outside, observer).unwrap();
+                assert!(!is_imaginary(closest, observer).unwrap());
+            }
+        }
+    }
+    #[test]
+    fn test_is_imaginary() {
+        let observer = StandardObserver::CIE1931;
+        assert_eq!(is_imaginary([0.95047, 1.0, 1.08884], observer), Ok(false));
+        assert_eq!(is_imaginary([0.0; 3], observer), Ok(false));
+        assert_eq!(is_imaginary([1.0, 1.0, -0.5], observer), Ok(true));
+        assert_eq!(is_imaginary([0.0, -0.1, 0.0], observer), Ok(true));
+        // the primaries of ProPhoto RGB are imaginary
+        assert_eq!(is_imaginary([0.0, 0.0, 0.8251], observer), Ok(true));
+        assert_eq!(is_imaginary([0.1, 0.0, 0.0], observer), Ok(true));
+        assert_eq!(
+            is_imaginary([f64::NAN, 1.0, 1.0], observer),
+            Err(ColorCalcError::UndefinedChromaticity)
+        );
+        assert_eq!(
+            is_imaginary([-1.5, 0.0, 0.5], observer),
+            Err(ColorCalcError::UndefinedChromaticity)
+        );
+    }
+    #[test]
+    fn test_closest_real_color() {
+        let observer = StandardObserver::CIE1931;
+        let white = [0.95047, 1.0, 1.08884];
+        assert_eq!(closest_real_color(white, observer), Ok(white));
+        assert_eq!(closest_real_color([1.0, -1.0, 1.0], observer), Ok([0.0; 3]));
+        let real = closest_real_color([0.2, 0.5, 0.0], observer).unwrap();
+        assert!((real[1] - 0.5).abs() <= 1e-10);
+        assert!(!is_imaginary(real, observer).unwrap());
+        // close to the locus, so not much has changed
+        assert!((real[0] - 0.2).abs() <= 0.1);
     }
-    (wavelengths, xyz_data)
 }